@@ -1,20 +1,66 @@
+use std::{
+    sync::mpsc::Receiver,
+    time::{Duration, Instant},
+};
+
 use eframe::egui::{self, FontId, RichText};
 use log::error;
 
 use crate::{
-    components::{setup_dialog::{SetupDialog, SetupDialogResult}, video_grid::VideoGrid},
-    data::db::YoutubeDatabase,
+    components::{
+        filter_rules_panel::{FilterRulesPanel, FilterRulesPanelResult},
+        setup_dialog::{SetupDialog, SetupDialogResult},
+        video_grid::VideoGrid,
+    },
+    data::{
+        db::YoutubeDatabase,
+        error::DataError,
+        grouping::GroupBy,
+        shortcuts::{KeyBindings, ShortcutAction},
+        source::SourceKind,
+        ui_state::{FilterPreset, UiState, ViewMode},
+    },
 };
 
+/// ID of the filter box, so the focus shortcut can find it.
+const FILTER_ID: &str = "filter_text";
+
+/// Explains the filter box's query syntax.
+const FILTER_SYNTAX_HELP: &str = "Words and \"quoted phrases\" search titles and puzzles.\n\
+    Fields: setter:, title:, channel:, tag:, notes:, duration:<30m, year:>=2021\n\
+    completed matches completed videos; put - in front of a term to exclude it.";
+
+/// Explains the filter box in description search mode.
+const SEARCH_HELP: &str = "Finds videos whose title or description contains every word and \"quoted phrase\".";
+
+/// How often at most the UI state is written to the database.
+const UI_STATE_SAVE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Smallest window width or height worth saving, so a minimised window isn't restored as 0×0.
+const MIN_SAVED_WINDOW_SIZE: f32 = 100.0;
+
 /// Main application struct for the Cracking the Cryptic Tracker.
 pub struct CtcTrackerApp {
     video_grid: VideoGrid,
     setup_dialog: Option<SetupDialog>,
-    api_key_receiver: std::sync::mpsc::Receiver<Option<String>>,
+    filter_rules_panel: Option<FilterRulesPanel>,
+    api_key_receiver: std::sync::mpsc::Receiver<(Option<String>, SourceKind)>,
     api_key_loaded: bool,
+    saved_ui_state: UiState,
+    last_ui_state_save: Instant,
+    /// UI state waiting for the save interval to pass, saved on exit if the app closes first.
+    unsaved_ui_state: Option<UiState>,
+    scroll_offset: f32,
+    pending_scroll_offset: Option<f32>,
+    filter_presets: Vec<FilterPreset>,
+    presets_receiver: Receiver<Vec<FilterPreset>>,
+    preset_name: String,
+    key_bindings: KeyBindings,
+    key_bindings_receiver: Receiver<KeyBindings>,
+    shortcut_help_open: bool,
 }
 impl CtcTrackerApp {
-    pub fn new(db: YoutubeDatabase) -> Self {
+    pub fn new(db: YoutubeDatabase, ui_state: UiState) -> Self {
         // Try to get API key from environment variable first, filtering out empty/whitespace values
         let env_api_key = std::env::var("CTC_API_KEY")
             .ok()
@@ -23,44 +69,290 @@ impl CtcTrackerApp {
         // Create a channel to receive the API key from the database
         let (sender, receiver) = std::sync::mpsc::channel();
 
-        // Spawn a task to load the API key from the database
+        // Spawn a task to load the API key and video source from the database
         let db_clone = db.clone();
         tokio::spawn(async move {
-            match db_clone.get_api_key().await {
-                Ok(api_key) => {
-                    sender.send(api_key).ok();
+            let api_key = db_clone.get_api_key().await.unwrap_or_else(|e| {
+                error!("Error loading API key from database: {e}");
+                None
+            });
+            let source_kind = db_clone.get_video_source().await.unwrap_or_else(|e| {
+                error!("Error loading video source from database: {e}");
+                SourceKind::default()
+            });
+            sender.send((api_key, source_kind)).ok();
+        });
+
+        // Load the saved filter presets
+        let (presets_sender, presets_receiver) = std::sync::mpsc::channel();
+        let db_clone = db.clone();
+        tokio::spawn(async move {
+            match db_clone.get_filter_presets().await {
+                Ok(presets) => {
+                    presets_sender.send(presets).ok();
                 }
-                Err(e) => {
-                    error!("Error loading API key from database: {e}");
-                    sender.send(None).ok();
+                Err(e) => error!("Error loading filter presets from database: {e}"),
+            }
+        });
+
+        // Load the user's keyboard shortcuts
+        let (key_bindings_sender, key_bindings_receiver) = std::sync::mpsc::channel();
+        let db_clone = db.clone();
+        tokio::spawn(async move {
+            match db_clone.get_key_bindings().await {
+                Ok(bindings) => {
+                    key_bindings_sender.send(bindings).ok();
                 }
+                Err(e) => error!("Error loading key bindings from database: {e}"),
             }
         });
 
-        let video_grid: VideoGrid = VideoGrid::new(env_api_key, db.clone());
+        let mut video_grid: VideoGrid = VideoGrid::new(env_api_key, db.clone());
+        video_grid.apply_filter_settings(ui_state.filter.clone());
+        video_grid.view_mode = ui_state.view_mode;
+        video_grid.group_by = ui_state.group_by;
         let setup_dialog = Some(SetupDialog::new(db));
 
         Self {
             video_grid,
             setup_dialog,
+            filter_rules_panel: None,
             api_key_receiver: receiver,
             api_key_loaded: false,
+            scroll_offset: ui_state.scroll_offset,
+            pending_scroll_offset: Some(ui_state.scroll_offset).filter(|offset| *offset > 0.0),
+            saved_ui_state: ui_state,
+            last_ui_state_save: Instant::now(),
+            unsaved_ui_state: None,
+            filter_presets: Vec::new(),
+            presets_receiver,
+            preset_name: String::new(),
+            key_bindings: KeyBindings::default(),
+            key_bindings_receiver,
+            shortcut_help_open: false,
         }
     }
+
+    /// Runs the actions of any shortcut keys pressed while no text field or dialog has focus.
+    fn handle_shortcuts(&mut self, ctx: &egui::Context) {
+        if ctx.wants_keyboard_input() || self.video_grid.is_playing() {
+            return;
+        }
+
+        // Undo and redo use the usual keys rather than configurable ones
+        let (undo, redo) = ctx.input_mut(|input| {
+            let redo = input.consume_key(egui::Modifiers::COMMAND, egui::Key::Y)
+                || input.consume_key(egui::Modifiers::COMMAND | egui::Modifiers::SHIFT, egui::Key::Z);
+            (input.consume_key(egui::Modifiers::COMMAND, egui::Key::Z), redo)
+        });
+        if undo {
+            self.video_grid.undo();
+        }
+        if redo {
+            self.video_grid.redo();
+        }
+
+        for action in ShortcutAction::ALL {
+            let Some(key) = egui::Key::from_name(self.key_bindings.key(action)) else {
+                continue;
+            };
+            if !ctx.input_mut(|input| input.consume_key(egui::Modifiers::NONE, key)) {
+                continue;
+            }
+
+            match action {
+                ShortcutAction::FocusFilter => {
+                    ctx.memory_mut(|memory| memory.request_focus(egui::Id::new(FILTER_ID)));
+                    // Don't type the shortcut into the filter box
+                    ctx.input_mut(|input| input.events.retain(|event| !matches!(event, egui::Event::Text(_))));
+                }
+                ShortcutAction::NextVideo => self.video_grid.move_selection(1),
+                ShortcutAction::PreviousVideo => self.video_grid.move_selection(-1),
+                ShortcutAction::ToggleCompleted => self.video_grid.toggle_selected_completion(),
+                ShortcutAction::OpenVideo => {
+                    if let Some(video) = self.video_grid.selected_video() {
+                        ctx.open_url(egui::OpenUrl::new_tab(video.get_video_url()));
+                    }
+                }
+                ShortcutAction::OpenPuzzle => {
+                    if let Some(link) = self.video_grid.selected_video().and_then(|video| video.primary_link()) {
+                        ctx.open_url(egui::OpenUrl::new_tab(&link.url));
+                    }
+                }
+                ShortcutAction::Refresh => self.video_grid.refresh_videos(),
+                ShortcutAction::ShowHelp => self.shortcut_help_open = !self.shortcut_help_open,
+            }
+        }
+    }
+
+    /// Shows the overlay listing the keyboard shortcuts.
+    fn show_shortcut_help(&mut self, ctx: &egui::Context) {
+        let mut open = self.shortcut_help_open;
+        egui::Window::new("Keyboard Shortcuts")
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                egui::Grid::new("shortcut_help")
+                    .num_columns(2)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for action in ShortcutAction::ALL {
+                            ui.label(RichText::new(self.key_bindings.key(action)).monospace().strong());
+                            ui.label(action.description());
+                            ui.end_row();
+                        }
+                    });
+                ui.add_space(6.0);
+                ui.label(RichText::new("↑/↓ also move between videos. Change shortcuts in ⚙ Settings.").small());
+            });
+        self.shortcut_help_open = open && !ctx.input(|input| input.key_pressed(egui::Key::Escape));
+    }
+
+    /// Returns the current filters, window size and scroll position.
+    fn ui_state(&self, ctx: &egui::Context) -> UiState {
+        let window_size = ctx
+            .input(|i| i.viewport().inner_rect)
+            .filter(|rect| rect.width() >= MIN_SAVED_WINDOW_SIZE && rect.height() >= MIN_SAVED_WINDOW_SIZE)
+            .map(|rect| [rect.width(), rect.height()])
+            .or(self.saved_ui_state.window_size);
+        UiState {
+            filter: self.video_grid.filter_settings(),
+            window_size,
+            // Keep the saved position until it has been restored
+            scroll_offset: self.pending_scroll_offset.unwrap_or(self.scroll_offset),
+            view_mode: self.video_grid.view_mode,
+            group_by: self.video_grid.group_by,
+        }
+    }
+
+    /// Saves the UI state when it has changed, at most once per `UI_STATE_SAVE_INTERVAL`.
+    fn save_ui_state(&mut self, ctx: &egui::Context) {
+        let ui_state = self.ui_state(ctx);
+        if ui_state == self.saved_ui_state {
+            self.unsaved_ui_state = None;
+            return;
+        }
+
+        let since_last_save = self.last_ui_state_save.elapsed();
+        if since_last_save < UI_STATE_SAVE_INTERVAL {
+            // Come back to save the latest change once the interval has passed
+            ctx.request_repaint_after(UI_STATE_SAVE_INTERVAL - since_last_save);
+            self.unsaved_ui_state = Some(ui_state);
+            return;
+        }
+
+        let db = self.video_grid.yt_db.clone();
+        let ui_state_clone = ui_state.clone();
+        tokio::spawn(async move {
+            if let Err(e) = db.set_ui_state(&ui_state_clone).await {
+                error!("Error saving UI state: {e}");
+            }
+        });
+        self.saved_ui_state = ui_state;
+        self.last_ui_state_save = Instant::now();
+        self.unsaved_ui_state = None;
+    }
+
+    /// Saves the filter presets to the database.
+    fn save_filter_presets(&self) {
+        let db = self.video_grid.yt_db.clone();
+        let presets = self.filter_presets.clone();
+        tokio::spawn(async move {
+            if let Err(e) = db.set_filter_presets(&presets).await {
+                error!("Error saving filter presets: {e}");
+            }
+        });
+    }
+
+    /// Shows the menu for applying, saving and deleting filter presets.
+    fn show_presets_menu(&mut self, ui: &mut egui::Ui) {
+        ui.menu_button("⭐ Presets", |ui| {
+            let mut deleted = None;
+            for (index, preset) in self.filter_presets.iter().enumerate() {
+                ui.horizontal(|ui| {
+                    if ui.button(&preset.name).clicked() {
+                        self.video_grid.apply_filter_settings(preset.filter.clone());
+                        ui.close_menu();
+                    }
+                    if ui.small_button("🗑").on_hover_text("Delete preset").clicked() {
+                        deleted = Some(index);
+                    }
+                });
+            }
+            if let Some(index) = deleted {
+                self.filter_presets.remove(index);
+                self.save_filter_presets();
+            }
+            if self.filter_presets.is_empty() {
+                ui.label("No saved presets");
+            }
+
+            ui.separator();
+            ui.horizontal(|ui| {
+                ui.add(egui::TextEdit::singleline(&mut self.preset_name).hint_text("Preset name"));
+                let name = self.preset_name.trim().to_string();
+                if ui
+                    .add_enabled(!name.is_empty(), egui::Button::new("Save current filters"))
+                    .clicked()
+                {
+                    let preset = FilterPreset {
+                        name,
+                        filter: self.video_grid.filter_settings(),
+                    };
+                    // Saving under an existing name replaces that preset
+                    match self.filter_presets.iter_mut().find(|p| p.name == preset.name) {
+                        Some(existing) => *existing = preset,
+                        None => self.filter_presets.push(preset),
+                    }
+                    self.save_filter_presets();
+                    self.preset_name.clear();
+                    ui.close_menu();
+                }
+            });
+        });
+    }
 }
 impl eframe::App for CtcTrackerApp {
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        // Save the last changes if the app closes before the save interval has passed
+        let Some(ui_state) = self.unsaved_ui_state.take() else {
+            return;
+        };
+        let db = self.video_grid.yt_db.clone();
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                if let Err(e) = db.set_ui_state(&ui_state).await {
+                    error!("Error saving UI state: {e}");
+                }
+            })
+        });
+    }
+
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        if let Ok(key_bindings) = self.key_bindings_receiver.try_recv() {
+            self.key_bindings = key_bindings;
+        }
+
+        if let Ok(mut presets) = self.presets_receiver.try_recv() {
+            // Keep presets saved while the stored ones were loading
+            presets.append(&mut self.filter_presets);
+            self.filter_presets = presets;
+        }
+
         // Check if we've received the API key from the database
         if !self.api_key_loaded {
-            if let Ok(db_api_key) = self.api_key_receiver.try_recv() {
+            if let Ok((db_api_key, source_kind)) = self.api_key_receiver.try_recv() {
                 self.api_key_loaded = true;
+                self.video_grid.source_kind = source_kind;
                 // Filter out empty/whitespace API keys from the database
                 if let Some(api_key) = db_api_key.filter(|key| !key.trim().is_empty()) {
                     // API key found in database, use it
                     self.video_grid.set_api_key(Some(api_key));
                     self.setup_dialog = None; // No need for setup dialog
-                } else if self.video_grid.has_api_key() {
-                    // API key from environment variable
+                } else if self.video_grid.has_video_source() {
+                    // API key from environment variable, or the public feed needs no key
                     self.setup_dialog = None;
                 }
             }
@@ -68,12 +360,34 @@ impl eframe::App for CtcTrackerApp {
 
         // Show setup dialog if API key is not set
         if let Some(setup_dialog) = &mut self.setup_dialog {
-            match setup_dialog.show(ctx) {
+            let result = setup_dialog.show(ctx);
+            if !matches!(result, SetupDialogResult::Showing) && setup_dialog.sources_changed() {
+                // Sync newly added or re-enabled channels and playlists, and re-extract links
+                self.video_grid.refresh_videos();
+            }
+            if let Some(key_bindings) = setup_dialog.saved_key_bindings() {
+                self.key_bindings = key_bindings.clone();
+            }
+            match result {
                 SetupDialogResult::Saved(api_key) => {
                     // User has entered an API key
+                    self.video_grid.source_kind = SourceKind::Api;
                     self.video_grid.set_api_key(Some(api_key));
                     self.setup_dialog = None;
                 }
+                SetupDialogResult::UseFeed => {
+                    // User chose the public feed
+                    self.video_grid.set_source_kind(SourceKind::Feed);
+                    self.setup_dialog = None;
+                }
+                SetupDialogResult::ImportCatalogue(path) => {
+                    self.video_grid.import_catalogue(path, ctx.clone());
+                    self.setup_dialog = None;
+                }
+                SetupDialogResult::ExportCatalogue(path) => {
+                    self.video_grid.export_catalogue(path);
+                    self.setup_dialog = None;
+                }
                 SetupDialogResult::Cancelled => {
                     // User cancelled - clear the error and close dialog
                     self.video_grid.api_error = None;
@@ -81,24 +395,53 @@ impl eframe::App for CtcTrackerApp {
                 }
                 SetupDialogResult::Showing => {
                     // Dialog still showing, don't show main UI for initial setup
-                    if !self.video_grid.has_api_key() {
+                    if !self.video_grid.has_video_source() {
                         return;
                     }
                 }
             }
         }
 
-        // Check if there's an API error and show settings dialog
-        if self.video_grid.api_error.is_some() && self.setup_dialog.is_none() {
+        // Only reopen the settings dialog for genuine API key errors
+        let api_key_rejected = self
+            .video_grid
+            .api_error
+            .as_ref()
+            .is_some_and(|error| error.is_invalid_api_key());
+        if api_key_rejected && self.setup_dialog.is_none() {
             // Open the settings dialog to let the user update their API key
-            if let Some(api_key) = self.video_grid.api_key.clone() {
-                self.setup_dialog = Some(SetupDialog::new_editing(
-                    self.video_grid.yt_db.clone(),
-                    api_key,
-                ));
+            self.setup_dialog = Some(SetupDialog::new_editing(
+                self.video_grid.yt_db.clone(),
+                self.video_grid.api_key.clone(),
+                self.video_grid.source_kind,
+            ));
+        }
+
+        // Show the filter rules panel if it's open
+        if let Some(filter_rules_panel) = &mut self.filter_rules_panel {
+            match filter_rules_panel.show(ctx) {
+                FilterRulesPanelResult::Saved(rules) => {
+                    self.video_grid.set_filter_rules(rules);
+                    self.filter_rules_panel = None;
+                }
+                FilterRulesPanelResult::Cancelled => {
+                    self.filter_rules_panel = None;
+                }
+                FilterRulesPanelResult::Showing => {}
             }
         }
 
+        // Shortcuts are only for the main window, not while a dialog is open
+        if self.setup_dialog.is_none() && self.filter_rules_panel.is_none() {
+            self.handle_shortcuts(ctx);
+        }
+        if self.shortcut_help_open {
+            self.show_shortcut_help(ctx);
+        }
+
+        // The detail panel has to be added before the central panel it sits beside
+        self.video_grid.show_detail_panel(ctx);
+
         egui::CentralPanel::default().show(ctx, |ui| {
             ui.with_layout(
                 egui::Layout::top_down_justified(egui::Align::Center),
@@ -119,41 +462,140 @@ impl eframe::App for CtcTrackerApp {
                         }
 
                         let without_links_button_text = if self.video_grid.show_without_links {
-                            "Hide videos without links"
+                            "Hide videos without puzzles"
                         } else {
-                            "Show videos without links"
+                            "Show videos without puzzles"
                         };
                         if ui.button(without_links_button_text).clicked() {
                             self.video_grid.show_without_links =
                                 !self.video_grid.show_without_links;
                         }
+                        let channel_filter_text = self
+                            .video_grid
+                            .channel_filter
+                            .as_ref()
+                            .and_then(|id| self.video_grid.channels().iter().find(|c| c.id == *id))
+                            .map(|channel| channel.display_name.clone())
+                            .unwrap_or_else(|| "All channels".to_string());
+                        let channels = self.video_grid.channels().to_vec();
+                        egui::ComboBox::from_id_salt("channel_filter")
+                            .selected_text(channel_filter_text)
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(&mut self.video_grid.channel_filter, None, "All channels");
+                                for channel in channels.iter().filter(|channel| channel.enabled) {
+                                    ui.selectable_value(
+                                        &mut self.video_grid.channel_filter,
+                                        Some(channel.id.clone()),
+                                        &channel.display_name,
+                                    );
+                                }
+                            });
+
+                        let playlist_filter_text = self
+                            .video_grid
+                            .playlist_filter
+                            .as_ref()
+                            .and_then(|id| self.video_grid.playlists().iter().find(|p| p.id == *id))
+                            .map(|playlist| playlist.title.clone())
+                            .unwrap_or_else(|| "All playlists".to_string());
+                        let playlists = self.video_grid.playlists().to_vec();
+                        egui::ComboBox::from_id_salt("playlist_filter")
+                            .selected_text(playlist_filter_text)
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(&mut self.video_grid.playlist_filter, None, "All playlists");
+                                for playlist in playlists.iter().filter(|playlist| playlist.enabled) {
+                                    ui.selectable_value(
+                                        &mut self.video_grid.playlist_filter,
+                                        Some(playlist.id.clone()),
+                                        &playlist.title,
+                                    );
+                                }
+                            });
+                        if let Some(playlist_id) = self.video_grid.playlist_filter.clone() {
+                            let (completed, total) = self.video_grid.playlist_progress(&playlist_id);
+                            ui.label(format!("{completed} / {total} completed"));
+                        }
+
                         ui.label(
                             RichText::new("Filter videos by:").font(FontId::proportional(16.)),
                         );
-                        ui.text_edit_singleline(&mut self.video_grid.filter_text);
+                        let filter_help = if self.video_grid.search_descriptions {
+                            SEARCH_HELP
+                        } else {
+                            FILTER_SYNTAX_HELP
+                        };
+                        ui.add(egui::TextEdit::singleline(&mut self.video_grid.filter_text).id(egui::Id::new(FILTER_ID)))
+                            .on_hover_text(filter_help);
+                        ui.toggle_value(&mut self.video_grid.search_descriptions, "🔎 Descriptions")
+                            .on_hover_text("Search titles and descriptions, best matches first");
+                        self.show_presets_menu(ui);
+
+                        // Switch between the table and the cards
+                        ui.selectable_value(&mut self.video_grid.view_mode, ViewMode::Table, "☰ Table");
+                        ui.selectable_value(&mut self.video_grid.view_mode, ViewMode::Cards, "▦ Cards");
+                        egui::ComboBox::from_id_salt("group_by")
+                            .selected_text(self.video_grid.group_by.label())
+                            .show_ui(ui, |ui| {
+                                for group_by in GroupBy::ALL {
+                                    ui.selectable_value(&mut self.video_grid.group_by, group_by, group_by.label());
+                                }
+                            })
+                            .response
+                            .on_hover_text("Group videos by");
 
                         // Add refresh button
                         if ui.button("🔄 Refresh").clicked() {
                             self.video_grid.refresh_videos();
                         }
 
+                        // Add undo and redo buttons
+                        let undo_description = self.video_grid.undo_description();
+                        let undo_button = ui
+                            .add_enabled(undo_description.is_some(), egui::Button::new("↶ Undo"))
+                            .on_hover_text(format!("Undo: {} (Ctrl+Z)", undo_description.unwrap_or_default()));
+                        if undo_button.clicked() {
+                            self.video_grid.undo();
+                        }
+                        let redo_description = self.video_grid.redo_description();
+                        let redo_button = ui
+                            .add_enabled(redo_description.is_some(), egui::Button::new("↷ Redo"))
+                            .on_hover_text(format!("Redo: {} (Ctrl+Y)", redo_description.unwrap_or_default()));
+                        if redo_button.clicked() {
+                            self.video_grid.redo();
+                        }
+
+                        // Add filter rules button
+                        if ui.button("🚫 Filter Rules").clicked() {
+                            self.filter_rules_panel =
+                                Some(FilterRulesPanel::new(self.video_grid.filter_rules.clone()));
+                        }
+
                         // Add settings button
                         if ui.button("⚙ Settings").clicked() {
-                            if let Some(api_key) = self.video_grid.api_key.clone() {
-                                self.setup_dialog = Some(SetupDialog::new_editing(
-                                    self.video_grid.yt_db.clone(),
-                                    api_key,
-                                ));
-                            }
+                            self.setup_dialog = Some(SetupDialog::new_editing(
+                                self.video_grid.yt_db.clone(),
+                                self.video_grid.api_key.clone(),
+                                self.video_grid.source_kind,
+                            ));
                         }
                     });
 
+                    // Show syntax errors in the filter text under the toolbar
+                    if let Some(error) = self.video_grid.filter_error() {
+                        ui.label(RichText::new(format!("Filter: {error}")).color(egui::Color32::RED));
+                    }
+
                     // Show error message if there's an API error
                     if let Some(error) = self.video_grid.api_error.clone() {
                         let mut dismiss = false;
+                        let message = match &error {
+                            DataError::InvalidApiKey(_) => "Invalid API key. Please check your YouTube API key and try again.".to_string(),
+                            DataError::QuotaExceeded(_) => "YouTube API quota exceeded. Showing locally stored videos; try refreshing later.".to_string(),
+                            other => other.to_string(),
+                        };
                         ui.horizontal(|ui| {
                             ui.label(RichText::new("⚠").color(egui::Color32::RED).font(FontId::proportional(20.)));
-                            ui.label(RichText::new(&error).color(egui::Color32::RED).strong());
+                            ui.label(RichText::new(&message).color(egui::Color32::RED).strong());
                             if ui.button("Dismiss").clicked() {
                                 dismiss = true;
                             }
@@ -164,13 +606,22 @@ impl eframe::App for CtcTrackerApp {
                         ui.add_space(10.0);
                     }
 
-                    egui::scroll_area::ScrollArea::vertical()
-                        .auto_shrink(false)
-                        .show(ui, |ui| {
-                            self.video_grid.update(ui, ctx.clone());
-                        });
+                    let mut scroll_area = egui::scroll_area::ScrollArea::vertical().auto_shrink(false);
+                    // Restore the saved position once there are videos to scroll through
+                    if self.video_grid.is_loaded() {
+                        if let Some(offset) = self.pending_scroll_offset.take() {
+                            scroll_area = scroll_area.vertical_scroll_offset(offset);
+                        }
+                    }
+                    let output = scroll_area.show(ui, |ui| {
+                        self.video_grid.update(ui, ctx.clone());
+                    });
+                    self.scroll_offset = output.state.offset.y;
                 },
             );
         });
+
+        self.video_grid.show_toast(ctx);
+        self.save_ui_state(ctx);
     }
 }
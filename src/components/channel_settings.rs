@@ -0,0 +1,157 @@
+use std::sync::mpsc::{Receiver, Sender};
+
+use eframe::egui::{self, RichText};
+use log::error;
+
+use crate::data::{
+    db::YoutubeDatabase,
+    error::DataError,
+    feed::FeedClient,
+    model::Channel,
+    source::{SourceKind, VideoSource},
+    sync::add_channel,
+    youtube_api::YouTubeClient,
+};
+
+/// Settings section for managing the tracked YouTube channels.
+pub struct ChannelSettings {
+    db: YoutubeDatabase,
+    channels: Vec<Channel>,
+    channels_receiver: Receiver<Vec<Channel>>,
+    add_sender: Sender<Result<Channel, DataError>>,
+    add_receiver: Receiver<Result<Channel, DataError>>,
+    channel_input: String,
+    add_in_progress: bool,
+    add_error: Option<String>,
+    changed: bool,
+}
+impl ChannelSettings {
+    /// Creates a new instance of `ChannelSettings` and loads the channels in the background.
+    pub fn new(db: YoutubeDatabase) -> Self {
+        let (channels_sender, channels_receiver) = std::sync::mpsc::channel();
+        let (add_sender, add_receiver) = std::sync::mpsc::channel();
+
+        let db_clone = db.clone();
+        tokio::spawn(async move {
+            match db_clone.get_channels().await {
+                Ok(channels) => {
+                    channels_sender.send(channels).ok();
+                }
+                Err(e) => error!("Error loading channels: {e}"),
+            }
+        });
+
+        Self {
+            db,
+            channels: Vec::new(),
+            channels_receiver,
+            add_sender,
+            add_receiver,
+            channel_input: String::new(),
+            add_in_progress: false,
+            add_error: None,
+            changed: false,
+        }
+    }
+
+    /// Returns whether any channel was added, removed, enabled or disabled.
+    pub fn changed(&self) -> bool {
+        self.changed
+    }
+
+    /// Shows the channel list and the form for adding a channel.
+    pub fn show(&mut self, ui: &mut egui::Ui, source_kind: SourceKind, api_key: Option<&str>) {
+        if let Ok(channels) = self.channels_receiver.try_recv() {
+            self.channels = channels;
+        }
+
+        if let Ok(result) = self.add_receiver.try_recv() {
+            self.add_in_progress = false;
+            match result {
+                Ok(channel) => {
+                    self.channels.retain(|existing| existing.id != channel.id);
+                    self.channels.push(channel);
+                    self.channel_input.clear();
+                    self.add_error = None;
+                    self.changed = true;
+                }
+                Err(e) => self.add_error = Some(e.to_string()),
+            }
+        }
+
+        ui.label(RichText::new("Channels").strong());
+
+        let mut removed_channel = None;
+        egui::Grid::new("channel_settings_grid")
+            .num_columns(3)
+            .show(ui, |ui| {
+                for channel in &mut self.channels {
+                    if ui.checkbox(&mut channel.enabled, "").changed() {
+                        self.changed = true;
+                        let db = self.db.clone();
+                        let (channel_id, enabled) = (channel.id.clone(), channel.enabled);
+                        tokio::spawn(async move {
+                            if let Err(e) = db.set_channel_enabled(&channel_id, enabled).await {
+                                error!("Error updating channel: {e}");
+                            }
+                        });
+                    }
+                    ui.label(&channel.display_name);
+                    if ui.button("🗑").on_hover_text("Stop tracking this channel").clicked() {
+                        removed_channel = Some(channel.id.clone());
+                    }
+                    ui.end_row();
+                }
+            });
+
+        if let Some(channel_id) = removed_channel {
+            self.channels.retain(|channel| channel.id != channel_id);
+            self.changed = true;
+            let db = self.db.clone();
+            tokio::spawn(async move {
+                if let Err(e) = db.remove_channel(&channel_id).await {
+                    error!("Error removing channel: {e}");
+                }
+            });
+        }
+
+        ui.horizontal(|ui| {
+            ui.label("Add channel:");
+            ui.text_edit_singleline(&mut self.channel_input)
+                .on_hover_text("Channel URL, @handle or channel ID");
+            if self.add_in_progress {
+                ui.spinner();
+            } else if ui.button("Add").clicked() && !self.channel_input.trim().is_empty() {
+                self.add_channel(source_kind, api_key);
+            }
+        });
+
+        if let Some(error) = &self.add_error {
+            ui.label(RichText::new(error).color(egui::Color32::RED));
+        }
+    }
+
+    /// Resolves the entered channel through the configured video source and saves it.
+    fn add_channel(&mut self, source_kind: SourceKind, api_key: Option<&str>) {
+        let source: Box<dyn VideoSource> = match (source_kind, api_key) {
+            (SourceKind::Feed, _) => Box::new(FeedClient::new()),
+            (SourceKind::Api, Some(api_key)) => Box::new(YouTubeClient::new(api_key.to_string())),
+            (SourceKind::Api, None) => {
+                self.add_error = Some("An API key is needed to look up channels.".to_string());
+                return;
+            }
+        };
+
+        self.add_in_progress = true;
+        let sender = self.add_sender.clone();
+        let db = self.db.clone();
+        let input = self.channel_input.trim().to_string();
+        tokio::spawn(async move {
+            let result = add_channel(source.as_ref(), &db, &input).await;
+            if let Err(e) = &result {
+                error!("Error adding channel {input}: {e}");
+            }
+            sender.send(result).ok();
+        });
+    }
+}
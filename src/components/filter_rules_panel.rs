@@ -0,0 +1,111 @@
+use eframe::egui::{self, RichText};
+
+use crate::data::rules::{FilterRule, MatchKind, RuleAction, RuleField};
+
+/// Result of showing the filter rules panel
+#[derive(Debug, Clone)]
+pub enum FilterRulesPanelResult {
+    /// Panel is still being shown
+    Showing,
+    /// User saved the edited rules
+    Saved(Vec<FilterRule>),
+    /// User closed the panel without saving
+    Cancelled,
+}
+
+/// Settings panel for editing the rules that hide videos from the grid.
+pub struct FilterRulesPanel {
+    rules: Vec<FilterRule>,
+}
+impl FilterRulesPanel {
+    /// Creates a new instance of `FilterRulesPanel` editing a copy of the given rules.
+    pub fn new(rules: Vec<FilterRule>) -> Self {
+        Self { rules }
+    }
+
+    /// Shows the panel and returns the result.
+    pub fn show(&mut self, ctx: &egui::Context) -> FilterRulesPanelResult {
+        let mut result = FilterRulesPanelResult::Showing;
+        let mut removed_rule = None;
+
+        egui::Window::new("Filter Rules")
+            .collapsible(false)
+            .resizable(true)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.label("Exclude rules hide matching videos; include rules bring them back.");
+                ui.label("Hidden videos are still stored, so changes apply without refreshing.");
+                ui.add_space(10.0);
+
+                egui::Grid::new("filter_rules_grid")
+                    .striped(true)
+                    .num_columns(6)
+                    .show(ui, |ui| {
+                        ui.label(RichText::new("Enabled").strong());
+                        ui.label(RichText::new("Action").strong());
+                        ui.label(RichText::new("Field").strong());
+                        ui.label(RichText::new("Match").strong());
+                        ui.label(RichText::new("Pattern").strong());
+                        ui.label("");
+                        ui.end_row();
+
+                        for (index, rule) in self.rules.iter_mut().enumerate() {
+                            ui.checkbox(&mut rule.enabled, "");
+
+                            egui::ComboBox::from_id_salt(("rule_action", index))
+                                .selected_text(rule.action.as_str())
+                                .show_ui(ui, |ui| {
+                                    ui.selectable_value(&mut rule.action, RuleAction::Exclude, "exclude");
+                                    ui.selectable_value(&mut rule.action, RuleAction::Include, "include");
+                                });
+
+                            egui::ComboBox::from_id_salt(("rule_field", index))
+                                .selected_text(rule.field.as_str())
+                                .show_ui(ui, |ui| {
+                                    ui.selectable_value(&mut rule.field, RuleField::Title, "title");
+                                    ui.selectable_value(&mut rule.field, RuleField::Description, "description");
+                                });
+
+                            egui::ComboBox::from_id_salt(("rule_match", index))
+                                .selected_text(rule.match_kind.as_str())
+                                .show_ui(ui, |ui| {
+                                    ui.selectable_value(&mut rule.match_kind, MatchKind::Substring, "substring");
+                                    ui.selectable_value(&mut rule.match_kind, MatchKind::Regex, "regex");
+                                });
+
+                            ui.vertical(|ui| {
+                                ui.text_edit_singleline(&mut rule.pattern);
+                                if let Err(e) = rule.compile() {
+                                    ui.label(RichText::new(format!("Invalid regex: {e}")).color(egui::Color32::RED).small());
+                                }
+                            });
+
+                            if ui.button("🗑").on_hover_text("Remove rule").clicked() {
+                                removed_rule = Some(index);
+                            }
+                            ui.end_row();
+                        }
+                    });
+
+                if ui.button("➕ Add rule").clicked() {
+                    self.rules.push(FilterRule::exclude_title(""));
+                }
+
+                ui.add_space(10.0);
+                ui.horizontal(|ui| {
+                    if ui.button("Save").clicked() {
+                        result = FilterRulesPanelResult::Saved(self.rules.clone());
+                    }
+                    if ui.button("Cancel").clicked() {
+                        result = FilterRulesPanelResult::Cancelled;
+                    }
+                });
+            });
+
+        if let Some(index) = removed_rule {
+            self.rules.remove(index);
+        }
+
+        result
+    }
+}
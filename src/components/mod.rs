@@ -1,2 +1,10 @@
+pub mod channel_settings;
+pub mod filter_rules_panel;
+pub mod playlist_settings;
+pub mod puzzle_hosts_settings;
+pub mod shortcut_settings;
+pub mod sudoku_player;
+pub mod thumbnails;
+pub mod video_detail;
 pub mod video_grid;
 pub mod setup_dialog;
@@ -0,0 +1,157 @@
+use std::sync::mpsc::{Receiver, Sender};
+
+use eframe::egui::{self, RichText};
+use log::error;
+
+use crate::data::{
+    db::YoutubeDatabase,
+    error::DataError,
+    feed::FeedClient,
+    model::Playlist,
+    source::{SourceKind, VideoSource},
+    sync::add_playlist,
+    youtube_api::YouTubeClient,
+};
+
+/// Settings section for managing the playlists synced as collections.
+pub struct PlaylistSettings {
+    db: YoutubeDatabase,
+    playlists: Vec<Playlist>,
+    playlists_receiver: Receiver<Vec<Playlist>>,
+    add_sender: Sender<Result<Playlist, DataError>>,
+    add_receiver: Receiver<Result<Playlist, DataError>>,
+    playlist_input: String,
+    add_in_progress: bool,
+    add_error: Option<String>,
+    changed: bool,
+}
+impl PlaylistSettings {
+    /// Creates a new instance of `PlaylistSettings` and loads the playlists in the background.
+    pub fn new(db: YoutubeDatabase) -> Self {
+        let (playlists_sender, playlists_receiver) = std::sync::mpsc::channel();
+        let (add_sender, add_receiver) = std::sync::mpsc::channel();
+
+        let db_clone = db.clone();
+        tokio::spawn(async move {
+            match db_clone.get_playlists().await {
+                Ok(playlists) => {
+                    playlists_sender.send(playlists).ok();
+                }
+                Err(e) => error!("Error loading playlists: {e}"),
+            }
+        });
+
+        Self {
+            db,
+            playlists: Vec::new(),
+            playlists_receiver,
+            add_sender,
+            add_receiver,
+            playlist_input: String::new(),
+            add_in_progress: false,
+            add_error: None,
+            changed: false,
+        }
+    }
+
+    /// Returns whether any playlist was added, removed, enabled or disabled.
+    pub fn changed(&self) -> bool {
+        self.changed
+    }
+
+    /// Shows the playlist list and the form for adding a playlist.
+    pub fn show(&mut self, ui: &mut egui::Ui, source_kind: SourceKind, api_key: Option<&str>) {
+        if let Ok(playlists) = self.playlists_receiver.try_recv() {
+            self.playlists = playlists;
+        }
+
+        if let Ok(result) = self.add_receiver.try_recv() {
+            self.add_in_progress = false;
+            match result {
+                Ok(playlist) => {
+                    self.playlists.retain(|existing| existing.id != playlist.id);
+                    self.playlists.push(playlist);
+                    self.playlist_input.clear();
+                    self.add_error = None;
+                    self.changed = true;
+                }
+                Err(e) => self.add_error = Some(e.to_string()),
+            }
+        }
+
+        ui.label(RichText::new("Playlists").strong());
+
+        let mut removed_playlist = None;
+        egui::Grid::new("playlist_settings_grid")
+            .num_columns(3)
+            .show(ui, |ui| {
+                for playlist in &mut self.playlists {
+                    if ui.checkbox(&mut playlist.enabled, "").changed() {
+                        self.changed = true;
+                        let db = self.db.clone();
+                        let (playlist_id, enabled) = (playlist.id.clone(), playlist.enabled);
+                        tokio::spawn(async move {
+                            if let Err(e) = db.set_playlist_enabled(&playlist_id, enabled).await {
+                                error!("Error updating playlist: {e}");
+                            }
+                        });
+                    }
+                    ui.label(&playlist.title);
+                    if ui.button("🗑").on_hover_text("Stop tracking this playlist").clicked() {
+                        removed_playlist = Some(playlist.id.clone());
+                    }
+                    ui.end_row();
+                }
+            });
+
+        if let Some(playlist_id) = removed_playlist {
+            self.playlists.retain(|playlist| playlist.id != playlist_id);
+            self.changed = true;
+            let db = self.db.clone();
+            tokio::spawn(async move {
+                if let Err(e) = db.remove_playlist(&playlist_id).await {
+                    error!("Error removing playlist: {e}");
+                }
+            });
+        }
+
+        ui.horizontal(|ui| {
+            ui.label("Add playlist:");
+            ui.text_edit_singleline(&mut self.playlist_input)
+                .on_hover_text("Playlist URL or playlist ID");
+            if self.add_in_progress {
+                ui.spinner();
+            } else if ui.button("Add").clicked() && !self.playlist_input.trim().is_empty() {
+                self.add_playlist(source_kind, api_key);
+            }
+        });
+
+        if let Some(error) = &self.add_error {
+            ui.label(RichText::new(error).color(egui::Color32::RED));
+        }
+    }
+
+    /// Resolves the entered playlist through the configured video source and saves it.
+    fn add_playlist(&mut self, source_kind: SourceKind, api_key: Option<&str>) {
+        let source: Box<dyn VideoSource> = match (source_kind, api_key) {
+            (SourceKind::Feed, _) => Box::new(FeedClient::new()),
+            (SourceKind::Api, Some(api_key)) => Box::new(YouTubeClient::new(api_key.to_string())),
+            (SourceKind::Api, None) => {
+                self.add_error = Some("An API key is needed to look up playlists.".to_string());
+                return;
+            }
+        };
+
+        self.add_in_progress = true;
+        let sender = self.add_sender.clone();
+        let db = self.db.clone();
+        let input = self.playlist_input.trim().to_string();
+        tokio::spawn(async move {
+            let result = add_playlist(source.as_ref(), &db, &input).await;
+            if let Err(e) = &result {
+                error!("Error adding playlist {input}: {e}");
+            }
+            sender.send(result).ok();
+        });
+    }
+}
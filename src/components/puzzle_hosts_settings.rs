@@ -0,0 +1,81 @@
+use std::sync::mpsc::Receiver;
+
+use eframe::egui::{self, RichText};
+use log::error;
+
+use crate::data::{db::YoutubeDatabase, links::DEFAULT_PUZZLE_HOSTS};
+
+/// Settings section for editing which hosts count as puzzle links.
+pub struct PuzzleHostsSettings {
+    db: YoutubeDatabase,
+    hosts_receiver: Receiver<Vec<String>>,
+    hosts_input: String,
+    changed: bool,
+}
+impl PuzzleHostsSettings {
+    /// Creates a new instance of `PuzzleHostsSettings` and loads the hosts in the background.
+    pub fn new(db: YoutubeDatabase) -> Self {
+        let (hosts_sender, hosts_receiver) = std::sync::mpsc::channel();
+
+        let db_clone = db.clone();
+        tokio::spawn(async move {
+            match db_clone.get_puzzle_hosts().await {
+                Ok(hosts) => {
+                    hosts_sender.send(hosts).ok();
+                }
+                Err(e) => error!("Error loading puzzle hosts: {e}"),
+            }
+        });
+
+        Self {
+            db,
+            hosts_receiver,
+            hosts_input: String::new(),
+            changed: false,
+        }
+    }
+
+    /// Returns whether the puzzle hosts were saved.
+    pub fn changed(&self) -> bool {
+        self.changed
+    }
+
+    /// Shows the editable list of puzzle hosts.
+    pub fn show(&mut self, ui: &mut egui::Ui) {
+        if let Ok(hosts) = self.hosts_receiver.try_recv() {
+            self.hosts_input = hosts.join("\n");
+        }
+
+        ui.label(RichText::new("Puzzle Hosts").strong());
+        ui.label("Links to these hosts (one per line, optionally with a path) count as puzzle links.");
+        ui.add(egui::TextEdit::multiline(&mut self.hosts_input).desired_rows(4));
+
+        ui.horizontal(|ui| {
+            if ui.button("Save Hosts").clicked() {
+                self.save();
+            }
+            if ui.button("Reset to Defaults").clicked() {
+                self.hosts_input = DEFAULT_PUZZLE_HOSTS.join("\n");
+                self.save();
+            }
+        });
+    }
+
+    /// Saves the entered hosts in the background.
+    fn save(&mut self) {
+        self.changed = true;
+        let db = self.db.clone();
+        let hosts = self
+            .hosts_input
+            .lines()
+            .map(str::trim)
+            .filter(|host| !host.is_empty())
+            .map(str::to_string)
+            .collect::<Vec<_>>();
+        tokio::spawn(async move {
+            if let Err(e) = db.set_puzzle_hosts(&hosts).await {
+                error!("Error saving puzzle hosts: {e}");
+            }
+        });
+    }
+}
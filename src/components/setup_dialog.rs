@@ -1,7 +1,16 @@
+use std::path::PathBuf;
+
 use eframe::egui::{self, RichText, FontId};
 use log::error;
 
-use crate::data::db::YoutubeDatabase;
+use crate::{
+    components::{
+        channel_settings::ChannelSettings, playlist_settings::PlaylistSettings,
+        puzzle_hosts_settings::PuzzleHostsSettings, shortcut_settings::ShortcutSettings,
+    },
+    data::{db::YoutubeDatabase, shortcuts::KeyBindings, source::SourceKind},
+    CONFIG_DIR,
+};
 
 /// Result of showing the setup dialog
 #[derive(Debug, Clone)]
@@ -10,18 +19,30 @@ pub enum SetupDialogResult {
     Showing,
     /// User saved a new API key
     Saved(String),
+    /// User chose the public feed instead of an API key
+    UseFeed,
+    /// User asked to import a seed catalogue file
+    ImportCatalogue(PathBuf),
+    /// User asked to export the stored videos as a seed catalogue file
+    ExportCatalogue(PathBuf),
     /// User cancelled the dialog
     Cancelled,
 }
 
-/// Setup dialog for configuring the YouTube API key.
+/// Setup dialog for configuring the video source and YouTube API key.
 pub struct SetupDialog {
     api_key_input: String,
+    source_kind: SourceKind,
     show_dialog: bool,
     save_in_progress: bool,
     db: YoutubeDatabase,
     editing_mode: bool,
     current_api_key: Option<String>,
+    catalogue_path: String,
+    channel_settings: Option<ChannelSettings>,
+    playlist_settings: Option<PlaylistSettings>,
+    puzzle_hosts_settings: Option<PuzzleHostsSettings>,
+    shortcut_settings: Option<ShortcutSettings>,
 }
 
 impl SetupDialog {
@@ -29,26 +50,77 @@ impl SetupDialog {
     pub fn new(db: YoutubeDatabase) -> Self {
         Self {
             api_key_input: String::new(),
+            source_kind: SourceKind::Api,
             show_dialog: true,
             save_in_progress: false,
             db,
             editing_mode: false,
             current_api_key: None,
+            catalogue_path: Self::default_catalogue_path(),
+            channel_settings: None,
+            playlist_settings: None,
+            puzzle_hosts_settings: None,
+            shortcut_settings: None,
         }
     }
 
-    /// Creates a new instance of `SetupDialog` for editing an existing API key.
-    pub fn new_editing(db: YoutubeDatabase, current_api_key: String) -> Self {
+    /// Creates a new instance of `SetupDialog` for editing the existing configuration.
+    pub fn new_editing(
+        db: YoutubeDatabase,
+        current_api_key: Option<String>,
+        source_kind: SourceKind,
+    ) -> Self {
+        let channel_settings = Some(ChannelSettings::new(db.clone()));
+        let playlist_settings = Some(PlaylistSettings::new(db.clone()));
+        let puzzle_hosts_settings = Some(PuzzleHostsSettings::new(db.clone()));
+        let shortcut_settings = Some(ShortcutSettings::new(db.clone()));
         Self {
             api_key_input: String::new(),
+            source_kind,
             show_dialog: true,
             save_in_progress: false,
             db,
             editing_mode: true,
-            current_api_key: Some(current_api_key),
+            current_api_key,
+            catalogue_path: Self::default_catalogue_path(),
+            channel_settings,
+            playlist_settings,
+            puzzle_hosts_settings,
+            shortcut_settings,
         }
     }
 
+    /// Returns whether the tracked channels, playlists or puzzle hosts were changed while the
+    /// dialog was open.
+    pub fn sources_changed(&self) -> bool {
+        self.channel_settings
+            .as_ref()
+            .is_some_and(ChannelSettings::changed)
+            || self
+                .playlist_settings
+                .as_ref()
+                .is_some_and(PlaylistSettings::changed)
+            || self
+                .puzzle_hosts_settings
+                .as_ref()
+                .is_some_and(PuzzleHostsSettings::changed)
+    }
+
+    /// Returns the keyboard shortcuts if they were saved while the dialog was open.
+    pub fn saved_key_bindings(&self) -> Option<&KeyBindings> {
+        self.shortcut_settings
+            .as_ref()
+            .and_then(ShortcutSettings::saved_bindings)
+    }
+
+    /// Returns the default location of the seed catalogue file.
+    fn default_catalogue_path() -> String {
+        CONFIG_DIR
+            .join("seed_catalogue.json")
+            .to_string_lossy()
+            .into_owned()
+    }
+
     /// Returns a masked version of the API key for display.
     fn mask_api_key(api_key: &str) -> String {
         if api_key.len() <= 8 {
@@ -72,12 +144,14 @@ impl SetupDialog {
 
         let mut setup_complete = false;
         let mut api_key_to_return = None;
+        let mut feed_selected = false;
+        let mut catalogue_action = None;
         let mut was_cancelled = false;
 
         let window_title = if self.editing_mode {
-            "Edit API Key"
+            "Settings"
         } else {
-            "YouTube API Key Setup"
+            "Video Source Setup"
         };
 
         egui::Window::new(window_title)
@@ -90,7 +164,7 @@ impl SetupDialog {
 
                     if self.editing_mode {
                         ui.label(
-                            RichText::new("Update API Key")
+                            RichText::new("Update Video Source")
                                 .font(FontId::proportional(20.0))
                         );
                         ui.add_space(10.0);
@@ -106,52 +180,130 @@ impl SetupDialog {
                         );
                         ui.add_space(10.0);
 
-                        ui.label("To use this application, you need to provide a YouTube API key.");
+                        ui.label("Choose how the tracker should find Cracking the Cryptic videos.");
                         ui.add_space(5.0);
                     }
 
-                    ui.label("You can get one from the Google Cloud Console:");
-                    ui.hyperlink("https://console.cloud.google.com/apis/credentials");
-                    ui.add_space(15.0);
-
                     ui.horizontal(|ui| {
-                        ui.label(if self.editing_mode { "New API Key:" } else { "API Key:" });
-                        ui.text_edit_singleline(&mut self.api_key_input);
+                        ui.radio_value(&mut self.source_kind, SourceKind::Api, "YouTube Data API key");
+                        ui.radio_value(&mut self.source_kind, SourceKind::Feed, "Public channel feed (no key)");
                     });
+                    ui.add_space(10.0);
 
-                    ui.add_space(15.0);
+                    if self.source_kind == SourceKind::Feed {
+                        ui.label("The public feed only lists the latest ~15 uploads and doesn't include durations.");
+                        ui.label("Durations are filled in automatically if you add an API key later.");
+                        ui.add_space(15.0);
+                    } else {
+                        ui.label("You can get an API key from the Google Cloud Console:");
+                        ui.hyperlink("https://console.cloud.google.com/apis/credentials");
+                        ui.add_space(15.0);
+
+                        ui.horizontal(|ui| {
+                            ui.label(if self.editing_mode { "New API Key:" } else { "API Key:" });
+                            ui.text_edit_singleline(&mut self.api_key_input);
+                        });
+
+                        ui.add_space(15.0);
+                    }
 
                     ui.horizontal(|ui| {
                         if self.save_in_progress {
                             ui.label(RichText::new("Saving...").strong());
                         } else {
-                            let button = ui.button(if self.editing_mode { "Update API Key" } else { "Save API Key" });
-                            if button.clicked() && !self.api_key_input.trim().is_empty() {
-                                let api_key = self.api_key_input.trim().to_string();
-                                let db = self.db.clone();
-                                self.save_in_progress = true;
-
-                                // Save the API key to the database
-                                let api_key_for_db = api_key.clone();
-                                tokio::spawn(async move {
-                                    if let Err(e) = db.set_api_key(&api_key_for_db).await {
-                                        error!("Error saving API key: {e}");
-                                    }
-                                });
+                            if self.source_kind == SourceKind::Feed {
+                                if ui.button("Use Public Feed").clicked() {
+                                    let db = self.db.clone();
+                                    self.save_in_progress = true;
 
-                                setup_complete = true;
-                                api_key_to_return = Some(api_key);
-                            }
+                                    // Save the source selection to the database
+                                    tokio::spawn(async move {
+                                        if let Err(e) = db.set_video_source(SourceKind::Feed).await {
+                                            error!("Error saving video source: {e}");
+                                        }
+                                    });
+
+                                    setup_complete = true;
+                                    feed_selected = true;
+                                }
+                            } else {
+                                // Allow switching back to the API with the key that's already saved
+                                let reuse_current_key = self.api_key_input.trim().is_empty()
+                                    && self.current_api_key.is_some();
+                                let button = ui.button(if self.editing_mode { "Update API Key" } else { "Save API Key" });
+                                if button.clicked() && (!self.api_key_input.trim().is_empty() || reuse_current_key) {
+                                    let api_key = if reuse_current_key {
+                                        self.current_api_key.clone().unwrap_or_default()
+                                    } else {
+                                        self.api_key_input.trim().to_string()
+                                    };
+                                    let db = self.db.clone();
+                                    self.save_in_progress = true;
+
+                                    // Save the API key and source selection to the database
+                                    let api_key_for_db = api_key.clone();
+                                    tokio::spawn(async move {
+                                        if let Err(e) = db.set_api_key(&api_key_for_db).await {
+                                            error!("Error saving API key: {e}");
+                                        }
+                                        if let Err(e) = db.set_video_source(SourceKind::Api).await {
+                                            error!("Error saving video source: {e}");
+                                        }
+                                    });
 
-                            if self.editing_mode {
-                                if ui.button("Cancel").clicked() {
                                     setup_complete = true;
-                                    was_cancelled = true;
+                                    api_key_to_return = Some(api_key);
                                 }
                             }
+
+                            if self.editing_mode && ui.button("Cancel").clicked() {
+                                setup_complete = true;
+                                was_cancelled = true;
+                            }
                         }
                     });
 
+                    if let Some(channel_settings) = &mut self.channel_settings {
+                        ui.separator();
+                        channel_settings.show(ui, self.source_kind, self.current_api_key.as_deref());
+                    }
+
+                    if let Some(playlist_settings) = &mut self.playlist_settings {
+                        ui.separator();
+                        playlist_settings.show(ui, self.source_kind, self.current_api_key.as_deref());
+                    }
+
+                    if let Some(puzzle_hosts_settings) = &mut self.puzzle_hosts_settings {
+                        ui.separator();
+                        puzzle_hosts_settings.show(ui);
+                    }
+
+                    if let Some(shortcut_settings) = &mut self.shortcut_settings {
+                        ui.separator();
+                        shortcut_settings.show(ui);
+                    }
+
+                    if self.editing_mode {
+                        ui.separator();
+                        ui.label(RichText::new("Seed Catalogue").strong());
+                        ui.label("Import a catalogue file to add videos without fetching them, or export the stored videos.");
+                        ui.horizontal(|ui| {
+                            ui.label("File:");
+                            ui.text_edit_singleline(&mut self.catalogue_path);
+                        });
+                        ui.horizontal(|ui| {
+                            let path = PathBuf::from(self.catalogue_path.trim());
+                            if ui.button("Import Catalogue").clicked() && !self.catalogue_path.trim().is_empty() {
+                                setup_complete = true;
+                                catalogue_action = Some(SetupDialogResult::ImportCatalogue(path.clone()));
+                            }
+                            if ui.button("Export Catalogue").clicked() && !self.catalogue_path.trim().is_empty() {
+                                setup_complete = true;
+                                catalogue_action = Some(SetupDialogResult::ExportCatalogue(path));
+                            }
+                        });
+                    }
+
                     ui.add_space(10.0);
                 });
             });
@@ -162,6 +314,10 @@ impl SetupDialog {
 
         if let Some(api_key) = api_key_to_return {
             SetupDialogResult::Saved(api_key)
+        } else if feed_selected {
+            SetupDialogResult::UseFeed
+        } else if let Some(action) = catalogue_action {
+            action
         } else if was_cancelled {
             SetupDialogResult::Cancelled
         } else {
@@ -0,0 +1,118 @@
+use std::sync::mpsc::Receiver;
+
+use eframe::egui::{self, RichText};
+use log::error;
+
+use crate::data::{
+    db::YoutubeDatabase,
+    shortcuts::{KeyBindings, ShortcutAction},
+};
+
+/// Settings section for changing the keyboard shortcuts.
+pub struct ShortcutSettings {
+    db: YoutubeDatabase,
+    bindings_receiver: Receiver<KeyBindings>,
+    bindings: KeyBindings,
+    recording: Option<ShortcutAction>,
+    saved: bool,
+}
+impl ShortcutSettings {
+    /// Creates a new instance of `ShortcutSettings` and loads the key bindings in the background.
+    pub fn new(db: YoutubeDatabase) -> Self {
+        let (bindings_sender, bindings_receiver) = std::sync::mpsc::channel();
+
+        let db_clone = db.clone();
+        tokio::spawn(async move {
+            match db_clone.get_key_bindings().await {
+                Ok(bindings) => {
+                    bindings_sender.send(bindings).ok();
+                }
+                Err(e) => error!("Error loading key bindings: {e}"),
+            }
+        });
+
+        Self {
+            db,
+            bindings_receiver,
+            bindings: KeyBindings::default(),
+            recording: None,
+            saved: false,
+        }
+    }
+
+    /// Returns the key bindings if they were saved while the settings were open.
+    pub fn saved_bindings(&self) -> Option<&KeyBindings> {
+        self.saved.then_some(&self.bindings)
+    }
+
+    /// Shows the shortcut for each action, letting the user record a different key.
+    pub fn show(&mut self, ui: &mut egui::Ui) {
+        if let Ok(bindings) = self.bindings_receiver.try_recv() {
+            self.bindings = bindings;
+        }
+
+        if let Some(action) = self.recording {
+            // Take the key press so it doesn't also activate a button
+            let pressed = ui.input_mut(|input| {
+                let key = input.events.iter().find_map(|event| match event {
+                    egui::Event::Key { key, pressed: true, .. } => Some(*key),
+                    _ => None,
+                });
+                input.events.retain(|event| !matches!(event, egui::Event::Key { .. }));
+                key
+            });
+            match pressed {
+                Some(egui::Key::Escape) => self.recording = None,
+                Some(key) => {
+                    self.bindings.set(action, key.symbol_or_name());
+                    self.recording = None;
+                }
+                None => {}
+            }
+        }
+
+        ui.label(RichText::new("Keyboard Shortcuts").strong());
+        ui.label("Click a shortcut, then press the key to use instead (Escape to cancel).");
+        egui::Grid::new("shortcut_settings").num_columns(2).show(ui, |ui| {
+            for action in ShortcutAction::ALL {
+                ui.label(action.description());
+                if self.recording == Some(action) {
+                    ui.label(RichText::new("Press a key…").italics());
+                } else if ui.button(self.bindings.key(action)).clicked() {
+                    self.recording = Some(action);
+                }
+                ui.end_row();
+            }
+        });
+
+        let conflicts = self.bindings.conflicts().join(", ");
+        if !conflicts.is_empty() {
+            ui.label(
+                RichText::new(format!("Bound to more than one action: {conflicts}"))
+                    .color(egui::Color32::RED),
+            );
+        }
+
+        ui.horizontal(|ui| {
+            if ui.add_enabled(conflicts.is_empty(), egui::Button::new("Save Shortcuts")).clicked() {
+                self.save();
+            }
+            if ui.button("Reset to Defaults").clicked() {
+                self.bindings = KeyBindings::default();
+                self.save();
+            }
+        });
+    }
+
+    /// Saves the key bindings in the background.
+    fn save(&mut self) {
+        self.saved = true;
+        let db = self.db.clone();
+        let bindings = self.bindings.clone();
+        tokio::spawn(async move {
+            if let Err(e) = db.set_key_bindings(&bindings).await {
+                error!("Error saving key bindings: {e}");
+            }
+        });
+    }
+}
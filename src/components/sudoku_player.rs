@@ -0,0 +1,623 @@
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::Receiver,
+        Arc,
+    },
+};
+
+use eframe::egui::{self, Color32, FontId, Key, Pos2, Rect, RichText, Sense, Stroke, Vec2};
+use log::error;
+
+use crate::data::{
+    db::YoutubeDatabase,
+    model::{CtcVideo, VideoId},
+    puzzle::{Cell, PuzzleDescription},
+    solve_state::{EntryMode, SolveState},
+    solver::{Check, Hint, Solver, Uniqueness},
+};
+
+/// Side length of a cell in points.
+const CELL_SIZE: f32 = 44.0;
+
+/// Colours available in colour mode, picked with the digit keys.
+const PALETTE: [Color32; 9] = [
+    Color32::from_rgb(214, 214, 214),
+    Color32::from_rgb(160, 160, 160),
+    Color32::from_rgb(110, 110, 110),
+    Color32::from_rgb(180, 230, 130),
+    Color32::from_rgb(230, 160, 240),
+    Color32::from_rgb(250, 200, 120),
+    Color32::from_rgb(240, 120, 120),
+    Color32::from_rgb(250, 240, 130),
+    Color32::from_rgb(130, 190, 250),
+];
+
+/// Digit keys in the order of the digits they enter.
+const DIGIT_KEYS: [Key; 9] = [
+    Key::Num1,
+    Key::Num2,
+    Key::Num3,
+    Key::Num4,
+    Key::Num5,
+    Key::Num6,
+    Key::Num7,
+    Key::Num8,
+    Key::Num9,
+];
+
+/// Result of showing the sudoku player
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SudokuPlayerResult {
+    /// Player is still being shown
+    Showing,
+    /// The grid was just completed correctly
+    Solved,
+    /// User closed the player
+    Closed,
+}
+
+/// Window for solving a video's puzzle offline, saving progress as the user goes.
+pub struct SudokuPlayer {
+    db: YoutubeDatabase,
+    video_id: VideoId,
+    title: String,
+    puzzle: PuzzleDescription,
+    regions: HashMap<Cell, usize>,
+    state: SolveState,
+    state_receiver: Receiver<SolveState>,
+    selected: Vec<Cell>,
+    mode: EntryMode,
+    solved: bool,
+    uniqueness_receiver: Receiver<Uniqueness>,
+    uniqueness: Option<Uniqueness>,
+    /// Set when the player closes, to stop the background solve.
+    cancel_solve: Arc<AtomicBool>,
+    hint: Option<(Hint, usize)>,
+    check_message: Option<String>,
+    mistakes: Vec<Cell>,
+}
+impl SudokuPlayer {
+    /// Creates a new instance of `SudokuPlayer` and loads any saved progress in the background.
+    pub fn new(db: YoutubeDatabase, video: &CtcVideo, puzzle: PuzzleDescription) -> Self {
+        let (state_sender, state_receiver) = std::sync::mpsc::channel();
+
+        let db_clone = db.clone();
+        let video_id = video.id.clone();
+        tokio::spawn(async move {
+            match db_clone.get_solve_state(&video_id).await {
+                Ok(Some(state)) => {
+                    state_sender.send(state).ok();
+                }
+                Ok(None) => {}
+                Err(e) => error!("Error loading solve state: {e}"),
+            }
+        });
+
+        // Solving can take a moment for hard puzzles, so keep it off the UI thread
+        let (uniqueness_sender, uniqueness_receiver) = std::sync::mpsc::channel();
+        let puzzle_clone = puzzle.clone();
+        let cancel_solve = Arc::new(AtomicBool::new(false));
+        let cancelled = cancel_solve.clone();
+        tokio::task::spawn_blocking(move || {
+            if let Some(uniqueness) = Solver::new(&puzzle_clone).uniqueness(&cancelled) {
+                uniqueness_sender.send(uniqueness).ok();
+            }
+        });
+
+        let regions = puzzle
+            .regions_or_boxes()
+            .into_iter()
+            .enumerate()
+            .flat_map(|(index, cells)| cells.into_iter().map(move |cell| (cell, index)))
+            .collect();
+
+        Self {
+            db,
+            video_id: video.id.clone(),
+            title: video.title.clone(),
+            state: SolveState::new(puzzle.size),
+            puzzle,
+            regions,
+            state_receiver,
+            selected: Vec::new(),
+            mode: EntryMode::default(),
+            solved: false,
+            uniqueness_receiver,
+            uniqueness: None,
+            cancel_solve,
+            hint: None,
+            check_message: None,
+            mistakes: Vec::new(),
+        }
+    }
+
+    /// Returns the ID of the video whose puzzle is being solved.
+    pub fn video_id(&self) -> &VideoId {
+        &self.video_id
+    }
+
+    /// Shows the player and returns the result.
+    pub fn show(&mut self, ctx: &egui::Context) -> SudokuPlayerResult {
+        if let Ok(state) = self.state_receiver.try_recv() {
+            // Keep whatever the user entered while the saved state was loading
+            if state.fits(self.puzzle.size) && !self.state.can_undo() {
+                self.solved = state.is_solved(&self.puzzle);
+                self.state = state;
+            }
+        }
+
+        if let Ok(uniqueness) = self.uniqueness_receiver.try_recv() {
+            self.uniqueness = Some(uniqueness);
+        }
+
+        let mut result = SudokuPlayerResult::Showing;
+        let mut open = true;
+        let mut changed = false;
+
+        egui::Window::new(format!("Solve: {}", self.title))
+            .id(egui::Id::new("sudoku_player"))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                if let Some(author) = &self.puzzle.author {
+                    ui.label(format!("by {author}"));
+                }
+                if let Some(rules) = &self.puzzle.rules {
+                    ui.collapsing("Rules", |ui| {
+                        ui.label(rules);
+                    });
+                }
+
+                ui.horizontal(|ui| {
+                    ui.selectable_value(&mut self.mode, EntryMode::Digit, "Digit");
+                    ui.selectable_value(&mut self.mode, EntryMode::Corner, "Corner");
+                    ui.selectable_value(&mut self.mode, EntryMode::Centre, "Centre");
+                    ui.selectable_value(&mut self.mode, EntryMode::Colour, "Colour");
+                });
+
+                self.show_grid(ui);
+
+                ui.horizontal(|ui| {
+                    let digits = self.puzzle.size.min(9) as u8;
+                    for digit in 1..=digits {
+                        let button = match self.mode {
+                            EntryMode::Colour => {
+                                egui::Button::new("  ").fill(PALETTE[digit as usize - 1])
+                            }
+                            _ => egui::Button::new(digit.to_string()),
+                        };
+                        if ui.add(button).clicked() {
+                            self.state
+                                .enter(&self.puzzle, &self.selected, self.mode, digit);
+                            changed = true;
+                        }
+                    }
+                });
+
+                ui.horizontal(|ui| {
+                    if ui.button("Delete").clicked() {
+                        self.state.clear(&self.puzzle, &self.selected);
+                        changed = true;
+                    }
+                    if ui
+                        .add_enabled(self.state.can_undo(), egui::Button::new("Undo"))
+                        .clicked()
+                    {
+                        self.state.undo();
+                        changed = true;
+                    }
+                    if ui.button("Check").clicked() {
+                        self.check();
+                    }
+                    if ui
+                        .button("Hint")
+                        .on_hover_text("Click again for more detail")
+                        .clicked()
+                    {
+                        self.next_hint();
+                    }
+                });
+
+                if let Some((hint, level)) = &self.hint {
+                    ui.label(hint.describe(*level));
+                }
+                if let Some(message) = &self.check_message {
+                    ui.label(message);
+                }
+                match &self.uniqueness {
+                    None => {
+                        ui.label(RichText::new("Checking the puzzle...").small());
+                    }
+                    Some(Uniqueness::Unique(_)) => {}
+                    Some(Uniqueness::Multiple) => {
+                        ui.label(
+                            RichText::new("This puzzle has several solutions with the rules the solver knows, so hints may stop early.")
+                                .small(),
+                        );
+                    }
+                    Some(Uniqueness::Unsupported) => {
+                        ui.label(RichText::new("This grid is too big for the solver, so there are no hints.").small());
+                    }
+                    Some(Uniqueness::NoSolution) => {
+                        ui.label(
+                            RichText::new("The solver found no solution; the puzzle may use rules it doesn't know.")
+                                .small(),
+                        );
+                    }
+                }
+
+                ui.label(
+                    RichText::new(
+                        "Shift+digit: corner mark, Ctrl+digit: centre mark, Ctrl+Z: undo",
+                    )
+                    .small(),
+                );
+                if self.solved {
+                    ui.label(
+                        RichText::new("Solved! Marked as completed.")
+                            .strong()
+                            .color(Color32::GREEN),
+                    );
+                }
+            });
+
+        changed |= self.handle_keys(ctx);
+
+        if changed {
+            self.hint = None;
+            self.check_message = None;
+            self.mistakes.clear();
+            self.save();
+            if !self.solved && self.state.is_solved(&self.puzzle) {
+                self.solved = true;
+                result = SudokuPlayerResult::Solved;
+            }
+        }
+
+        if !open {
+            result = SudokuPlayerResult::Closed;
+        }
+
+        result
+    }
+
+    /// Paints the grid and updates the selection from clicks and drags.
+    fn show_grid(&mut self, ui: &mut egui::Ui) {
+        let size = self.puzzle.size;
+        let side = CELL_SIZE * size as f32;
+        let (response, painter) = ui.allocate_painter(Vec2::splat(side), Sense::click_and_drag());
+        let origin = response.rect.min;
+        let cell_rect = |(row, col): Cell| {
+            Rect::from_min_size(
+                origin + Vec2::new(col as f32 * CELL_SIZE, row as f32 * CELL_SIZE),
+                Vec2::splat(CELL_SIZE),
+            )
+        };
+        let cell_at = |pos: Pos2| {
+            let offset = pos - origin;
+            let (row, col) = (
+                (offset.y / CELL_SIZE).floor(),
+                (offset.x / CELL_SIZE).floor(),
+            );
+            (row >= 0.0 && col >= 0.0 && (row as usize) < size && (col as usize) < size)
+                .then_some((row as usize, col as usize))
+        };
+
+        // Update the selection
+        let modifiers = ui.input(|input| input.modifiers);
+        if let Some(cell) = response.interact_pointer_pos().and_then(cell_at) {
+            if (response.clicked() || response.drag_started())
+                && !modifiers.shift
+                && !modifiers.command
+            {
+                self.selected.clear();
+            }
+            if !self.selected.contains(&cell) {
+                self.selected.push(cell);
+            }
+        }
+
+        let visuals = ui.visuals();
+        let text_colour = visuals.strong_text_color();
+        let entered_colour = Color32::from_rgb(30, 100, 220);
+        painter.rect_filled(response.rect, 0.0, visuals.extreme_bg_color);
+
+        // Colours and selection
+        for row in 0..size {
+            for col in 0..size {
+                let rect = cell_rect((row, col));
+                let colours = &self.state.cell((row, col)).colours;
+                let stripe = CELL_SIZE / colours.len().max(1) as f32;
+                for (index, colour) in colours.iter().enumerate() {
+                    // Colours are numbered from 1; skip invalid ones from a damaged saved state
+                    let Some(palette_index) = (*colour as usize).checked_sub(1) else {
+                        continue;
+                    };
+                    let stripe_rect = Rect::from_min_size(
+                        rect.min + Vec2::new(index as f32 * stripe, 0.0),
+                        Vec2::new(stripe, CELL_SIZE),
+                    );
+                    painter.rect_filled(
+                        stripe_rect,
+                        0.0,
+                        PALETTE[palette_index % PALETTE.len()],
+                    );
+                }
+                if self.selected.contains(&(row, col)) {
+                    painter.rect_filled(
+                        rect,
+                        0.0,
+                        Color32::from_rgba_unmultiplied(120, 170, 250, 110),
+                    );
+                }
+                if self.mistakes.contains(&(row, col)) {
+                    painter.rect_filled(
+                        rect,
+                        0.0,
+                        Color32::from_rgba_unmultiplied(240, 80, 80, 110),
+                    );
+                }
+                if let Some((hint, level)) = &self.hint {
+                    if *level > 0 && hint.cell == (row, col) {
+                        painter.rect_filled(
+                            rect,
+                            0.0,
+                            Color32::from_rgba_unmultiplied(250, 220, 80, 130),
+                        );
+                    }
+                }
+            }
+        }
+
+        // Thermometers
+        let thermo_colour = Color32::from_gray(170);
+        for thermometer in &self.puzzle.thermometers {
+            let points = thermometer
+                .iter()
+                .map(|cell| cell_rect(*cell).center())
+                .collect::<Vec<_>>();
+            if let Some(bulb) = points.first() {
+                painter.circle_filled(*bulb, CELL_SIZE * 0.35, thermo_colour);
+            }
+            painter.add(egui::Shape::line(
+                points,
+                Stroke::new(CELL_SIZE * 0.25, thermo_colour),
+            ));
+        }
+
+        // Cages, outlined just inside their cells
+        let cage_stroke = Stroke::new(1.0, text_colour);
+        let inset = 4.0;
+        for cage in &self.puzzle.cages {
+            for &(row, col) in &cage.cells {
+                let rect = cell_rect((row, col)).shrink(inset);
+                let outside =
+                    |cell: Option<Cell>| cell.is_none_or(|cell| !cage.cells.contains(&cell));
+                if outside(row.checked_sub(1).map(|row| (row, col))) {
+                    painter.line_segment([rect.left_top(), rect.right_top()], cage_stroke);
+                }
+                if outside(Some((row + 1, col))) {
+                    painter.line_segment([rect.left_bottom(), rect.right_bottom()], cage_stroke);
+                }
+                if outside(col.checked_sub(1).map(|col| (row, col))) {
+                    painter.line_segment([rect.left_top(), rect.left_bottom()], cage_stroke);
+                }
+                if outside(Some((row, col + 1))) {
+                    painter.line_segment([rect.right_top(), rect.right_bottom()], cage_stroke);
+                }
+            }
+            if let (Some(sum), Some(first)) = (cage.sum, cage.cells.iter().min()) {
+                painter.text(
+                    cell_rect(*first).min + Vec2::splat(inset + 1.0),
+                    egui::Align2::LEFT_TOP,
+                    sum.to_string(),
+                    FontId::proportional(10.0),
+                    text_colour,
+                );
+            }
+        }
+
+        // Cell and region borders
+        let thin = Stroke::new(1.0, Color32::from_gray(140));
+        let thick = Stroke::new(3.0, text_colour);
+        for row in 0..size {
+            for col in 0..size {
+                let rect = cell_rect((row, col));
+                let region = self.regions.get(&(row, col));
+                if col + 1 < size {
+                    let stroke = if region == self.regions.get(&(row, col + 1)) {
+                        thin
+                    } else {
+                        thick
+                    };
+                    painter.line_segment([rect.right_top(), rect.right_bottom()], stroke);
+                }
+                if row + 1 < size {
+                    let stroke = if region == self.regions.get(&(row + 1, col)) {
+                        thin
+                    } else {
+                        thick
+                    };
+                    painter.line_segment([rect.left_bottom(), rect.right_bottom()], stroke);
+                }
+            }
+        }
+        painter.rect_stroke(response.rect, 0.0, thick, egui::StrokeKind::Middle);
+
+        // Digits and pencil marks
+        for row in 0..size {
+            for col in 0..size {
+                let rect = cell_rect((row, col));
+                let cell = self.state.cell((row, col));
+                if let Some(digit) = self.puzzle.given((row, col)) {
+                    painter.text(
+                        rect.center(),
+                        egui::Align2::CENTER_CENTER,
+                        digit.to_string(),
+                        FontId::proportional(28.0),
+                        text_colour,
+                    );
+                } else if let Some(digit) = cell.digit {
+                    painter.text(
+                        rect.center(),
+                        egui::Align2::CENTER_CENTER,
+                        digit.to_string(),
+                        FontId::proportional(28.0),
+                        entered_colour,
+                    );
+                } else {
+                    let centre_text = cell
+                        .centre_marks
+                        .iter()
+                        .map(u8::to_string)
+                        .collect::<String>();
+                    painter.text(
+                        rect.center(),
+                        egui::Align2::CENTER_CENTER,
+                        centre_text,
+                        FontId::proportional(11.0),
+                        entered_colour,
+                    );
+                    for (index, mark) in cell.corner_marks.iter().enumerate() {
+                        let (x, y) = ((index % 3) as f32, (index / 3) as f32);
+                        let pos = rect.min + Vec2::new(8.0 + x * 14.0, 8.0 + y * 14.0);
+                        painter.text(
+                            pos,
+                            egui::Align2::CENTER_CENTER,
+                            mark.to_string(),
+                            FontId::proportional(10.0),
+                            entered_colour,
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    /// Applies keyboard input to the selected cells.
+    ///
+    /// Returns whether the grid's contents were changed.
+    fn handle_keys(&mut self, ctx: &egui::Context) -> bool {
+        if ctx.wants_keyboard_input() || self.selected.is_empty() {
+            return false;
+        }
+
+        let mut changed = false;
+        let digits = self.puzzle.size.min(9);
+        ctx.input_mut(|input| {
+            if input.consume_key(egui::Modifiers::COMMAND, Key::Z) {
+                self.state.undo();
+                changed = true;
+            }
+            if input.consume_key(egui::Modifiers::NONE, Key::Delete)
+                || input.consume_key(egui::Modifiers::NONE, Key::Backspace)
+            {
+                self.state.clear(&self.puzzle, &self.selected);
+                changed = true;
+            }
+
+            for (index, key) in DIGIT_KEYS.iter().take(digits).enumerate() {
+                let digit = index as u8 + 1;
+                let mode = if input.consume_key(egui::Modifiers::SHIFT, *key) {
+                    EntryMode::Corner
+                } else if input.consume_key(egui::Modifiers::COMMAND, *key) {
+                    EntryMode::Centre
+                } else if input.consume_key(egui::Modifiers::NONE, *key) {
+                    self.mode
+                } else {
+                    continue;
+                };
+                self.state.enter(&self.puzzle, &self.selected, mode, digit);
+                changed = true;
+            }
+
+            // Arrow keys move a single selected cell
+            let size = self.puzzle.size;
+            if let Some(&(row, col)) = self.selected.last() {
+                let moved = if input.consume_key(egui::Modifiers::NONE, Key::ArrowUp) {
+                    Some(((row + size - 1) % size, col))
+                } else if input.consume_key(egui::Modifiers::NONE, Key::ArrowDown) {
+                    Some(((row + 1) % size, col))
+                } else if input.consume_key(egui::Modifiers::NONE, Key::ArrowLeft) {
+                    Some((row, (col + size - 1) % size))
+                } else if input.consume_key(egui::Modifiers::NONE, Key::ArrowRight) {
+                    Some((row, (col + 1) % size))
+                } else {
+                    None
+                };
+                if let Some(cell) = moved {
+                    self.selected = vec![cell];
+                }
+            }
+        });
+
+        changed
+    }
+
+    /// Returns the unique solution, once the background solve has found one.
+    fn solution(&self) -> Option<&[u8]> {
+        match &self.uniqueness {
+            Some(Uniqueness::Unique(solution)) => Some(solution),
+            _ => None,
+        }
+    }
+
+    /// Highlights entered digits that break the rules or don't match the solution.
+    fn check(&mut self) {
+        let grid = self.state.grid(&self.puzzle);
+        let check = Solver::new(&self.puzzle).check(&grid, self.solution());
+        self.mistakes.clear();
+        self.check_message = Some(match check {
+            Check::Solved => "Everything is correct.".to_string(),
+            Check::Incomplete => "No mistakes so far.".to_string(),
+            Check::Mistakes(cells) if cells.is_empty() => {
+                "The grid is full but breaks a rule.".to_string()
+            }
+            Check::Mistakes(cells) => {
+                let message = match cells.len() {
+                    1 => "1 cell is wrong.".to_string(),
+                    count => format!("{count} cells are wrong."),
+                };
+                self.mistakes = cells;
+                message
+            }
+        });
+    }
+
+    /// Reveals more of the current hint, or finds one if there isn't one yet.
+    fn next_hint(&mut self) {
+        if let Some((_, level)) = &mut self.hint {
+            *level = (*level + 1).min(Hint::MAX_LEVEL);
+            return;
+        }
+
+        let grid = self.state.grid(&self.puzzle);
+        self.check_message = None;
+        self.hint = Solver::new(&self.puzzle)
+            .hint(&grid, self.solution())
+            .map(|hint| (hint, 0));
+        if self.hint.is_none() {
+            self.check_message = Some("No hint available.".to_string());
+        }
+    }
+
+    /// Saves the current state in the background.
+    fn save(&self) {
+        let db = self.db.clone();
+        let video_id = self.video_id.clone();
+        let state = self.state.clone();
+        tokio::spawn(async move {
+            if let Err(e) = db.set_solve_state(&video_id, &state).await {
+                error!("Error saving solve state: {e}");
+            }
+        });
+    }
+}
+impl Drop for SudokuPlayer {
+    fn drop(&mut self) {
+        self.cancel_solve.store(true, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,104 @@
+use std::{
+    collections::HashMap,
+    sync::mpsc::{Receiver, Sender},
+    time::{Duration, Instant},
+};
+
+use eframe::egui;
+use log::error;
+
+use crate::{
+    data::{
+        model::VideoId,
+        thumbnail::{Thumbnail, ThumbnailClient},
+    },
+    CONFIG_DIR,
+};
+
+/// How long to wait before trying to load a thumbnail again after it failed.
+const RETRY_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Where a requested thumbnail is up to.
+enum ThumbnailState {
+    Loading,
+    Loaded(egui::TextureHandle),
+    Failed(Instant),
+}
+
+/// Video thumbnails for the card view, loaded in the background when first shown.
+pub struct Thumbnails {
+    client: Option<ThumbnailClient>,
+    sender: Sender<(VideoId, Option<Thumbnail>)>,
+    receiver: Receiver<(VideoId, Option<Thumbnail>)>,
+    /// Thumbnails that have been requested.
+    textures: HashMap<VideoId, ThumbnailState>,
+}
+impl Thumbnails {
+    /// Creates a new instance of `Thumbnails` with nothing loaded.
+    pub fn new() -> Self {
+        let (sender, receiver) = std::sync::mpsc::channel();
+        Self {
+            client: None,
+            sender,
+            receiver,
+            textures: HashMap::new(),
+        }
+    }
+
+    /// Turns thumbnails that have finished loading into textures.
+    pub fn poll(&mut self, ctx: &egui::Context) {
+        while let Ok((video_id, thumbnail)) = self.receiver.try_recv() {
+            let state = match thumbnail {
+                Some(thumbnail) => {
+                    let image = egui::ColorImage::from_rgb([thumbnail.width, thumbnail.height], &thumbnail.rgb);
+                    let texture = ctx.load_texture(format!("thumbnail-{video_id}"), image, egui::TextureOptions::LINEAR);
+                    ThumbnailState::Loaded(texture)
+                }
+                None => ThumbnailState::Failed(Instant::now()),
+            };
+            self.textures.insert(video_id, state);
+        }
+    }
+
+    /// Returns the video's thumbnail if it has loaded.
+    pub fn get(&self, video_id: &VideoId) -> Option<&egui::TextureHandle> {
+        match self.textures.get(video_id)? {
+            ThumbnailState::Loaded(texture) => Some(texture),
+            _ => None,
+        }
+    }
+
+    /// Starts loading the thumbnails of videos that haven't been requested before, or that
+    /// failed to load more than `RETRY_INTERVAL` ago.
+    pub fn request(&mut self, video_ids: Vec<VideoId>, ctx: &egui::Context) {
+        for video_id in video_ids {
+            let should_load = match self.textures.get(&video_id) {
+                None => true,
+                Some(ThumbnailState::Failed(failed_at)) => failed_at.elapsed() >= RETRY_INTERVAL,
+                Some(_) => false,
+            };
+            if !should_load {
+                continue;
+            }
+            self.textures.insert(video_id.clone(), ThumbnailState::Loading);
+
+            let client = self
+                .client
+                .get_or_insert_with(|| ThumbnailClient::new(CONFIG_DIR.join("thumbnails")))
+                .clone();
+            let sender = self.sender.clone();
+            let ctx = ctx.clone();
+            tokio::spawn(async move {
+                let thumbnail = match client.get(&video_id).await {
+                    Ok(thumbnail) => Some(thumbnail),
+                    Err(e) => {
+                        error!("Error loading thumbnail for {video_id}: {e}");
+                        None
+                    }
+                };
+                sender.send((video_id, thumbnail)).ok();
+                ctx.request_repaint();
+            });
+        }
+    }
+}
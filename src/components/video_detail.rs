@@ -0,0 +1,194 @@
+use std::sync::mpsc::Receiver;
+
+use eframe::egui::{self, RichText};
+use log::error;
+
+use crate::data::{
+    db::YoutubeDatabase,
+    description::{segments, Segment},
+    model::{CompletionEvent, CtcVideo, VideoId},
+};
+
+/// Result of showing the video detail panel
+#[derive(Debug, Clone)]
+pub enum VideoDetailResult {
+    /// Panel is still being shown
+    Showing,
+    /// User edited the video's notes
+    NotesChanged(String),
+    /// User edited the video's tags
+    TagsChanged(Vec<String>),
+    /// User closed the panel
+    Closed,
+}
+
+/// Side panel showing everything known about a single video.
+pub struct VideoDetail {
+    video_id: VideoId,
+    history_receiver: Receiver<Vec<CompletionEvent>>,
+    history: Vec<CompletionEvent>,
+    notes_input: String,
+    tags_input: String,
+}
+impl VideoDetail {
+    /// Creates a new instance of `VideoDetail` and loads the completion history in the background.
+    pub fn new(db: YoutubeDatabase, video_id: VideoId, notes: &str, tags: &[String]) -> Self {
+        let (history_sender, history_receiver) = std::sync::mpsc::channel();
+
+        let video_id_clone = video_id.clone();
+        tokio::spawn(async move {
+            match db.get_completion_history(&video_id_clone).await {
+                Ok(history) => {
+                    history_sender.send(history).ok();
+                }
+                Err(e) => error!("Error loading completion history: {e}"),
+            }
+        });
+
+        Self {
+            video_id,
+            history_receiver,
+            history: Vec::new(),
+            notes_input: notes.to_string(),
+            tags_input: tags.join(", "),
+        }
+    }
+
+    /// Returns the ID of the video being shown.
+    pub fn video_id(&self) -> &VideoId {
+        &self.video_id
+    }
+
+    /// Adds a completion change made while the panel is open to the history.
+    pub fn record_completion(&mut self, completed: bool) {
+        self.history.push(CompletionEvent::now(completed));
+    }
+
+    /// Shows notes changed elsewhere, such as by undoing an edit.
+    pub fn set_notes(&mut self, notes: &str) {
+        self.notes_input = notes.to_string();
+    }
+
+    /// Shows tags changed elsewhere, such as by undoing an edit.
+    pub fn set_tags(&mut self, tags: &[String]) {
+        self.tags_input = tags.join(", ");
+    }
+
+    /// Shows the details of the video and returns the result.
+    pub fn show(&mut self, ui: &mut egui::Ui, video: &CtcVideo, channel_name: &str) -> VideoDetailResult {
+        if let Ok(mut history) = self.history_receiver.try_recv() {
+            // Keep changes recorded while the history was loading
+            history.append(&mut self.history);
+            self.history = history;
+        }
+
+        let mut result = VideoDetailResult::Showing;
+
+        ui.horizontal(|ui| {
+            ui.heading(&video.title);
+            if ui.button("✖").on_hover_text("Close").clicked() {
+                result = VideoDetailResult::Closed;
+            }
+        });
+        ui.label(format!("{channel_name} — {} — {}", video.date, video.duration));
+        ui.hyperlink_to("Watch video", video.get_video_url());
+        ui.label(RichText::new("↑/↓ to move between videos").small());
+        ui.separator();
+
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            ui.collapsing(RichText::new("Description").strong(), |ui| {
+                self.show_description(ui, video);
+            });
+
+            if !video.chapters.is_empty() {
+                ui.label(RichText::new("Chapters").strong());
+                for chapter in &video.chapters {
+                    ui.horizontal(|ui| {
+                        ui.hyperlink_to(chapter.timestamp(), video.get_video_url_at(chapter.start));
+                        ui.label(&chapter.title);
+                    });
+                }
+                ui.add_space(10.0);
+            }
+
+            ui.label(RichText::new("Links").strong());
+            if video.extracted_links.is_empty() {
+                ui.label("No links found");
+            }
+            for link in video.puzzle_links().chain(video.other_links()) {
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new(link.kind.label()).small());
+                    ui.hyperlink_to(link.display_label(), &link.url)
+                        .on_hover_text(&link.url);
+                });
+            }
+            ui.add_space(10.0);
+
+            ui.label(RichText::new("Completion history").strong());
+            if self.history.is_empty() {
+                ui.label("Never marked as completed");
+            }
+            for event in &self.history {
+                let status = if event.completed { "Completed" } else { "Marked as not completed" };
+                ui.label(format!("{} — {status}", event.changed_at_text()));
+            }
+            ui.add_space(10.0);
+
+            ui.label(RichText::new("Notes").strong());
+            let notes = ui.add(egui::TextEdit::multiline(&mut self.notes_input).desired_rows(4));
+            if notes.lost_focus() {
+                result = VideoDetailResult::NotesChanged(self.notes_input.clone());
+            }
+            ui.add_space(10.0);
+
+            ui.label(RichText::new("Tags").strong());
+            ui.label(RichText::new("Separate tags with commas").small());
+            let tags = ui.text_edit_singleline(&mut self.tags_input);
+            if tags.lost_focus() {
+                result = VideoDetailResult::TagsChanged(parse_tags(&self.tags_input));
+            }
+        });
+
+        result
+    }
+
+    /// Shows the description with clickable links and timestamps.
+    fn show_description(&self, ui: &mut egui::Ui, video: &CtcVideo) {
+        for line in video.description.lines() {
+            if line.trim().is_empty() {
+                ui.add_space(6.0);
+                continue;
+            }
+            ui.horizontal_wrapped(|ui| {
+                ui.spacing_mut().item_spacing.x = 0.0;
+                for segment in segments(line) {
+                    match segment {
+                        Segment::Text(text) => {
+                            ui.label(text);
+                        }
+                        Segment::Url { text, href } => {
+                            ui.hyperlink_to(text, href);
+                        }
+                        Segment::Timestamp(text, seconds) => {
+                            ui.hyperlink_to(text, video.get_video_url_at(seconds))
+                                .on_hover_text("Watch from here");
+                        }
+                    }
+                }
+            });
+        }
+    }
+}
+
+/// Splits comma-separated tags, dropping blanks and duplicates.
+fn parse_tags(input: &str) -> Vec<String> {
+    let mut tags = input
+        .split(',')
+        .map(str::trim)
+        .filter(|tag| !tag.is_empty())
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+    tags.sort();
+    tags.dedup();
+    tags
+}
@@ -1,36 +1,162 @@
-use std::collections::{HashMap, HashSet};
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+    time::{Duration, Instant},
+};
 
 use eframe::egui::{self, RichText};
 use log::{debug, error, info};
 
-use crate::data::{
-    db::YoutubeDatabase,
-    model::{CtcVideo, VideoId},
-    youtube_api::YouTubeClient,
+use crate::{
+    components::{
+        sudoku_player::{SudokuPlayer, SudokuPlayerResult},
+        thumbnails::Thumbnails,
+        video_detail::{VideoDetail, VideoDetailResult},
+    },
+    data::{
+        db::YoutubeDatabase,
+        error::DataError,
+        feed::FeedClient,
+        grouping::{Group, GroupBy},
+        history::{Change, Edit, EditHistory},
+        links::{DescriptionLink, LinkExtractor},
+        model::{Channel, CtcVideo, Playlist, VideoId},
+        puzzle::PuzzleDescription,
+        puzzle_id::PuzzleId,
+        query::{Query, QueryError, VideoContext},
+        search::SearchHit,
+        rules::{FilterRule, RuleSet},
+        seed::{first_run_catalogue, read_catalogue, write_catalogue, write_seed_videos, SeedVideo},
+        source::{SourceKind, VideoSource},
+        sync::{backfill_durations, fetch_new_videos, fetch_playlist, FetchResult},
+        ui_state::{FilterSettings, ViewMode},
+        youtube_api::YouTubeClient,
+    },
+    CONFIG_DIR,
 };
 
-/// YouTube channel ID for Cracking the Cryptic
-static CHANNEL_ID: &str = "UCC-UOdK8-mIjxBQm_ot1T-Q";
+/// Maximum number of full-text search results shown.
+const SEARCH_LIMIT: u32 = 200;
+
+/// How long the toast offering to undo an edit stays up.
+const TOAST_DURATION: Duration = Duration::from_secs(6);
+
+/// Width of a card in the card view, in points.
+const CARD_WIDTH: f32 = 240.0;
+
+/// Minimum height of a card, so cards in a row line up.
+const CARD_MIN_HEIGHT: f32 = 300.0;
+
+/// Tag given to videos by "Enqueue", so `tag:queue` lists them. There's no separate watch
+/// queue: the queue is just the videos with this tag.
+const QUEUE_TAG: &str = "queue";
+
+/// Tracked playlists along with their video IDs, keyed by playlist ID.
+type PlaylistData = (Vec<Playlist>, HashMap<String, Vec<VideoId>>);
+
+/// The user's notes and tags, keyed by video ID.
+type Annotations = (HashMap<VideoId, String>, HashMap<VideoId, Vec<String>>);
 
-/// Displays a list of videos from the Cracking the Cryptic YouTube channel with completion status.
+/// What the button on a toast does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ToastAction {
+    Undo,
+    Redo,
+}
+
+/// A short-lived message, such as about an edit with a button to undo or redo it.
+struct Toast {
+    message: String,
+    action: Option<ToastAction>,
+    shown_at: Instant,
+}
+impl Toast {
+    fn new(message: String, action: Option<ToastAction>) -> Self {
+        Self {
+            message,
+            action,
+            shown_at: Instant::now(),
+        }
+    }
+}
+
+/// A video listed in the grid, with the excerpt of its description matching the search.
+type ListedVideo<'a> = (&'a CtcVideo, Option<&'a SearchHit>);
+
+/// What the user did in the list of videos while it was drawn, applied afterwards.
+#[derive(Default)]
+struct ListActions {
+    opened_player: Option<SudokuPlayer>,
+    clicked_title: Option<(VideoId, egui::Modifiers)>,
+    toggled_completion: Option<(VideoId, bool)>,
+    wanted_thumbnails: Vec<VideoId>,
+}
+
+/// Displays a list of videos from the tracked YouTube channels with completion status.
 pub struct VideoGrid {
     videos: Vec<CtcVideo>,
     video_completion_statuses: HashMap<VideoId, bool>,
     pub show_completed_videos: bool,
     pub show_without_links: bool,
     pub filter_text: String,
+    parsed_filter_text: String,
+    filter_query: Result<Query, QueryError>,
+    pub search_descriptions: bool,
+    searched_text: String,
+    search_sender: std::sync::mpsc::Sender<(String, Vec<SearchHit>)>,
+    search_receiver: std::sync::mpsc::Receiver<(String, Vec<SearchHit>)>,
+    search_results: Option<Vec<SearchHit>>,
     yt_sender: std::sync::mpsc::Sender<Vec<CtcVideo>>,
     yt_receiver: std::sync::mpsc::Receiver<Vec<CtcVideo>>,
     completion_sender: std::sync::mpsc::Sender<HashMap<VideoId, bool>>,
     completion_receiver: std::sync::mpsc::Receiver<HashMap<VideoId, bool>>,
-    error_sender: std::sync::mpsc::Sender<String>,
-    error_receiver: std::sync::mpsc::Receiver<String>,
+    error_sender: std::sync::mpsc::Sender<DataError>,
+    error_receiver: std::sync::mpsc::Receiver<DataError>,
+    import_sender: std::sync::mpsc::Sender<Result<usize, DataError>>,
+    import_receiver: std::sync::mpsc::Receiver<Result<usize, DataError>>,
+    rules_receiver: std::sync::mpsc::Receiver<Vec<FilterRule>>,
+    channels_sender: std::sync::mpsc::Sender<Vec<Channel>>,
+    channels_receiver: std::sync::mpsc::Receiver<Vec<Channel>>,
+    channels: Vec<Channel>,
+    pub channel_filter: Option<String>,
+    playlists_sender: std::sync::mpsc::Sender<PlaylistData>,
+    playlists_receiver: std::sync::mpsc::Receiver<PlaylistData>,
+    playlists: Vec<Playlist>,
+    playlist_videos: HashMap<String, Vec<VideoId>>,
+    pub playlist_filter: Option<String>,
+    shared_puzzles_sender: std::sync::mpsc::Sender<HashMap<PuzzleId, Vec<VideoId>>>,
+    shared_puzzles_receiver: std::sync::mpsc::Receiver<HashMap<PuzzleId, Vec<VideoId>>>,
+    shared_puzzles: HashMap<PuzzleId, Vec<VideoId>>,
+    puzzle_descriptions_sender: std::sync::mpsc::Sender<HashMap<PuzzleId, PuzzleDescription>>,
+    puzzle_descriptions_receiver: std::sync::mpsc::Receiver<HashMap<PuzzleId, PuzzleDescription>>,
+    puzzle_descriptions: HashMap<PuzzleId, PuzzleDescription>,
+    sudoku_player: Option<SudokuPlayer>,
+    annotations_sender: std::sync::mpsc::Sender<Annotations>,
+    annotations_receiver: std::sync::mpsc::Receiver<Annotations>,
+    notes: HashMap<VideoId, String>,
+    tags: HashMap<VideoId, Vec<String>>,
+    video_detail: Option<VideoDetail>,
+    pub view_mode: ViewMode,
+    pub group_by: GroupBy,
+    thumbnails: Thumbnails,
+    selection: HashSet<VideoId>,
+    selection_anchor: Option<VideoId>,
+    bulk_tag: String,
+    export_path: String,
+    history: EditHistory,
+    edit_sender: tokio::sync::mpsc::UnboundedSender<Vec<Change>>,
+    toast: Option<Toast>,
+    visible_videos: Vec<VideoId>,
+    scroll_to_selected: bool,
+    pub filter_rules: Vec<FilterRule>,
+    rule_set: RuleSet,
     pub yt_db: YoutubeDatabase,
     loading_completion: bool,
     completion_loaded: bool,
     loading_videos: bool,
     pub api_key: Option<String>,
-    pub api_error: Option<String>,
+    pub source_kind: SourceKind,
+    pub api_error: Option<DataError>,
 }
 impl VideoGrid {
     /// Creates a new instance of `VideoGrid`.
@@ -40,6 +166,36 @@ impl VideoGrid {
         let (yt_sender, yt_receiver) = std::sync::mpsc::channel();
         let (completion_sender, completion_receiver) = std::sync::mpsc::channel();
         let (error_sender, error_receiver) = std::sync::mpsc::channel();
+        let (import_sender, import_receiver) = std::sync::mpsc::channel();
+        let (rules_sender, rules_receiver) = std::sync::mpsc::channel();
+        let (channels_sender, channels_receiver) = std::sync::mpsc::channel();
+        let (playlists_sender, playlists_receiver) = std::sync::mpsc::channel();
+        let (shared_puzzles_sender, shared_puzzles_receiver) = std::sync::mpsc::channel();
+        let (puzzle_descriptions_sender, puzzle_descriptions_receiver) = std::sync::mpsc::channel();
+        let (annotations_sender, annotations_receiver) = std::sync::mpsc::channel();
+        let (search_sender, search_receiver) = std::sync::mpsc::channel();
+
+        // Save edits one at a time, so an undo can't overtake the edit it undoes
+        let (edit_sender, mut edit_receiver) = tokio::sync::mpsc::unbounded_channel::<Vec<Change>>();
+        let db = yt_db.clone();
+        tokio::spawn(async move {
+            while let Some(changes) = edit_receiver.recv().await {
+                if let Err(e) = db.apply_changes(&changes).await {
+                    error!("Error saving changes: {e}");
+                }
+            }
+        });
+
+        // Load the user's filter rules in the background
+        let db = yt_db.clone();
+        tokio::spawn(async move {
+            match db.get_filter_rules().await {
+                Ok(rules) => {
+                    rules_sender.send(rules).ok();
+                }
+                Err(e) => error!("Error loading filter rules: {e}"),
+            }
+        });
 
         Self {
             videos,
@@ -47,17 +203,63 @@ impl VideoGrid {
             show_completed_videos: false,
             show_without_links: false,
             filter_text: String::new(),
+            parsed_filter_text: String::new(),
+            filter_query: Ok(Query::default()),
+            search_descriptions: false,
+            searched_text: String::new(),
+            search_sender,
+            search_receiver,
+            search_results: None,
             yt_sender,
             yt_receiver,
             completion_sender,
             completion_receiver,
             error_sender,
             error_receiver,
+            import_sender,
+            import_receiver,
+            rules_receiver,
+            channels_sender,
+            channels_receiver,
+            channels: Vec::new(),
+            channel_filter: None,
+            playlists_sender,
+            playlists_receiver,
+            playlists: Vec::new(),
+            playlist_videos: HashMap::new(),
+            playlist_filter: None,
+            shared_puzzles_sender,
+            shared_puzzles_receiver,
+            shared_puzzles: HashMap::new(),
+            puzzle_descriptions_sender,
+            puzzle_descriptions_receiver,
+            puzzle_descriptions: HashMap::new(),
+            sudoku_player: None,
+            annotations_sender,
+            annotations_receiver,
+            notes: HashMap::new(),
+            tags: HashMap::new(),
+            video_detail: None,
+            view_mode: ViewMode::default(),
+            group_by: GroupBy::default(),
+            thumbnails: Thumbnails::new(),
+            selection: HashSet::new(),
+            selection_anchor: None,
+            bulk_tag: String::new(),
+            export_path: CONFIG_DIR.join("selected_videos.json").to_string_lossy().into_owned(),
+            history: EditHistory::default(),
+            edit_sender,
+            toast: None,
+            visible_videos: Vec::new(),
+            scroll_to_selected: false,
+            filter_rules: Vec::new(),
+            rule_set: RuleSet::default(),
             yt_db,
             loading_completion: false,
             completion_loaded: false,
             loading_videos: false,
             api_key,
+            source_kind: SourceKind::Api,
             api_error: None,
         }
     }
@@ -74,6 +276,121 @@ impl VideoGrid {
         self.api_key.is_some()
     }
 
+    /// Sets where videos are loaded from and triggers a refresh.
+    pub fn set_source_kind(&mut self, source_kind: SourceKind) {
+        self.source_kind = source_kind;
+        self.api_error = None;
+        self.refresh_videos();
+    }
+
+    /// Returns whether the video grid has a usable video source.
+    pub fn has_video_source(&self) -> bool {
+        self.source_kind == SourceKind::Feed || self.has_api_key()
+    }
+
+    /// Returns which videos are shown, for saving as UI state or a preset.
+    pub fn filter_settings(&self) -> FilterSettings {
+        FilterSettings {
+            show_completed_videos: self.show_completed_videos,
+            show_without_links: self.show_without_links,
+            filter_text: self.filter_text.clone(),
+            search_descriptions: self.search_descriptions,
+            channel_filter: self.channel_filter.clone(),
+            playlist_filter: self.playlist_filter.clone(),
+        }
+    }
+
+    /// Shows the videos matching saved filter settings.
+    pub fn apply_filter_settings(&mut self, filter: FilterSettings) {
+        self.show_completed_videos = filter.show_completed_videos;
+        self.show_without_links = filter.show_without_links;
+        self.filter_text = filter.filter_text;
+        self.search_descriptions = filter.search_descriptions;
+        self.channel_filter = filter.channel_filter;
+        self.playlist_filter = filter.playlist_filter;
+    }
+
+    /// Returns the syntax error in the filter text, if there is one.
+    pub fn filter_error(&mut self) -> Option<&QueryError> {
+        if self.search_descriptions {
+            return None; // Search text isn't a query
+        }
+        self.parse_filter_text();
+        self.filter_query.as_ref().err()
+    }
+
+    /// Parses the filter text into a query if it has changed since it was last parsed.
+    fn parse_filter_text(&mut self) {
+        if self.parsed_filter_text != self.filter_text {
+            self.filter_query = Query::parse(&self.filter_text);
+            self.parsed_filter_text = self.filter_text.clone();
+        }
+    }
+
+    /// Searches titles and descriptions for the filter text in the background if it has changed.
+    fn update_search(&mut self, ctx: &egui::Context) {
+        let text = if self.search_descriptions { self.filter_text.trim() } else { "" };
+        if text == self.searched_text {
+            return;
+        }
+
+        self.searched_text = text.to_string();
+        if text.is_empty() {
+            self.search_results = None;
+            return;
+        }
+
+        // Keep showing the previous results until the new ones arrive
+        let text = text.to_string();
+        let db = self.yt_db.clone();
+        let search_sender = self.search_sender.clone();
+        let ctx = ctx.clone();
+        tokio::spawn(async move {
+            match db.search(&text, SEARCH_LIMIT).await {
+                Ok(hits) => {
+                    search_sender.send((text, hits)).ok();
+                    ctx.request_repaint();
+                }
+                Err(e) => error!("Error searching videos: {e}"),
+            }
+        });
+    }
+
+    /// Returns the videos to list, with their search hits when searching descriptions (best match first).
+    fn listed_videos<'a>(
+        videos: &'a [CtcVideo],
+        search_results: Option<&'a [SearchHit]>,
+    ) -> Vec<(&'a CtcVideo, Option<&'a SearchHit>)> {
+        let Some(search_results) = search_results else {
+            return videos.iter().map(|video| (video, None)).collect();
+        };
+
+        let videos = videos
+            .iter()
+            .map(|video| (&video.id, video))
+            .collect::<HashMap<_, _>>();
+        search_results
+            .iter()
+            .filter_map(|hit| Some((*videos.get(&hit.video_id)?, Some(hit))))
+            .collect()
+    }
+
+    /// Shows a search excerpt with the matching words highlighted.
+    fn show_search_snippet(ui: &mut egui::Ui, hit: &SearchHit) {
+        ui.horizontal_wrapped(|ui| {
+            ui.spacing_mut().item_spacing.x = 0.0;
+            for (text, highlighted) in hit.highlights() {
+                let text = RichText::new(text.replace('\n', " ")).small();
+                ui.label(if highlighted { text.strong().color(ui.visuals().warn_fg_color) } else { text });
+            }
+        });
+    }
+
+    /// Returns whether the videos and their completion statuses have been loaded.
+    pub fn is_loaded(&self) -> bool {
+        self.completion_loaded && !self.loading_videos && !self.videos.is_empty()
+    }
+
     /// Resets the video loading state to trigger a refresh.
     pub fn refresh_videos(&mut self) {
         self.loading_videos = false;
@@ -82,8 +399,19 @@ impl VideoGrid {
 
     pub fn load_completion_data(&self, ctx: egui::Context) {
         let sender = self.completion_sender.clone();
+        let annotations_sender = self.annotations_sender.clone();
         let db = self.yt_db.clone();
         tokio::spawn(async move {
+            let notes = db.get_all_video_notes().await.unwrap_or_else(|e| {
+                error!("Error fetching notes: {e}");
+                HashMap::new()
+            });
+            let tags = db.get_all_video_tags().await.unwrap_or_else(|e| {
+                error!("Error fetching tags: {e}");
+                HashMap::new()
+            });
+            annotations_sender.send((notes, tags)).ok();
+
             let completion_data =
                 db.get_all_video_completion_statuses()
                     .await
@@ -104,122 +432,575 @@ impl VideoGrid {
         });
     }
 
-    fn set_completion_status(&self, video_id: &VideoId, completed: bool) {
-        let db = self.yt_db.clone();
-        let video_id = video_id.clone();
-        tokio::spawn(async move {
-            if let Err(e) = db.set_video_completion_status(&video_id, completed).await {
-                error!("Error updating completion status: {e}");
+    /// Marks a video as completed or not, as an undoable edit.
+    fn set_completed(&mut self, video_id: &VideoId, completed: bool) {
+        let before = self.video_completion_statuses.get(video_id) == Some(&true);
+        self.perform(vec![Change::Completion {
+            video_id: video_id.clone(),
+            before,
+            after: completed,
+        }]);
+    }
+
+    /// Changes a video's notes, as an undoable edit.
+    fn set_notes(&mut self, video_id: &VideoId, notes: String) {
+        let before = self.notes.get(video_id).cloned().unwrap_or_default();
+        self.perform(vec![Change::Notes {
+            video_id: video_id.clone(),
+            before,
+            after: notes,
+        }]);
+    }
+
+    /// Changes a video's tags, as an undoable edit.
+    fn set_tags(&mut self, video_id: &VideoId, tags: Vec<String>) {
+        let before = self.tags.get(video_id).cloned().unwrap_or_default();
+        self.perform(vec![Change::Tags {
+            video_id: video_id.clone(),
+            before,
+            after: tags,
+        }]);
+    }
+
+    /// Makes an edit, records it for undoing and offers to undo it.
+    fn perform(&mut self, changes: Vec<Change>) {
+        let edit = Edit::new(changes);
+        if edit.is_empty() {
+            return;
+        }
+
+        self.apply_changes(&edit.changes);
+        self.toast = Some(Toast::new(self.describe_edit(&edit), Some(ToastAction::Undo)));
+        self.history.push(edit);
+    }
+
+    /// Undoes the most recent edit.
+    pub fn undo(&mut self) {
+        if let Some(edit) = self.history.undo() {
+            self.apply_changes(&edit.inverse().changes);
+            let message = format!("Undone: {}", self.describe_edit(&edit));
+            self.toast = Some(Toast::new(message, Some(ToastAction::Redo)));
+        }
+    }
+
+    /// Makes the most recently undone edit again.
+    pub fn redo(&mut self) {
+        if let Some(edit) = self.history.redo() {
+            self.apply_changes(&edit.changes);
+            self.toast = Some(Toast::new(self.describe_edit(&edit), Some(ToastAction::Undo)));
+        }
+    }
+
+    /// Describes the edit `undo` would undo, if there is one.
+    pub fn undo_description(&self) -> Option<String> {
+        self.history.next_undo().map(|edit| self.describe_edit(edit))
+    }
+
+    /// Describes the edit `redo` would redo, if there is one.
+    pub fn redo_description(&self) -> Option<String> {
+        self.history.next_redo().map(|edit| self.describe_edit(edit))
+    }
+
+    /// Describes an edit, naming videos by their titles.
+    fn describe_edit(&self, edit: &Edit) -> String {
+        edit.describe(|video_id| {
+            self.videos
+                .iter()
+                .find(|video| video.id == *video_id)
+                .map_or_else(|| video_id.to_string(), |video| video.title.clone())
+        })
+    }
+
+    /// Shows the new values in the grid and the detail panel, and queues them for saving.
+    fn apply_changes(&mut self, changes: &[Change]) {
+        for change in changes {
+            let video_detail = self
+                .video_detail
+                .as_mut()
+                .filter(|detail| detail.video_id() == change.video_id());
+            match change {
+                Change::Completion { video_id, after, .. } => {
+                    self.video_completion_statuses.insert(video_id.clone(), *after);
+                    if let Some(video_detail) = video_detail {
+                        video_detail.record_completion(*after);
+                    }
+                }
+                Change::Notes { video_id, after, .. } => {
+                    if after.trim().is_empty() {
+                        self.notes.remove(video_id);
+                    } else {
+                        self.notes.insert(video_id.clone(), after.clone());
+                    }
+                    if let Some(video_detail) = video_detail {
+                        video_detail.set_notes(after);
+                    }
+                }
+                Change::Tags { video_id, after, .. } => {
+                    if after.is_empty() {
+                        self.tags.remove(video_id);
+                    } else {
+                        self.tags.insert(video_id.clone(), after.clone());
+                    }
+                    if let Some(video_detail) = video_detail {
+                        video_detail.set_tags(after);
+                    }
+                }
+            }
+        }
+
+        if self.edit_sender.send(changes.to_vec()).is_err() {
+            error!("Error saving changes: the writer has stopped");
+        }
+    }
+
+    /// Shows the toast for the latest edit until it times out.
+    pub fn show_toast(&mut self, ctx: &egui::Context) {
+        let Some(toast) = &self.toast else {
+            return;
+        };
+        let remaining = TOAST_DURATION.saturating_sub(toast.shown_at.elapsed());
+        if remaining.is_zero() {
+            self.toast = None;
+            return;
+        }
+        ctx.request_repaint_after(remaining);
+
+        let mut clicked = None;
+        let mut dismissed = false;
+        egui::Area::new(egui::Id::new("edit_toast"))
+            .anchor(egui::Align2::CENTER_BOTTOM, [0.0, -20.0])
+            .order(egui::Order::Foreground)
+            .show(ctx, |ui| {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label(&toast.message);
+                        if let Some(action) = toast.action {
+                            let button_text = match action {
+                                ToastAction::Undo => "Undo",
+                                ToastAction::Redo => "Redo",
+                            };
+                            if ui.button(button_text).clicked() {
+                                clicked = Some(action);
+                            }
+                        }
+                        if ui.small_button("✖").on_hover_text("Dismiss").clicked() {
+                            dismissed = true;
+                        }
+                    });
+                });
+            });
+
+        match clicked {
+            Some(ToastAction::Undo) => self.undo(),
+            Some(ToastAction::Redo) => self.redo(),
+            None if dismissed => self.toast = None,
+            None => {}
+        }
+    }
+
+    /// Opens the detail panel for a video.
+    fn select_video(&mut self, video_id: &VideoId) {
+        let notes = self.notes.get(video_id).map_or("", String::as_str);
+        let tags = self.tags.get(video_id).map_or(&[][..], Vec::as_slice);
+        self.video_detail = Some(VideoDetail::new(self.yt_db.clone(), video_id.clone(), notes, tags));
+    }
+
+    /// Selects the video `step` rows below (or above, if negative) the selected one, or the first
+    /// listed video if none is selected.
+    pub fn move_selection(&mut self, step: isize) {
+        let position = self
+            .video_detail
+            .as_ref()
+            .and_then(|detail| self.visible_videos.iter().position(|id| id == detail.video_id()));
+        let next = match position {
+            Some(position) => position
+                .saturating_add_signed(step)
+                .min(self.visible_videos.len().saturating_sub(1)),
+            None => 0,
+        };
+        if position == Some(next) {
+            return;
+        }
+        if let Some(next_id) = self.visible_videos.get(next).cloned() {
+            self.select_video(&next_id);
+            self.scroll_to_selected = true;
+        }
+    }
+
+    /// Handles a click on a video's title: Ctrl-click adds or removes it from the selection,
+    /// Shift-click selects the videos between it and the last clicked one, and a plain click opens
+    /// its details.
+    fn click_title(&mut self, video_id: VideoId, modifiers: egui::Modifiers) {
+        if modifiers.shift {
+            let anchor = self.selection_anchor.as_ref().unwrap_or(&video_id);
+            let position = |id: &VideoId| self.visible_videos.iter().position(|visible| visible == id);
+            if let (Some(from), Some(to)) = (position(anchor), position(&video_id)) {
+                let range = self.visible_videos[from.min(to)..=from.max(to)].to_vec();
+                if !modifiers.command {
+                    self.selection.clear();
+                }
+                self.selection.extend(range);
+            }
+        } else if modifiers.command {
+            if !self.selection.remove(&video_id) {
+                self.selection.insert(video_id.clone());
+            }
+            self.selection_anchor = Some(video_id);
+        } else {
+            self.selection.clear();
+            self.select_video(&video_id);
+            self.selection_anchor = Some(video_id);
+        }
+    }
+
+    /// Returns the selected videos in the order they're stored. The selection only holds videos
+    /// that are listed, as videos that stop being listed are dropped from it.
+    fn selected_videos(&self) -> impl Iterator<Item = &CtcVideo> {
+        self.videos.iter().filter(|video| self.selection.contains(&video.id))
+    }
+
+    /// Marks every selected video as completed or not, as one undoable edit.
+    fn set_selection_completed(&mut self, completed: bool) {
+        let changes = self
+            .selected_videos()
+            .map(|video| Change::Completion {
+                video_id: video.id.clone(),
+                before: self.video_completion_statuses.get(&video.id) == Some(&true),
+                after: completed,
+            })
+            .collect();
+        self.perform(changes);
+    }
+
+    /// Adds a tag to, or removes it from, every selected video as one undoable edit.
+    fn tag_selection(&mut self, tag: &str, add: bool) {
+        let changes = self
+            .selected_videos()
+            .map(|video| {
+                let before = self.tags.get(&video.id).cloned().unwrap_or_default();
+                let mut after = before.iter().filter(|t| *t != tag).cloned().collect::<Vec<_>>();
+                if add {
+                    after.push(tag.to_string());
+                    after.sort();
+                }
+                Change::Tags {
+                    video_id: video.id.clone(),
+                    before,
+                    after,
+                }
+            })
+            .collect();
+        self.perform(changes);
+    }
+
+    /// Writes the selected videos to a seed catalogue file in the background.
+    fn export_selection(&mut self, path: PathBuf) {
+        let entries = self.selected_videos().map(SeedVideo::from).collect::<Vec<_>>();
+        let message = format!("Exporting {} videos to {}", entries.len(), path.display());
+        let error_sender = self.error_sender.clone();
+        tokio::task::spawn_blocking(move || {
+            let count = entries.len();
+            match write_seed_videos(&path, entries) {
+                Ok(()) => info!("Exported {count} selected videos to {}", path.display()),
+                Err(e) => {
+                    error!("Error exporting selected videos: {e}");
+                    error_sender.send(e).ok();
+                }
             }
         });
+        self.toast = Some(Toast::new(message, None));
     }
 
-    /// Loads videos from the Cracking the Cryptic YouTube channel.
-    pub fn load_channel_videos(&mut self, ctx: egui::Context) {
-        // Don't attempt to load videos if we don't have an API key or if it's empty
-        let Some(api_key) = self.api_key.clone() else {
-            error!("Attempted to load videos without an API key");
-            self.loading_videos = false;
+    /// Shows the actions for the selected videos, or a button to select every listed video.
+    fn show_selection_bar(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            if self.selection.is_empty() {
+                let select_all = ui
+                    .small_button("Select all shown")
+                    .on_hover_text("Ctrl-click or Shift-click titles to select several videos");
+                if select_all.clicked() {
+                    self.selection = self.visible_videos.iter().cloned().collect();
+                }
+                return;
+            }
+
+            ui.label(RichText::new(format!("{} selected", self.selection.len())).strong());
+            if ui.button("✔ Mark complete").clicked() {
+                self.set_selection_completed(true);
+            }
+            if ui.button("Mark not complete").clicked() {
+                self.set_selection_completed(false);
+            }
+            ui.menu_button("🏷 Tag", |ui| {
+                ui.add(egui::TextEdit::singleline(&mut self.bulk_tag).hint_text("Tag"));
+                let tag = self.bulk_tag.trim().to_string();
+                ui.horizontal(|ui| {
+                    if ui.add_enabled(!tag.is_empty(), egui::Button::new("Add")).clicked() {
+                        self.tag_selection(&tag, true);
+                        ui.close_menu();
+                    }
+                    if ui.add_enabled(!tag.is_empty(), egui::Button::new("Remove")).clicked() {
+                        self.tag_selection(&tag, false);
+                        ui.close_menu();
+                    }
+                });
+            });
+            let enqueue = ui.button("➕ Enqueue").on_hover_text(format!(
+                "Tag the videos \"{QUEUE_TAG}\" to watch later; list them with tag:{QUEUE_TAG}"
+            ));
+            if enqueue.clicked() {
+                self.tag_selection(QUEUE_TAG, true);
+            }
+            ui.menu_button("💾 Export", |ui| {
+                ui.label("Save the selected videos as a catalogue file:");
+                ui.text_edit_singleline(&mut self.export_path);
+                let path = self.export_path.trim().to_string();
+                if ui.add_enabled(!path.is_empty(), egui::Button::new("Export")).clicked() {
+                    self.export_selection(PathBuf::from(path));
+                    ui.close_menu();
+                }
+            });
+            if ui.button("Select all shown").clicked() {
+                self.selection.extend(self.visible_videos.iter().cloned());
+            }
+            if ui.button("Clear selection").clicked() {
+                self.selection.clear();
+            }
+        });
+    }
+
+    /// Returns the video shown in the detail panel, if any.
+    pub fn selected_video(&self) -> Option<&CtcVideo> {
+        let video_id = self.video_detail.as_ref()?.video_id();
+        self.videos.iter().find(|video| video.id == *video_id)
+    }
+
+    /// Toggles whether the selected video is marked as completed.
+    pub fn toggle_selected_completion(&mut self) {
+        let Some(video_id) = self.video_detail.as_ref().map(|detail| detail.video_id().clone()) else {
             return;
         };
+        let completed = self.video_completion_statuses.get(&video_id) != Some(&true);
+        self.set_completed(&video_id, completed);
+    }
 
-        if api_key.trim().is_empty() {
-            error!("Attempted to load videos with empty API key");
-            self.loading_videos = false;
+    /// Returns whether the sudoku player is open, and so using the keyboard.
+    pub fn is_playing(&self) -> bool {
+        self.sudoku_player.is_some()
+    }
+
+    /// Shows the detail panel for the selected video, handling the arrow keys that move the
+    /// selection. Must be called before the central panel is shown.
+    pub fn show_detail_panel(&mut self, ctx: &egui::Context) {
+        if self.video_detail.is_none() {
             return;
         }
 
+        // The sudoku player uses the arrow keys while it's open
+        if !self.is_playing() && !ctx.wants_keyboard_input() {
+            let step = ctx.input_mut(|input| {
+                if input.consume_key(egui::Modifiers::NONE, egui::Key::ArrowDown) {
+                    1
+                } else if input.consume_key(egui::Modifiers::NONE, egui::Key::ArrowUp) {
+                    -1
+                } else {
+                    0
+                }
+            });
+            if step != 0 {
+                self.move_selection(step);
+            }
+        }
+
+        let Some(mut video_detail) = self.video_detail.take() else {
+            return;
+        };
+        let video_id = video_detail.video_id().clone();
+        let Some(video) = self.videos.iter().find(|video| video.id == video_id) else {
+            return;
+        };
+        let channel_name = self.channel_name(&video.channel_id);
+
+        let mut result = VideoDetailResult::Showing;
+        egui::SidePanel::right("video_detail")
+            .resizable(true)
+            .default_width(380.0)
+            .show(ctx, |ui| {
+                result = video_detail.show(ui, video, channel_name);
+            });
+        self.video_detail = Some(video_detail);
+
+        match result {
+            VideoDetailResult::Showing => {}
+            VideoDetailResult::NotesChanged(notes) => self.set_notes(&video_id, notes),
+            VideoDetailResult::TagsChanged(tags) => self.set_tags(&video_id, tags),
+            VideoDetailResult::Closed => self.video_detail = None,
+        }
+    }
+
+    /// Loads videos from every enabled channel.
+    pub fn load_channel_videos(&mut self, ctx: egui::Context) {
+        let api_client = match self.api_key.clone() {
+            Some(api_key) if !api_key.trim().is_empty() => Some(YouTubeClient::new(api_key)),
+            _ => None,
+        };
+
+        // Don't attempt to load videos from the API if we don't have an API key or if it's empty
+        let source: Box<dyn VideoSource> = match (self.source_kind, api_client.clone()) {
+            (SourceKind::Feed, _) => Box::new(FeedClient::new()),
+            (SourceKind::Api, Some(client)) => Box::new(client),
+            (SourceKind::Api, None) => {
+                error!("Attempted to load videos without an API key");
+                self.loading_videos = false;
+                return;
+            }
+        };
+
         let sender = self.yt_sender.clone();
+        let channels_sender = self.channels_sender.clone();
+        let playlists_sender = self.playlists_sender.clone();
+        let shared_puzzles_sender = self.shared_puzzles_sender.clone();
+        let puzzle_descriptions_sender = self.puzzle_descriptions_sender.clone();
         let error_sender = self.error_sender.clone();
-        let yt_client = YouTubeClient::new(api_key);
 
         // Spawn a new thread to fetch videos
-        let known_video_ids: HashSet<VideoId> =
+        let mut known_video_ids: HashSet<VideoId> =
             self.video_completion_statuses.keys().cloned().collect();
         let yt_db = self.yt_db.clone();
         tokio::spawn(async move {
-            let mut next_page_token = None;
-            let mut videos: Vec<CtcVideo> = vec![];
-            loop {
-                let mut get_next_page = true;
-                let result = yt_client
-                    .get_channel_page(CHANNEL_ID, next_page_token)
-                    .await;
-                match result {
-                    Ok(mut playlist_items) => {
-                        // If we have any of the video IDs in the database, then we don't need to get the next page.
-                        let result_video_ids =
-                            crate::data::youtube_api::get_video_ids_from_playlist(
-                                &mut playlist_items,
-                            );
-                        if result_video_ids
-                            .iter()
-                            .any(|id| known_video_ids.contains(id))
-                        {
-                            debug!(
-                                "Page contains a video already in the database, skipping next fetch."
-                            );
-                            get_next_page = false;
+            // On first launch, load the seed catalogue so only newer uploads need fetching
+            match seed_empty_database(&yt_db).await {
+                Ok(seeded) => known_video_ids.extend(seeded),
+                Err(e) => error!("Error loading seed catalogue: {e}"),
+            }
+
+            let channels = yt_db.get_channels().await.unwrap_or_else(|e| {
+                error!("Error fetching channels from database: {e}");
+                Vec::new()
+            });
+            channels_sender.send(channels.clone()).ok();
+
+            let mut videos = Vec::new();
+            for channel in channels.iter().filter(|channel| channel.enabled) {
+                let FetchResult {
+                    videos: channel_videos,
+                    error,
+                } = fetch_new_videos(source.as_ref(), channel, &known_video_ids).await;
+                videos.extend(channel_videos);
+                if let Some(e) = error {
+                    // Keep going so one failing channel doesn't hold back the others
+                    error_sender.send(e).ok();
+                }
+            }
+
+            // Sync the membership of every enabled playlist, picking up videos we haven't seen
+            let playlists = yt_db.get_playlists().await.unwrap_or_else(|e| {
+                error!("Error fetching playlists from database: {e}");
+                Vec::new()
+            });
+            for playlist in playlists.iter().filter(|playlist| playlist.enabled) {
+                match fetch_playlist(source.as_ref(), &playlist.id).await {
+                    Ok((video_ids, playlist_videos)) => {
+                        // Sources that only list the latest videos can't tell which ones left
+                        let saved = if source.lists_whole_playlists() {
+                            yt_db.set_playlist_videos(&playlist.id, &video_ids).await
+                        } else {
+                            yt_db.add_playlist_videos(&playlist.id, &video_ids).await
+                        };
+                        if let Err(e) = saved {
+                            error!("Error saving playlist {}: {e}", playlist.title);
                         }
-                        videos.extend(
-                            yt_client
-                                .load_playist_videos(&mut playlist_items)
-                                .await
-                                .unwrap_or_else(|e: Box<dyn std::error::Error + Send + Sync>| {
-                                    error!("Error loading videos from playlist: {e}");
-                                    Vec::new()
-                                })
-                                .into_iter()
-                                .filter(|video| !known_video_ids.contains(&video.id))
-                                .collect::<Vec<_>>(),
-                        );
-                        info!("{} new videos loaded.", videos.len());
-                        next_page_token = playlist_items.next_page_token.clone();
-                        if !get_next_page || next_page_token.is_none() {
-                            break;
+                        for video in playlist_videos {
+                            if !known_video_ids.contains(&video.id)
+                                && !videos.iter().any(|v: &CtcVideo| v.id == video.id)
+                            {
+                                videos.push(video);
+                            }
                         }
                     }
                     Err(e) => {
-                        let error_msg = format!("Error fetching videos: {e}");
-                        error!("{error_msg}");
-
-                        // Check if it's an authentication/API key error
-                        let error_string = e.to_string();
-                        if error_string.contains("403") || error_string.contains("Forbidden") || error_string.contains("API key") {
-                            error_sender.send("Invalid API key. Please check your YouTube API key and try again.".to_string()).ok();
-                        } else {
-                            error_sender.send(error_msg).ok();
-                        }
-                        break;
+                        error!("Error fetching playlist {}: {e}", playlist.title);
+                        error_sender.send(e).ok();
                     }
                 }
             }
+            let playlist_videos = yt_db.get_playlist_videos().await.unwrap_or_else(|e| {
+                error!("Error fetching playlist videos from database: {e}");
+                HashMap::new()
+            });
+            playlists_sender.send((playlists, playlist_videos)).ok();
+
+            // Fill in durations for videos previously added from the feed once a key is available
+            if let Some(client) = api_client {
+                match backfill_durations(&client, &yt_db).await {
+                    Ok(0) => {}
+                    Ok(count) => info!("Backfilled durations for {count} videos."),
+                    Err(e) => error!("Error backfilling video durations: {e}"),
+                }
+            }
 
             videos.extend(yt_db.get_all_video_data().await.unwrap_or_else(|e| {
                 error!("Error fetching video data from database: {e}");
                 Vec::new()
             }));
 
-            for video in &videos {
-                // Write data to DB if not already present
-                if !known_video_ids.contains(&video.id) {
-                    if let Err(e) = yt_db
-                        .set_video_data(
-                            &video.id,
-                            &video.title,
-                            &video.description,
-                            *video.date,
-                            *video.duration,
-                        )
-                        .await
-                    {
-                        error!("Error inserting video data into database: {e}");
-                    }
-                    if let Err(e) = yt_db.set_video_completion_status(&video.id, false).await {
-                        error!("Error inserting video completion status into database: {e}");
+            // Write data to DB if not already present
+            let new_videos = videos
+                .iter()
+                .filter(|video| !known_video_ids.contains(&video.id))
+                .map(SeedVideo::from)
+                .collect::<Vec<_>>();
+            if let Err(e) = yt_db.save_fetched_videos(&new_videos).await {
+                error!("Error inserting video data into database: {e}");
+            }
+
+            // Extract puzzle links with the user's configured hosts
+            let link_extractor = match yt_db.get_puzzle_hosts().await {
+                Ok(puzzle_hosts) => LinkExtractor::new(&puzzle_hosts),
+                Err(e) => {
+                    error!("Error fetching puzzle hosts from database: {e}");
+                    LinkExtractor::default()
+                }
+            };
+            for video in &mut videos {
+                video.extracted_links = link_extractor.extract(&video.description);
+            }
+
+            // Remember which puzzles each video links so repeats across videos can be spotted
+            if let Err(e) = yt_db.set_video_puzzles(&videos).await {
+                error!("Error saving video puzzles: {e}");
+            }
+            match yt_db.get_shared_puzzles().await {
+                Ok(shared_puzzles) => {
+                    shared_puzzles_sender.send(shared_puzzles).ok();
+                }
+                Err(e) => error!("Error fetching shared puzzles: {e}"),
+            }
+
+            // Decode puzzles embedded in links that haven't been decoded before
+            let mut puzzle_descriptions = yt_db.get_puzzle_descriptions().await.unwrap_or_else(|e| {
+                error!("Error fetching puzzle descriptions from database: {e}");
+                HashMap::new()
+            });
+            for link in videos.iter().flat_map(CtcVideo::puzzle_links) {
+                let Some(puzzle_id) = &link.puzzle_id else {
+                    continue;
+                };
+                if puzzle_descriptions.contains_key(puzzle_id) {
+                    continue;
+                }
+                match PuzzleDescription::from_url(&link.url) {
+                    Ok(Some(description)) => {
+                        if let Err(e) = yt_db.set_puzzle_description(puzzle_id, &description).await {
+                            error!("Error saving puzzle description: {e}");
+                        }
+                        puzzle_descriptions.insert(puzzle_id.clone(), description);
                     }
+                    Ok(None) => {}
+                    Err(e) => debug!("Couldn't decode puzzle {}: {e}", link.url),
                 }
             }
+            puzzle_descriptions_sender.send(puzzle_descriptions).ok();
 
             videos.sort_by(|a, b| a.duration.cmp(&b.duration));
 
@@ -230,9 +1011,168 @@ impl VideoGrid {
         });
     }
 
+    /// Returns the tracked channels.
+    pub fn channels(&self) -> &[Channel] {
+        &self.channels
+    }
+
+    /// Returns the display name of a channel, falling back to its ID.
+    fn channel_name<'a>(&'a self, channel_id: &'a str) -> &'a str {
+        self.channels
+            .iter()
+            .find(|channel| channel.id == channel_id)
+            .map(|channel| channel.display_name.as_str())
+            .unwrap_or(channel_id)
+    }
+
+    /// Returns the tracked playlists.
+    pub fn playlists(&self) -> &[Playlist] {
+        &self.playlists
+    }
+
+    /// Returns how many videos of a playlist are completed, and how many it has in total.
+    pub fn playlist_progress(&self, playlist_id: &str) -> (usize, usize) {
+        let video_ids = self
+            .playlist_videos
+            .get(playlist_id)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        let completed = video_ids
+            .iter()
+            .filter(|id| self.video_completion_statuses.get(id) == Some(&true))
+            .count();
+        (completed, video_ids.len())
+    }
+
+    /// Returns the decoded description of a linked puzzle, if the link embeds one.
+    fn puzzle_description(&self, link: &DescriptionLink) -> Option<&PuzzleDescription> {
+        self.puzzle_descriptions.get(link.puzzle_id.as_ref()?)
+    }
+
+    /// Returns whether the video, its decoded puzzles and the user's data about it match the query.
+    fn matches_query(&self, query: &Query, video: &CtcVideo) -> bool {
+        let context = VideoContext {
+            video,
+            channel_name: self.channel_name(&video.channel_id),
+            completed: self.video_completion_statuses.get(&video.id) == Some(&true),
+            tags: self.tags.get(&video.id).map(Vec::as_slice).unwrap_or_default(),
+            notes: self.notes.get(&video.id).map(String::as_str).unwrap_or_default(),
+            puzzles: video
+                .puzzle_links()
+                .filter_map(|link| self.puzzle_description(link))
+                .collect(),
+        };
+        query.matches(&context)
+    }
+
+    /// Shows the video's non-puzzle links (Patreon, merch, ...) in a menu grouped by kind.
+    fn show_other_links(&self, ui: &mut egui::Ui, video: &CtcVideo) {
+        let mut other_links = video.other_links().collect::<Vec<_>>();
+        if other_links.is_empty() {
+            ui.label("");
+            return;
+        }
+
+        other_links.sort_by_key(|link| link.kind);
+        ui.menu_button(format!("🔗 {}", other_links.len()), |ui| {
+            for (index, link) in other_links.iter().enumerate() {
+                if index == 0 || other_links[index - 1].kind != link.kind {
+                    ui.label(RichText::new(link.kind.label()).strong());
+                }
+                ui.hyperlink_to(link.display_label(), &link.url)
+                    .on_hover_text(&link.url);
+            }
+        })
+        .response
+        .on_hover_text("Show other links");
+    }
+
+    /// Marks a puzzle link that is also featured in other videos, noting whether it's been solved.
+    fn show_shared_puzzle(&self, ui: &mut egui::Ui, video: &CtcVideo, link: &DescriptionLink) {
+        let Some(video_ids) = link
+            .puzzle_id
+            .as_ref()
+            .and_then(|puzzle_id| self.shared_puzzles.get(puzzle_id))
+        else {
+            return;
+        };
+
+        let others = self
+            .videos
+            .iter()
+            .filter(|other| other.id != video.id && video_ids.contains(&other.id))
+            .collect::<Vec<_>>();
+        if others.is_empty() {
+            return;
+        }
+
+        let is_completed = |other: &&CtcVideo| self.video_completion_statuses.get(&other.id) == Some(&true);
+        let details = others
+            .iter()
+            .map(|other| {
+                let status = if is_completed(other) { " (completed)" } else { "" };
+                format!("{} — {}{status}", other.date, other.title)
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        let hover_text = format!("Also featured in:\n{details}");
+
+        if others.iter().any(is_completed) {
+            ui.label(RichText::new("✔ Solved elsewhere").color(egui::Color32::GREEN))
+                .on_hover_text(hover_text);
+        } else {
+            ui.label("🔁").on_hover_text(hover_text);
+        }
+    }
+
+    /// Applies new filter rules immediately and saves them in the background.
+    pub fn set_filter_rules(&mut self, rules: Vec<FilterRule>) {
+        self.rule_set = RuleSet::new(rules.clone());
+        self.filter_rules = rules.clone();
+        let db = self.yt_db.clone();
+        tokio::spawn(async move {
+            if let Err(e) = db.set_filter_rules(&rules).await {
+                error!("Error saving filter rules: {e}");
+            }
+        });
+    }
+
+    /// Imports a seed catalogue file in the background and reloads the grid afterwards.
+    pub fn import_catalogue(&self, path: PathBuf, ctx: egui::Context) {
+        let sender = self.import_sender.clone();
+        let db = self.yt_db.clone();
+        tokio::spawn(async move {
+            let result = match read_catalogue(&path) {
+                Ok(catalogue) => db.import_videos(&catalogue).await,
+                Err(e) => Err(e),
+            };
+            sender.send(result).ok();
+            ctx.request_repaint();
+        });
+    }
+
+    /// Exports all stored videos to a seed catalogue file in the background.
+    pub fn export_catalogue(&self, path: PathBuf) {
+        let error_sender = self.error_sender.clone();
+        let db = self.yt_db.clone();
+        tokio::spawn(async move {
+            let result = match db.get_all_video_data().await {
+                Ok(videos) => write_catalogue(&path, &videos).map(|_| videos.len()),
+                Err(e) => Err(e),
+            };
+            match result {
+                Ok(count) => info!("Exported {count} videos to {}", path.display()),
+                Err(e) => {
+                    error!("Error exporting catalogue: {e}");
+                    error_sender.send(e).ok();
+                }
+            }
+        });
+    }
+
     /// Updates the UI with the current state of the video grid.
     pub fn update(&mut self, ui: &mut egui::Ui, ctx: egui::Context) {
-        if self.api_key.is_none() {
+        if !self.has_video_source() {
             ui.label(RichText::new("API key not set").strong());
             return;
         }
@@ -243,8 +1183,54 @@ impl VideoGrid {
             self.completion_loaded = true;
         }
 
-        if let Ok(error_msg) = self.error_receiver.try_recv() {
-            self.api_error = Some(error_msg);
+        if let Ok(channels) = self.channels_receiver.try_recv() {
+            self.channels = channels;
+        }
+
+        if let Ok((playlists, playlist_videos)) = self.playlists_receiver.try_recv() {
+            self.playlists = playlists;
+            self.playlist_videos = playlist_videos;
+        }
+
+        if let Ok((notes, tags)) = self.annotations_receiver.try_recv() {
+            self.notes = notes;
+            self.tags = tags;
+        }
+
+        if let Ok(puzzle_descriptions) = self.puzzle_descriptions_receiver.try_recv() {
+            self.puzzle_descriptions = puzzle_descriptions;
+        }
+
+        if let Ok(shared_puzzles) = self.shared_puzzles_receiver.try_recv() {
+            self.shared_puzzles = shared_puzzles;
+        }
+
+        if let Ok((text, hits)) = self.search_receiver.try_recv() {
+            // Ignore results for text that has been edited since
+            if text == self.searched_text {
+                self.search_results = Some(hits);
+            }
+        }
+
+        if let Ok(rules) = self.rules_receiver.try_recv() {
+            self.rule_set = RuleSet::new(rules.clone());
+            self.filter_rules = rules;
+        }
+
+        if let Ok(import_result) = self.import_receiver.try_recv() {
+            match import_result {
+                Ok(count) => {
+                    info!("Imported {count} videos from catalogue.");
+                    // Reload completion data and videos so the imported entries show up
+                    self.completion_loaded = false;
+                    self.refresh_videos();
+                }
+                Err(e) => self.api_error = Some(e),
+            }
+        }
+
+        if let Ok(error) = self.error_receiver.try_recv() {
+            self.api_error = Some(error);
             self.loading_videos = false;
         }
 
@@ -277,61 +1263,401 @@ impl VideoGrid {
             return;
         }
 
-        egui::Grid::new("video_grid")
+        if let Some(player) = &mut self.sudoku_player {
+            match player.show(&ctx) {
+                SudokuPlayerResult::Showing => {}
+                SudokuPlayerResult::Solved => {
+                    let video_id = player.video_id().clone();
+                    self.set_completed(&video_id, true);
+                }
+                SudokuPlayerResult::Closed => self.sudoku_player = None,
+            }
+        }
+
+        // Filter text with syntax errors is shown under the filter box and otherwise ignored
+        self.parse_filter_text();
+        self.update_search(&ctx);
+
+        self.show_selection_bar(ui);
+        self.thumbnails.poll(&ctx);
+
+        // Completed videos that are hidden still count towards the progress of their group
+        let matching = Self::listed_videos(&self.videos, self.search_results.as_deref())
+            .into_iter()
+            .filter(|(video, _)| self.matches_filters(video))
+            .collect::<Vec<_>>();
+        let mut actions = ListActions::default();
+        let visible_videos = if self.group_by == GroupBy::None {
+            let listed = matching
+                .into_iter()
+                .filter(|(video, _)| self.is_shown(video))
+                .collect::<Vec<_>>();
+            self.show_videos(ui, "video_grid", &listed, &mut actions);
+            listed.iter().map(|(video, _)| video.id.clone()).collect()
+        } else {
+            let groups = self.group_by.group(&matching, |(video, _)| self.group_names(video));
+            self.show_groups(ui, &groups, &mut actions)
+        };
+        if visible_videos != self.visible_videos {
+            // Bulk actions only apply to videos that are still listed
+            let visible = visible_videos.iter().collect::<HashSet<_>>();
+            self.selection.retain(|id| visible.contains(id));
+        }
+        self.visible_videos = visible_videos;
+        self.scroll_to_selected = false;
+
+        if actions.opened_player.is_some() {
+            self.sudoku_player = actions.opened_player;
+        }
+        if let Some((video_id, modifiers)) = actions.clicked_title {
+            self.click_title(video_id, modifiers);
+        }
+        if let Some((video_id, completed)) = actions.toggled_completion {
+            self.set_completed(&video_id, completed);
+        }
+        self.thumbnails.request(actions.wanted_thumbnails, &ctx);
+    }
+
+    /// Returns whether the video is marked as completed.
+    fn is_completed(&self, video_id: &VideoId) -> bool {
+        self.video_completion_statuses.get(video_id) == Some(&true)
+    }
+
+    /// Returns whether a video that passes the filters is shown, as completed videos may be hidden.
+    fn is_shown(&self, video: &CtcVideo) -> bool {
+        self.show_completed_videos || !self.is_completed(&video.id)
+    }
+
+    /// Returns whether the video passes the filters, other than the one hiding completed videos.
+    fn matches_filters(&self, video: &CtcVideo) -> bool {
+        if let Some(channel_id) = &self.channel_filter {
+            if video.channel_id != *channel_id {
+                return false; // Skip videos from other channels
+            }
+        }
+
+        if let Some(playlist_id) = &self.playlist_filter {
+            let in_playlist = self
+                .playlist_videos
+                .get(playlist_id)
+                .is_some_and(|video_ids| video_ids.contains(&video.id));
+            if !in_playlist {
+                return false; // Skip videos outside the selected playlist
+            }
+        }
+
+        if self
+            .channels
+            .iter()
+            .any(|channel| channel.id == video.channel_id && !channel.enabled)
+        {
+            return false; // Skip videos from disabled channels
+        }
+
+        if self.rule_set.is_excluded(video) {
+            return false; // Skip videos hidden by the user's filter rules
+        }
+
+        if !self.show_without_links && video.primary_link().is_none() {
+            return false; // Skip videos without puzzle links
+        }
+
+        if let Some(query) = self.filter_query.as_ref().ok().filter(|_| !self.search_descriptions) {
+            if !query.is_empty() && !self.matches_query(query, video) {
+                return false; // Skip videos that don't match the filter
+            }
+        }
+
+        true
+    }
+
+    /// Returns the names of the groups the video is in when grouping by `group_by`.
+    fn group_names(&self, video: &CtcVideo) -> Vec<String> {
+        let date = |format: &str| video.date.known().map(|date| date.format(format).to_string());
+        match self.group_by {
+            GroupBy::None => Vec::new(),
+            GroupBy::Year => date("%Y").into_iter().collect(),
+            GroupBy::Month => date("%Y-%m").into_iter().collect(),
+            GroupBy::Setter => self.setter(video).map(str::to_string).into_iter().collect(),
+            GroupBy::Genre => video
+                .puzzle_links()
+                .filter_map(|link| self.puzzle_description(link))
+                .filter(|description| description.size > 0)
+                .flat_map(|description| match description.constraints.as_slice() {
+                    [] => vec!["classic".to_string()],
+                    constraints => constraints.to_vec(),
+                })
+                .collect(),
+            GroupBy::Playlist => self
+                .playlists
+                .iter()
+                .filter(|playlist| playlist.enabled)
+                .filter(|playlist| {
+                    self.playlist_videos
+                        .get(&playlist.id)
+                        .is_some_and(|video_ids| video_ids.contains(&video.id))
+                })
+                .map(|playlist| playlist.title.clone())
+                .collect(),
+        }
+    }
+
+    /// Shows each group under a collapsible header with its progress, returning the videos
+    /// shown in the open groups.
+    fn show_groups(&self, ui: &mut egui::Ui, groups: &[Group<ListedVideo>], actions: &mut ListActions) -> Vec<VideoId> {
+        let mut visible_videos = Vec::new();
+        let mut seen = HashSet::new();
+        for group in groups {
+            let completed = group
+                .items
+                .iter()
+                .filter(|(video, _)| self.is_completed(&video.id))
+                .count();
+            let total = group.items.len();
+            let shown = group
+                .items
+                .iter()
+                .copied()
+                .filter(|(video, _)| self.is_shown(video))
+                .collect::<Vec<_>>();
+
+            let id = ui.make_persistent_id(("video_group", self.group_by, &group.name));
+            egui::collapsing_header::CollapsingState::load_with_default_open(ui.ctx(), id, true)
+                .show_header(ui, |ui| {
+                    ui.label(RichText::new(&group.name).strong());
+                    ui.add(
+                        egui::ProgressBar::new(completed as f32 / total as f32)
+                            .desired_width(200.0)
+                            .text(format!("completed {completed} / total {total}")),
+                    );
+                })
+                .body(|ui| {
+                    if shown.is_empty() {
+                        ui.label("Every video in this group is completed.");
+                    } else {
+                        self.show_videos(ui, id, &shown, actions);
+                    }
+                    for (video, _) in &shown {
+                        if seen.insert(&video.id) {
+                            visible_videos.push(video.id.clone());
+                        }
+                    }
+                });
+        }
+        visible_videos
+    }
+
+    /// Shows the videos as a table or as cards, depending on the view mode.
+    fn show_videos(&self, ui: &mut egui::Ui, id_salt: impl std::hash::Hash, listed: &[ListedVideo], actions: &mut ListActions) {
+        match self.view_mode {
+            ViewMode::Table => self.show_table(ui, id_salt, listed, actions),
+            ViewMode::Cards => self.show_cards(ui, listed, actions),
+        }
+    }
+
+    /// Shows the videos as rows of a table.
+    fn show_table(&self, ui: &mut egui::Ui, id_salt: impl std::hash::Hash, listed: &[ListedVideo], actions: &mut ListActions) {
+        egui::Grid::new(id_salt)
             .striped(true)
-            .num_columns(6)
+            .num_columns(8)
             .show(ui, |ui| {
                 // Header row
                 ui.label(RichText::new("Title").strong());
+                ui.label(RichText::new("Channel").strong());
                 ui.label(RichText::new("Date").strong());
                 ui.label(RichText::new("Duration").strong());
                 ui.label(RichText::new("Video").strong());
                 ui.label(RichText::new("Puzzle").strong());
+                ui.label(RichText::new("Links").strong());
                 ui.label(RichText::new("Completed").strong());
                 ui.end_row();
 
-                for video in &self.videos {
-                    if !self.show_completed_videos {
-                        if let Some(true) = self.video_completion_statuses.get(&video.id) {
-                            continue; // Skip videos that are marked as completed
-                        }
-                    }
-
-                    if !self.show_without_links && video.extracted_links.is_empty() {
-                        continue; // Skip videos without links
-                    }
-
-                    if !self.filter_text.is_empty()
-                        && !video
-                            .title
-                            .to_lowercase()
-                            .contains(&self.filter_text.to_lowercase())
-                    {
-                        continue; // Skip videos that don't match the filter
-                    }
-
-                    ui.label(&video.title);
+                for &(video, search_hit) in listed {
+                    ui.vertical(|ui| {
+                        self.show_title(ui, video, search_hit, actions);
+                    });
+                    ui.label(self.channel_name(&video.channel_id));
                     ui.label(video.date.to_string());
                     ui.label(video.duration.to_string());
-                    ui.hyperlink_to("Watch video", video.get_video_url());
-                    if video.extracted_links.is_empty() {
-                        ui.label("No puzzle link found");
-                    } else {
-                        ui.hyperlink_to("Puzzle link", &video.extracted_links[0]);
-                    }
-                    let mut checked = self
-                        .video_completion_statuses
-                        .get(&video.id)
-                        .cloned()
-                        .unwrap_or(false);
-                    if ui.checkbox(&mut checked, "").clicked() {
-                        self.video_completion_statuses
-                            .insert(video.id.clone(), checked);
-                        // Update the database with the new completion status
-                        self.set_completion_status(&video.id, checked);
-                    }
+                    ui.horizontal(|ui| {
+                        Self::show_watch_links(ui, video);
+                    });
+                    self.show_puzzle_links(ui, video, actions);
+                    self.show_other_links(ui, video);
+                    self.show_completion_checkbox(ui, video, "", actions);
                     ui.end_row();
                 }
             });
     }
+
+    /// Shows the videos as cards wrapping to the width of the window.
+    fn show_cards(&self, ui: &mut egui::Ui, listed: &[ListedVideo], actions: &mut ListActions) {
+        ui.with_layout(egui::Layout::left_to_right(egui::Align::TOP).with_main_wrap(true), |ui| {
+            ui.spacing_mut().item_spacing = egui::vec2(12.0, 12.0);
+            for &(video, search_hit) in listed {
+                self.show_card(ui, video, search_hit, actions);
+            }
+        });
+    }
+
+    /// Shows a video as a card with its thumbnail, details and links.
+    fn show_card(&self, ui: &mut egui::Ui, video: &CtcVideo, search_hit: Option<&SearchHit>, actions: &mut ListActions) {
+        egui::Frame::group(ui.style()).show(ui, |ui| {
+            ui.set_width(CARD_WIDTH);
+            ui.set_min_height(CARD_MIN_HEIGHT);
+            ui.vertical(|ui| {
+                ui.spacing_mut().item_spacing = egui::vec2(4.0, 4.0);
+                self.show_thumbnail(ui, video, actions);
+                self.show_title(ui, video, search_hit, actions);
+                let details = format!("{} • {} • {}", video.date, video.duration, self.channel_name(&video.channel_id));
+                ui.label(RichText::new(details).small());
+                if let Some(setter) = self.setter(video) {
+                    ui.label(RichText::new(format!("by {setter}")).small().italics());
+                }
+                ui.horizontal_wrapped(|ui| {
+                    Self::show_watch_links(ui, video);
+                    self.show_other_links(ui, video);
+                });
+                self.show_puzzle_links(ui, video, actions);
+                self.show_completion_checkbox(ui, video, "Completed", actions);
+            });
+        });
+    }
+
+    /// Shows a video's thumbnail, with a badge if it's completed, asking for it to be loaded
+    /// once it scrolls into view.
+    fn show_thumbnail(&self, ui: &mut egui::Ui, video: &CtcVideo, actions: &mut ListActions) {
+        let size = egui::vec2(CARD_WIDTH, CARD_WIDTH * 9.0 / 16.0);
+        let response = match self.thumbnails.get(&video.id) {
+            Some(texture) => ui.add(
+                egui::Image::new(texture)
+                    .fit_to_exact_size(size)
+                    .sense(egui::Sense::click()),
+            ),
+            None => {
+                let (rect, response) = ui.allocate_exact_size(size, egui::Sense::click());
+                ui.painter().rect_filled(rect, 4.0, ui.visuals().faint_bg_color);
+                if ui.is_rect_visible(rect) {
+                    actions.wanted_thumbnails.push(video.id.clone());
+                }
+                response
+            }
+        };
+        if response.clicked() {
+            actions.clicked_title = Some((video.id.clone(), ui.input(|input| input.modifiers)));
+        }
+
+        if self.is_completed(&video.id) {
+            let badge = egui::Rect::from_min_size(response.rect.left_top() + egui::vec2(6.0, 6.0), egui::vec2(90.0, 20.0));
+            ui.painter().rect_filled(badge, 4.0, egui::Color32::from_rgb(40, 130, 60));
+            ui.painter().text(
+                badge.center(),
+                egui::Align2::CENTER_CENTER,
+                "✔ Completed",
+                egui::FontId::proportional(12.0),
+                egui::Color32::WHITE,
+            );
+        }
+    }
+
+    /// Shows a video's title, highlighted when selected, with the search excerpt when searching.
+    fn show_title(&self, ui: &mut egui::Ui, video: &CtcVideo, search_hit: Option<&SearchHit>, actions: &mut ListActions) {
+        let is_selected = self
+            .video_detail
+            .as_ref()
+            .is_some_and(|detail| *detail.video_id() == video.id);
+        let highlighted = is_selected || self.selection.contains(&video.id);
+        let title = ui.selectable_label(highlighted, &video.title);
+        if let Some(hit) = search_hit {
+            ui.set_max_width(360.0);
+            Self::show_search_snippet(ui, hit);
+        }
+        if title.clicked() {
+            actions.clicked_title = Some((video.id.clone(), ui.input(|input| input.modifiers)));
+        }
+        if is_selected && self.scroll_to_selected {
+            title.scroll_to_me(Some(egui::Align::Center));
+        }
+    }
+
+    /// Shows links to watch the video, from the start or from where the solve starts.
+    fn show_watch_links(ui: &mut egui::Ui, video: &CtcVideo) {
+        ui.hyperlink_to("Watch video", video.get_video_url());
+        if let Some(chapter) = video.solve_start() {
+            ui.hyperlink_to("⏩", video.get_video_url_at(chapter.start))
+                .on_hover_text(format!("Jump to the start of the solve ({})", chapter.timestamp()));
+        }
+    }
+
+    /// Shows the link to the video's puzzle, any other puzzle links in a menu, and a button to
+    /// solve the puzzle here if it could be decoded.
+    fn show_puzzle_links(&self, ui: &mut egui::Ui, video: &CtcVideo, actions: &mut ListActions) {
+        let Some(link) = video.primary_link() else {
+            ui.label("No puzzle link found");
+            return;
+        };
+
+        ui.horizontal(|ui| {
+            let hover_text = match self.puzzle_description(link) {
+                Some(description) => format!("{}\n{}", link.display_label(), description.summary()),
+                None => link.display_label().to_string(),
+            };
+            ui.hyperlink_to("Puzzle link", &link.url)
+                .on_hover_text(hover_text);
+            let puzzle_link_count = video.puzzle_links().count();
+            if puzzle_link_count > 1 {
+                let text = format!("+{}", puzzle_link_count - 1);
+                ui.menu_button(text, |ui| {
+                    for link in video.puzzle_links() {
+                        ui.hyperlink_to(link.display_label(), &link.url)
+                            .on_hover_text(&link.url);
+                    }
+                })
+                .response
+                .on_hover_text("Show all puzzle links");
+            }
+            if let Some(description) = self.puzzle_description(link).filter(|description| description.is_playable()) {
+                if ui.small_button("▶ Solve").on_hover_text("Solve this puzzle here").clicked() {
+                    actions.opened_player = Some(SudokuPlayer::new(self.yt_db.clone(), video, description.clone()));
+                }
+            }
+            self.show_shared_puzzle(ui, video, link);
+        });
+    }
+
+    /// Shows a checkbox marking the video as completed.
+    fn show_completion_checkbox(&self, ui: &mut egui::Ui, video: &CtcVideo, text: &str, actions: &mut ListActions) {
+        let mut checked = self
+            .video_completion_statuses
+            .get(&video.id)
+            .cloned()
+            .unwrap_or(false);
+        if ui.checkbox(&mut checked, text).clicked() {
+            actions.toggled_completion = Some((video.id.clone(), checked));
+        }
+    }
+
+    /// Returns the setter of the video's puzzle, if a decoded puzzle names one.
+    fn setter<'a>(&'a self, video: &'a CtcVideo) -> Option<&'a str> {
+        video
+            .puzzle_links()
+            .filter_map(|link| self.puzzle_description(link))
+            .find_map(|description| description.author.as_deref())
+    }
+}
+
+/// Loads the user's seed catalogue, if they've provided one, into an empty database and returns
+/// the IDs it added.
+async fn seed_empty_database(db: &YoutubeDatabase) -> Result<Vec<VideoId>, DataError> {
+    if db.count_videos().await? > 0 {
+        return Ok(Vec::new());
+    }
+
+    let catalogue = first_run_catalogue(&CONFIG_DIR.join("seed_catalogue.json"))?;
+    let imported = db.import_videos(&catalogue).await?;
+    if imported > 0 {
+        info!("Seeded database with {imported} videos from the catalogue.");
+    }
+
+    Ok(catalogue.iter().map(|video| VideoId::new(&video.id)).collect())
 }
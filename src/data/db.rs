@@ -1,9 +1,36 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
 use log::{debug, info};
-use sqlx::{migrate::MigrateDatabase, Executor};
+use sqlx::{
+    migrate::MigrateDatabase,
+    sqlite::{SqliteConnectOptions, SqliteConnection, SqlitePoolOptions},
+    Executor,
+};
 
 use crate::CONFIG_DIR;
 
-use super::model::{CtcVideo, CtcVideoCompletionRow, CtcVideoRow};
+use super::{
+    error::DataError,
+    history::Change,
+    links::DEFAULT_PUZZLE_HOSTS,
+    model::{
+        Channel, CompletionEvent, CtcVideo, CtcVideoCompletionRow, CtcVideoRow, Playlist, VideoId,
+        CTC_CHANNEL_ID,
+    },
+    puzzle::PuzzleDescription,
+    puzzle_id::PuzzleId,
+    rules::{FilterRule, FilterRuleRow},
+    search::{fts_query, SearchHit, HIGHLIGHT_END, HIGHLIGHT_START},
+    seed::SeedVideo,
+    shortcuts::KeyBindings,
+    solve_state::SolveState,
+    source::SourceKind,
+    ui_state::{FilterPreset, UiState},
+};
 
 /// YouTube database for storing video data and completion status.
 #[derive(Clone)]
@@ -11,51 +38,163 @@ pub struct YoutubeDatabase {
     pub db: sqlx::SqlitePool,
 }
 impl YoutubeDatabase {
-    /// Creates a new instance of `YoutubeDatabase`.
-    pub async fn new() -> Self {
-        let db_path = CONFIG_DIR.join("db").join("ctc_tracker.db");
-        let path = db_path
-            .to_str()
-            .expect("Config directory path should be valid.");
+    /// Opens the database in the default location inside the configuration directory.
+    pub async fn new() -> Result<Self, DataError> {
+        Self::open(&Self::default_path()).await
+    }
+
+    /// Returns the default database path inside the configuration directory.
+    pub fn default_path() -> PathBuf {
+        CONFIG_DIR.join("db").join("ctc_tracker.db")
+    }
+
+    /// Opens the database at the given file path, creating it if it doesn't exist.
+    pub async fn open(db_path: &Path) -> Result<Self, DataError> {
+        let path = db_path.to_str().ok_or_else(|| {
+            DataError::Database(format!("Invalid database path: {}", db_path.display()))
+        })?;
 
         // Initialize the SQLite database with sqlx
         debug!("Database path: {}", path);
 
+        if let Some(parent) = db_path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent).map_err(|e| {
+                DataError::Database(format!("Failed to create {}: {e}", parent.display()))
+            })?;
+        }
+
         if !sqlx::Sqlite::database_exists(path).await.unwrap_or(false) {
             info!("Creating database {path}");
-            match sqlx::Sqlite::create_database(path).await {
-                Ok(_) => info!("Create db success"),
-                Err(error) => panic!("error: {}", error),
-            }
+            sqlx::Sqlite::create_database(path).await?;
         } else {
             info!("Database already exists");
         };
 
-        let pool = sqlx::SqlitePool::connect(path).await.unwrap_or_else(|e| {
-            panic!("Failed to connect to the local database: {}", e);
-        });
+        let pool = sqlx::SqlitePool::connect(path).await?;
+        Self::from_pool(pool).await
+    }
 
+    /// Opens the database from a SQLite connection URL such as `sqlite://profile.db` or `sqlite::memory:`.
+    pub async fn connect(url: &str) -> Result<Self, DataError> {
+        let options = SqliteConnectOptions::from_str(url)?.create_if_missing(true);
+        let pool_options = if url.contains(":memory:") || url.contains("mode=memory") {
+            // Every connection to an in-memory database gets its own copy, so keep exactly one alive.
+            SqlitePoolOptions::new()
+                .max_connections(1)
+                .idle_timeout(None)
+                .max_lifetime(None)
+        } else {
+            SqlitePoolOptions::new()
+        };
+        let pool = pool_options.connect_with(options).await?;
+        Self::from_pool(pool).await
+    }
+
+    /// Opens a fresh, empty in-memory database.
+    pub async fn in_memory() -> Result<Self, DataError> {
+        Self::connect("sqlite::memory:").await
+    }
+
+    /// Creates the schema on the given pool if needed.
+    async fn from_pool(pool: sqlx::SqlitePool) -> Result<Self, DataError> {
         // Create the video_completion table if it doesn't exist
         pool.execute("CREATE TABLE IF NOT EXISTS video_completion (id VARCHAR(10) PRIMARY KEY NOT NULL, completed BOOL NOT NULL);")
-            .await
-            .unwrap();
+            .await?;
 
         // Create the video_data table if it doesn't exist
         pool.execute("CREATE TABLE IF NOT EXISTS video_data (id VARCHAR(10) PRIMARY KEY NOT NULL, title TEXT NOT NULL, description TEXT NOT NULL, date INTEGER NOT NULL, duration INTEGER NOT NULL);")
-            .await
-            .unwrap();
+            .await?;
 
         // Create the settings table if it doesn't exist
         pool.execute("CREATE TABLE IF NOT EXISTS settings (key TEXT PRIMARY KEY NOT NULL, value TEXT NOT NULL);")
-            .await
-            .unwrap();
+            .await?;
 
-        Self { db: pool }
+        // Create the filter_rules table if it doesn't exist
+        pool.execute("CREATE TABLE IF NOT EXISTS filter_rules (id INTEGER PRIMARY KEY AUTOINCREMENT, action TEXT NOT NULL, match_kind TEXT NOT NULL, field TEXT NOT NULL, pattern TEXT NOT NULL, enabled BOOL NOT NULL);")
+            .await?;
+
+        // Tag existing videos with the channel they came from; everything before this was CTC
+        add_column_if_missing(
+            &pool,
+            "video_data",
+            "channel_id",
+            &format!("TEXT NOT NULL DEFAULT '{CTC_CHANNEL_ID}'"),
+        )
+        .await?;
+
+        // Create the channels table if it doesn't exist
+        pool.execute("CREATE TABLE IF NOT EXISTS channels (id TEXT PRIMARY KEY NOT NULL, display_name TEXT NOT NULL, uploads_playlist TEXT NOT NULL, enabled BOOL NOT NULL);")
+            .await?;
+
+        // Create the playlists and playlist_videos tables if they don't exist
+        pool.execute("CREATE TABLE IF NOT EXISTS playlists (id TEXT PRIMARY KEY NOT NULL, title TEXT NOT NULL, enabled BOOL NOT NULL);")
+            .await?;
+        pool.execute("CREATE TABLE IF NOT EXISTS playlist_videos (playlist_id TEXT NOT NULL, video_id TEXT NOT NULL, position INTEGER NOT NULL, PRIMARY KEY (playlist_id, video_id));")
+            .await?;
+
+        // Create the video_puzzles table if it doesn't exist
+        pool.execute("CREATE TABLE IF NOT EXISTS video_puzzles (video_id TEXT NOT NULL, puzzle_id TEXT NOT NULL, url TEXT NOT NULL, PRIMARY KEY (video_id, puzzle_id));")
+            .await?;
+
+        // Create the puzzle_descriptions table if it doesn't exist
+        pool.execute("CREATE TABLE IF NOT EXISTS puzzle_descriptions (puzzle_id TEXT PRIMARY KEY NOT NULL, description TEXT NOT NULL);")
+            .await?;
+
+        // Create the solve_states table if it doesn't exist
+        pool.execute("CREATE TABLE IF NOT EXISTS solve_states (video_id TEXT PRIMARY KEY NOT NULL, state TEXT NOT NULL);")
+            .await?;
+
+        // Create the completion_history table if it doesn't exist
+        pool.execute("CREATE TABLE IF NOT EXISTS completion_history (video_id TEXT NOT NULL, completed BOOL NOT NULL, changed_at INTEGER NOT NULL);")
+            .await?;
+
+        // Create the video_notes table if it doesn't exist
+        pool.execute("CREATE TABLE IF NOT EXISTS video_notes (video_id TEXT PRIMARY KEY NOT NULL, notes TEXT NOT NULL);")
+            .await?;
+
+        // Create the video_tags table if it doesn't exist
+        pool.execute("CREATE TABLE IF NOT EXISTS video_tags (video_id TEXT NOT NULL, tag TEXT NOT NULL, PRIMARY KEY (video_id, tag));")
+            .await?;
+
+        // Mirror titles and descriptions into a full-text index, kept in sync by triggers
+        let (search_tables,) =
+            sqlx::query_as::<_, (i64,)>("SELECT COUNT(*) FROM sqlite_master WHERE name = 'video_search'")
+                .fetch_one(&pool)
+                .await?;
+        pool.execute("CREATE VIRTUAL TABLE IF NOT EXISTS video_search USING fts5(title, description, content='video_data', content_rowid='rowid');")
+            .await?;
+        pool.execute("CREATE TRIGGER IF NOT EXISTS video_search_insert AFTER INSERT ON video_data BEGIN INSERT INTO video_search (rowid, title, description) VALUES (new.rowid, new.title, new.description); END;")
+            .await?;
+        pool.execute("CREATE TRIGGER IF NOT EXISTS video_search_delete AFTER DELETE ON video_data BEGIN INSERT INTO video_search (video_search, rowid, title, description) VALUES ('delete', old.rowid, old.title, old.description); END;")
+            .await?;
+        pool.execute("CREATE TRIGGER IF NOT EXISTS video_search_update AFTER UPDATE ON video_data BEGIN INSERT INTO video_search (video_search, rowid, title, description) VALUES ('delete', old.rowid, old.title, old.description); INSERT INTO video_search (rowid, title, description) VALUES (new.rowid, new.title, new.description); END;")
+            .await?;
+        if search_tables == 0 {
+            // Index the videos stored before the index existed
+            pool.execute("INSERT INTO video_search (video_search) VALUES ('rebuild');")
+                .await?;
+        }
+
+        let db = Self { db: pool };
+
+        // Track Cracking the Cryptic out of the box
+        if db.get_setting("channels_seeded").await?.is_none() {
+            db.add_channel(&Channel::new(CTC_CHANNEL_ID, "Cracking the Cryptic")).await?;
+            db.set_setting("channels_seeded", "true").await?;
+        }
+
+        // Seed the rules that used to be hard-coded, once, so users can edit or remove them
+        if db.get_setting("filter_rules_seeded").await?.is_none() {
+            db.set_filter_rules(&FilterRule::defaults()).await?;
+            db.set_setting("filter_rules_seeded", "true").await?;
+        }
+
+        Ok(db)
     }
 
     pub async fn get_all_video_completion_statuses(
         &self,
-    ) -> Result<Vec<CtcVideoCompletionRow>, sqlx::Error> {
+    ) -> Result<Vec<CtcVideoCompletionRow>, DataError> {
         let statuses = sqlx::query_as::<_, CtcVideoCompletionRow>(
             "SELECT id, completed FROM video_completion",
         )
@@ -65,26 +204,62 @@ impl YoutubeDatabase {
         Ok(statuses)
     }
 
-    pub async fn set_video_completion_status(
-        &self,
-        video_id: &str,
-        completed: bool,
-    ) -> Result<(), sqlx::Error> {
-        sqlx::query(
-            "INSERT INTO video_completion (id, completed) VALUES (?, ?) ON CONFLICT(id) DO UPDATE SET completed = excluded.completed"
+    /// Saves the new values of a user edit's changes in a single transaction.
+    pub async fn apply_changes(&self, changes: &[Change]) -> Result<(), DataError> {
+        let mut transaction = self.db.begin().await?;
+
+        for change in changes {
+            match change {
+                Change::Completion { video_id, after, .. } => {
+                    write_completion(&mut transaction, video_id, *after).await?
+                }
+                Change::Notes { video_id, after, .. } => write_notes(&mut transaction, video_id, after).await?,
+                Change::Tags { video_id, after, .. } => write_tags(&mut transaction, video_id, after).await?,
+            }
+        }
+
+        transaction.commit().await?;
+        Ok(())
+    }
+
+    /// Gets every change to a video's completion status, oldest first.
+    pub async fn get_completion_history(&self, video_id: &str) -> Result<Vec<CompletionEvent>, DataError> {
+        let events = sqlx::query_as::<_, CompletionEvent>(
+            "SELECT completed, changed_at FROM completion_history WHERE video_id = ? ORDER BY changed_at, rowid",
         )
         .bind(video_id)
-        .bind(completed)
-        .execute(&self.db)
+        .fetch_all(&self.db)
         .await?;
 
-        Ok(())
+        Ok(events)
+    }
+
+    /// Gets the user's notes for every video that has some.
+    pub async fn get_all_video_notes(&self) -> Result<HashMap<VideoId, String>, DataError> {
+        let rows = sqlx::query_as::<_, (VideoId, String)>("SELECT video_id, notes FROM video_notes")
+            .fetch_all(&self.db)
+            .await?;
+
+        Ok(rows.into_iter().collect())
+    }
+
+    /// Gets the tags of every tagged video, each video's tags sorted.
+    pub async fn get_all_video_tags(&self) -> Result<HashMap<VideoId, Vec<String>>, DataError> {
+        let rows = sqlx::query_as::<_, (VideoId, String)>("SELECT video_id, tag FROM video_tags ORDER BY tag")
+            .fetch_all(&self.db)
+            .await?;
+
+        let mut tags: HashMap<VideoId, Vec<String>> = HashMap::new();
+        for (video_id, tag) in rows {
+            tags.entry(video_id).or_default().push(tag);
+        }
+        Ok(tags)
     }
 
     /// Fetches all video data from the database.
-    pub async fn get_all_video_data(&self) -> Result<Vec<CtcVideo>, sqlx::Error> {
+    pub async fn get_all_video_data(&self) -> Result<Vec<CtcVideo>, DataError> {
         let rows = sqlx::query_as::<_, CtcVideoRow>(
-            "SELECT id, title, description, date, duration FROM video_data",
+            "SELECT id, title, description, date, duration, channel_id FROM video_data",
         )
         .fetch_all(&self.db)
         .await?;
@@ -94,49 +269,935 @@ impl YoutubeDatabase {
         Ok(videos)
     }
 
-    /// Sets video data in the database.
-    pub async fn set_video_data(
+    /// Saves videos fetched from YouTube in a single transaction, updating any already stored.
+    /// New videos start out not completed, without an entry in their completion history.
+    pub async fn save_fetched_videos(&self, videos: &[SeedVideo]) -> Result<(), DataError> {
+        let mut transaction = self.db.begin().await?;
+
+        for video in videos {
+            sqlx::query(
+                "INSERT INTO video_data (id, title, description, date, duration, channel_id) VALUES (?, ?, ?, ?, ?, ?) ON CONFLICT(id) DO UPDATE SET title = excluded.title, description = excluded.description, date = excluded.date, duration = excluded.duration, channel_id = excluded.channel_id"
+            )
+            .bind(&video.id)
+            .bind(&video.title)
+            .bind(&video.description)
+            .bind(video.date)
+            .bind(video.duration as i64)
+            .bind(&video.channel_id)
+            .execute(&mut *transaction)
+            .await?;
+
+            sqlx::query("INSERT INTO video_completion (id, completed) VALUES (?, false) ON CONFLICT(id) DO NOTHING")
+                .bind(&video.id)
+                .execute(&mut *transaction)
+                .await?;
+        }
+
+        transaction.commit().await?;
+        Ok(())
+    }
+
+    /// Searches video titles and descriptions, returning up to `limit` matches with the best first.
+    ///
+    /// Title matches count for more than description matches. Each hit has an excerpt of the
+    /// description with the matching words highlighted.
+    pub async fn search(&self, text: &str, limit: u32) -> Result<Vec<SearchHit>, DataError> {
+        let Some(query) = fts_query(text) else {
+            return Ok(Vec::new());
+        };
+
+        let hits = sqlx::query_as::<_, SearchHit>(
+            "SELECT video_data.id, snippet(video_search, 1, ?, ?, '…', 16) AS snippet FROM video_search JOIN video_data ON video_data.rowid = video_search.rowid WHERE video_search MATCH ? ORDER BY bm25(video_search, 10.0, 1.0) LIMIT ?"
+        )
+        .bind(HIGHLIGHT_START)
+        .bind(HIGHLIGHT_END)
+        .bind(query)
+        .bind(limit)
+        .fetch_all(&self.db)
+        .await?;
+
+        Ok(hits)
+    }
+
+    /// Counts the videos stored in the database.
+    pub async fn count_videos(&self) -> Result<i64, DataError> {
+        let (count,) = sqlx::query_as::<_, (i64,)>("SELECT COUNT(*) FROM video_data")
+            .fetch_one(&self.db)
+            .await?;
+
+        Ok(count)
+    }
+
+    /// Imports catalogue entries in a single transaction and returns how many were new.
+    ///
+    /// Existing videos keep their data, except that an unknown duration is filled in from the catalogue.
+    pub async fn import_videos(&self, videos: &[SeedVideo]) -> Result<usize, DataError> {
+        let mut transaction = self.db.begin().await?;
+        let mut imported = 0;
+
+        for video in videos {
+            let result = sqlx::query(
+                "INSERT INTO video_data (id, title, description, date, duration, channel_id) VALUES (?, ?, ?, ?, ?, ?) ON CONFLICT(id) DO NOTHING"
+            )
+            .bind(&video.id)
+            .bind(&video.title)
+            .bind(&video.description)
+            .bind(video.date)
+            .bind(video.duration as i64)
+            .bind(&video.channel_id)
+            .execute(&mut *transaction)
+            .await?;
+
+            if result.rows_affected() > 0 {
+                imported += 1;
+            } else if video.duration > 0 {
+                sqlx::query("UPDATE video_data SET duration = ? WHERE id = ? AND duration = 0")
+                    .bind(video.duration as i64)
+                    .bind(&video.id)
+                    .execute(&mut *transaction)
+                    .await?;
+            }
+
+            sqlx::query("INSERT INTO video_completion (id, completed) VALUES (?, false) ON CONFLICT(id) DO NOTHING")
+                .bind(&video.id)
+                .execute(&mut *transaction)
+                .await?;
+        }
+
+        transaction.commit().await?;
+        Ok(imported)
+    }
+
+    /// Gets the IDs of stored videos whose duration isn't known yet.
+    pub async fn get_video_ids_with_unknown_duration(&self) -> Result<Vec<VideoId>, DataError> {
+        let rows = sqlx::query_as::<_, (VideoId,)>("SELECT id FROM video_data WHERE duration = 0")
+            .fetch_all(&self.db)
+            .await?;
+
+        Ok(rows.into_iter().map(|(id,)| id).collect())
+    }
+
+    /// Updates the duration of a stored video.
+    pub async fn set_video_duration(&self, video_id: &str, duration: u64) -> Result<(), DataError> {
+        sqlx::query("UPDATE video_data SET duration = ? WHERE id = ?")
+            .bind(duration as i64)
+            .bind(video_id)
+            .execute(&self.db)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Gets all tracked channels.
+    pub async fn get_channels(&self) -> Result<Vec<Channel>, DataError> {
+        let channels = sqlx::query_as::<_, Channel>(
+            "SELECT id, display_name, uploads_playlist, enabled FROM channels ORDER BY display_name",
+        )
+        .fetch_all(&self.db)
+        .await?;
+
+        Ok(channels)
+    }
+
+    /// Adds a channel, or updates its name and playlist if it's already tracked.
+    pub async fn add_channel(&self, channel: &Channel) -> Result<(), DataError> {
+        sqlx::query(
+            "INSERT INTO channels (id, display_name, uploads_playlist, enabled) VALUES (?, ?, ?, ?) ON CONFLICT(id) DO UPDATE SET display_name = excluded.display_name, uploads_playlist = excluded.uploads_playlist"
+        )
+        .bind(&channel.id)
+        .bind(&channel.display_name)
+        .bind(&channel.uploads_playlist)
+        .bind(channel.enabled)
+        .execute(&self.db)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Enables or disables syncing and display of a channel.
+    pub async fn set_channel_enabled(&self, channel_id: &str, enabled: bool) -> Result<(), DataError> {
+        sqlx::query("UPDATE channels SET enabled = ? WHERE id = ?")
+            .bind(enabled)
+            .bind(channel_id)
+            .execute(&self.db)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Stops tracking a channel. Its stored videos are kept.
+    pub async fn remove_channel(&self, channel_id: &str) -> Result<(), DataError> {
+        sqlx::query("DELETE FROM channels WHERE id = ?")
+            .bind(channel_id)
+            .execute(&self.db)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Gets all tracked playlists.
+    pub async fn get_playlists(&self) -> Result<Vec<Playlist>, DataError> {
+        let playlists = sqlx::query_as::<_, Playlist>(
+            "SELECT id, title, enabled FROM playlists ORDER BY title",
+        )
+        .fetch_all(&self.db)
+        .await?;
+
+        Ok(playlists)
+    }
+
+    /// Adds a playlist, or updates its title if it's already tracked.
+    pub async fn add_playlist(&self, playlist: &Playlist) -> Result<(), DataError> {
+        sqlx::query(
+            "INSERT INTO playlists (id, title, enabled) VALUES (?, ?, ?) ON CONFLICT(id) DO UPDATE SET title = excluded.title"
+        )
+        .bind(&playlist.id)
+        .bind(&playlist.title)
+        .bind(playlist.enabled)
+        .execute(&self.db)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Enables or disables syncing and display of a playlist.
+    pub async fn set_playlist_enabled(&self, playlist_id: &str, enabled: bool) -> Result<(), DataError> {
+        sqlx::query("UPDATE playlists SET enabled = ? WHERE id = ?")
+            .bind(enabled)
+            .bind(playlist_id)
+            .execute(&self.db)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Stops tracking a playlist and forgets its membership. Its videos are kept.
+    pub async fn remove_playlist(&self, playlist_id: &str) -> Result<(), DataError> {
+        let mut transaction = self.db.begin().await?;
+
+        sqlx::query("DELETE FROM playlist_videos WHERE playlist_id = ?")
+            .bind(playlist_id)
+            .execute(&mut *transaction)
+            .await?;
+        sqlx::query("DELETE FROM playlists WHERE id = ?")
+            .bind(playlist_id)
+            .execute(&mut *transaction)
+            .await?;
+
+        transaction.commit().await?;
+        Ok(())
+    }
+
+    /// Replaces the videos of a playlist, in playlist order, in a single transaction.
+    pub async fn set_playlist_videos(&self, playlist_id: &str, video_ids: &[VideoId]) -> Result<(), DataError> {
+        let mut transaction = self.db.begin().await?;
+
+        sqlx::query("DELETE FROM playlist_videos WHERE playlist_id = ?")
+            .bind(playlist_id)
+            .execute(&mut *transaction)
+            .await?;
+
+        for (position, video_id) in video_ids.iter().enumerate() {
+            sqlx::query(
+                "INSERT INTO playlist_videos (playlist_id, video_id, position) VALUES (?, ?, ?) ON CONFLICT DO NOTHING"
+            )
+            .bind(playlist_id)
+            .bind(video_id.as_str())
+            .bind(position as i64)
+            .execute(&mut *transaction)
+            .await?;
+        }
+
+        transaction.commit().await?;
+        Ok(())
+    }
+
+    /// Adds videos to a playlist after the ones already in it, keeping its existing membership.
+    pub async fn add_playlist_videos(&self, playlist_id: &str, video_ids: &[VideoId]) -> Result<(), DataError> {
+        let mut transaction = self.db.begin().await?;
+
+        for video_id in video_ids {
+            sqlx::query(
+                "INSERT INTO playlist_videos (playlist_id, video_id, position)
+                 VALUES (?, ?, (SELECT COALESCE(MAX(position) + 1, 0) FROM playlist_videos WHERE playlist_id = ?))
+                 ON CONFLICT DO NOTHING",
+            )
+            .bind(playlist_id)
+            .bind(video_id.as_str())
+            .bind(playlist_id)
+            .execute(&mut *transaction)
+            .await?;
+        }
+
+        transaction.commit().await?;
+        Ok(())
+    }
+
+    /// Gets the videos of every tracked playlist, keyed by playlist ID, in playlist order.
+    pub async fn get_playlist_videos(&self) -> Result<HashMap<String, Vec<VideoId>>, DataError> {
+        let rows = sqlx::query_as::<_, (String, String)>(
+            "SELECT playlist_id, video_id FROM playlist_videos ORDER BY playlist_id, position",
+        )
+        .fetch_all(&self.db)
+        .await?;
+
+        let mut playlist_videos = HashMap::<String, Vec<VideoId>>::new();
+        for (playlist_id, video_id) in rows {
+            playlist_videos
+                .entry(playlist_id)
+                .or_default()
+                .push(VideoId::new(&video_id));
+        }
+        Ok(playlist_videos)
+    }
+
+    /// Replaces the canonical puzzle IDs linked from each video in a single transaction.
+    pub async fn set_video_puzzles(&self, videos: &[CtcVideo]) -> Result<(), DataError> {
+        let mut transaction = self.db.begin().await?;
+
+        sqlx::query("DELETE FROM video_puzzles")
+            .execute(&mut *transaction)
+            .await?;
+
+        for video in videos {
+            for link in &video.extracted_links {
+                let Some(puzzle_id) = &link.puzzle_id else {
+                    continue;
+                };
+                sqlx::query(
+                    "INSERT INTO video_puzzles (video_id, puzzle_id, url) VALUES (?, ?, ?) ON CONFLICT DO NOTHING"
+                )
+                .bind(video.id.as_str())
+                .bind(puzzle_id.as_str())
+                .bind(&link.url)
+                .execute(&mut *transaction)
+                .await?;
+            }
+        }
+
+        transaction.commit().await?;
+        Ok(())
+    }
+
+    /// Gets the puzzles featured in more than one video, with the IDs of those videos.
+    pub async fn get_shared_puzzles(&self) -> Result<HashMap<PuzzleId, Vec<VideoId>>, DataError> {
+        let rows = sqlx::query_as::<_, (PuzzleId, VideoId)>(
+            "SELECT puzzle_id, video_id FROM video_puzzles WHERE puzzle_id IN (SELECT puzzle_id FROM video_puzzles GROUP BY puzzle_id HAVING COUNT(*) > 1) ORDER BY puzzle_id, video_id",
+        )
+        .fetch_all(&self.db)
+        .await?;
+
+        let mut shared_puzzles = HashMap::<PuzzleId, Vec<VideoId>>::new();
+        for (puzzle_id, video_id) in rows {
+            shared_puzzles.entry(puzzle_id).or_default().push(video_id);
+        }
+        Ok(shared_puzzles)
+    }
+
+    /// Stores the decoded description of a puzzle.
+    pub async fn set_puzzle_description(
+        &self,
+        puzzle_id: &PuzzleId,
+        description: &PuzzleDescription,
+    ) -> Result<(), DataError> {
+        sqlx::query(
+            "INSERT INTO puzzle_descriptions (puzzle_id, description) VALUES (?, ?) ON CONFLICT(puzzle_id) DO UPDATE SET description = excluded.description"
+        )
+        .bind(puzzle_id.as_str())
+        .bind(serde_json::to_string(description)?)
+        .execute(&self.db)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Gets every decoded puzzle description, keyed by puzzle ID.
+    pub async fn get_puzzle_descriptions(
         &self,
-        video_id: &str,
-        title: &str,
-        description: &str,
-        date: i64,
-        duration: u64,
-    ) -> Result<(), sqlx::Error> {
+    ) -> Result<HashMap<PuzzleId, PuzzleDescription>, DataError> {
+        let rows = sqlx::query_as::<_, (PuzzleId, String)>(
+            "SELECT puzzle_id, description FROM puzzle_descriptions",
+        )
+        .fetch_all(&self.db)
+        .await?;
+
+        rows.into_iter()
+            .map(|(puzzle_id, description)| Ok((puzzle_id, serde_json::from_str(&description)?)))
+            .collect()
+    }
+
+    /// Gets the in-progress solve of a video's puzzle, if one was saved.
+    pub async fn get_solve_state(&self, video_id: &str) -> Result<Option<SolveState>, DataError> {
+        let result = sqlx::query_as::<_, (String,)>("SELECT state FROM solve_states WHERE video_id = ?")
+            .bind(video_id)
+            .fetch_optional(&self.db)
+            .await?;
+
+        Ok(result.map(|(state,)| serde_json::from_str(&state)).transpose()?)
+    }
+
+    /// Saves the in-progress solve of a video's puzzle.
+    pub async fn set_solve_state(&self, video_id: &str, state: &SolveState) -> Result<(), DataError> {
         sqlx::query(
-            "INSERT INTO video_data (id, title, description, date, duration) VALUES (?, ?, ?, ?, ?) ON CONFLICT(id) DO UPDATE SET title = excluded.title, description = excluded.description, date = excluded.date, duration = excluded.duration"
+            "INSERT INTO solve_states (video_id, state) VALUES (?, ?) ON CONFLICT(video_id) DO UPDATE SET state = excluded.state"
         )
         .bind(video_id)
-        .bind(title)
-        .bind(description)
-        .bind(date)
-        .bind(duration as i64)
+        .bind(serde_json::to_string(state)?)
         .execute(&self.db)
         .await?;
 
         Ok(())
     }
 
-    /// Gets the API key from the database.
-    pub async fn get_api_key(&self) -> Result<Option<String>, sqlx::Error> {
-        let result = sqlx::query_as::<_, (String,)>(
-            "SELECT value FROM settings WHERE key = 'api_key'"
+    /// Gets the user's filter rules in display order.
+    pub async fn get_filter_rules(&self) -> Result<Vec<FilterRule>, DataError> {
+        let rows = sqlx::query_as::<_, FilterRuleRow>(
+            "SELECT action, match_kind, field, pattern, enabled FROM filter_rules ORDER BY id",
         )
-        .fetch_optional(&self.db)
+        .fetch_all(&self.db)
         .await?;
 
+        Ok(rows.into_iter().map(FilterRule::from).collect())
+    }
+
+    /// Replaces the user's filter rules in a single transaction.
+    pub async fn set_filter_rules(&self, rules: &[FilterRule]) -> Result<(), DataError> {
+        let mut transaction = self.db.begin().await?;
+
+        sqlx::query("DELETE FROM filter_rules")
+            .execute(&mut *transaction)
+            .await?;
+
+        for rule in rules {
+            sqlx::query(
+                "INSERT INTO filter_rules (action, match_kind, field, pattern, enabled) VALUES (?, ?, ?, ?, ?)"
+            )
+            .bind(rule.action.as_str())
+            .bind(rule.match_kind.as_str())
+            .bind(rule.field.as_str())
+            .bind(&rule.pattern)
+            .bind(rule.enabled)
+            .execute(&mut *transaction)
+            .await?;
+        }
+
+        transaction.commit().await?;
+        Ok(())
+    }
+
+    /// Gets a value from the settings table.
+    pub async fn get_setting(&self, key: &str) -> Result<Option<String>, DataError> {
+        let result = sqlx::query_as::<_, (String,)>("SELECT value FROM settings WHERE key = ?")
+            .bind(key)
+            .fetch_optional(&self.db)
+            .await?;
+
         Ok(result.map(|(value,)| value))
     }
 
-    /// Sets the API key in the database.
-    pub async fn set_api_key(&self, api_key: &str) -> Result<(), sqlx::Error> {
+    /// Sets a value in the settings table.
+    pub async fn set_setting(&self, key: &str, value: &str) -> Result<(), DataError> {
         sqlx::query(
-            "INSERT INTO settings (key, value) VALUES ('api_key', ?) ON CONFLICT(key) DO UPDATE SET value = excluded.value"
+            "INSERT INTO settings (key, value) VALUES (?, ?) ON CONFLICT(key) DO UPDATE SET value = excluded.value"
         )
-        .bind(api_key)
+        .bind(key)
+        .bind(value)
         .execute(&self.db)
         .await?;
 
         Ok(())
     }
+
+    /// Gets the UI state saved when the app was last used, or the defaults.
+    pub async fn get_ui_state(&self) -> Result<UiState, DataError> {
+        match self.get_setting("ui_state").await? {
+            Some(state) => Ok(serde_json::from_str(&state)?),
+            None => Ok(UiState::default()),
+        }
+    }
+
+    /// Saves the UI state to restore on the next launch.
+    pub async fn set_ui_state(&self, state: &UiState) -> Result<(), DataError> {
+        self.set_setting("ui_state", &serde_json::to_string(state)?).await
+    }
+
+    /// Gets the user's saved filter presets in display order.
+    pub async fn get_filter_presets(&self) -> Result<Vec<FilterPreset>, DataError> {
+        match self.get_setting("filter_presets").await? {
+            Some(presets) => Ok(serde_json::from_str(&presets)?),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Replaces the user's saved filter presets.
+    pub async fn set_filter_presets(&self, presets: &[FilterPreset]) -> Result<(), DataError> {
+        self.set_setting("filter_presets", &serde_json::to_string(presets)?).await
+    }
+
+    /// Gets the user's keyboard shortcuts, with defaults for any they haven't changed.
+    pub async fn get_key_bindings(&self) -> Result<KeyBindings, DataError> {
+        match self.get_setting("key_bindings").await? {
+            Some(bindings) => Ok(serde_json::from_str(&bindings)?),
+            None => Ok(KeyBindings::default()),
+        }
+    }
+
+    /// Saves the user's keyboard shortcuts.
+    pub async fn set_key_bindings(&self, bindings: &KeyBindings) -> Result<(), DataError> {
+        self.set_setting("key_bindings", &serde_json::to_string(bindings)?).await
+    }
+
+    /// Gets the API key from the database.
+    pub async fn get_api_key(&self) -> Result<Option<String>, DataError> {
+        self.get_setting("api_key").await
+    }
+
+    /// Sets the API key in the database.
+    pub async fn set_api_key(&self, api_key: &str) -> Result<(), DataError> {
+        self.set_setting("api_key", api_key).await
+    }
+
+    /// Gets the hosts whose links count as puzzle links, defaulting to the built-in list.
+    pub async fn get_puzzle_hosts(&self) -> Result<Vec<String>, DataError> {
+        Ok(match self.get_setting("puzzle_hosts").await? {
+            Some(hosts) => hosts.lines().map(str::to_string).collect(),
+            None => DEFAULT_PUZZLE_HOSTS.iter().map(|host| host.to_string()).collect(),
+        })
+    }
+
+    /// Sets the hosts whose links count as puzzle links.
+    pub async fn set_puzzle_hosts(&self, hosts: &[String]) -> Result<(), DataError> {
+        self.set_setting("puzzle_hosts", &hosts.join("\n")).await
+    }
+
+    /// Gets the configured video source, defaulting to the YouTube Data API.
+    pub async fn get_video_source(&self) -> Result<SourceKind, DataError> {
+        Ok(self
+            .get_setting("video_source")
+            .await?
+            .map(|value| SourceKind::from_setting(&value))
+            .unwrap_or_default())
+    }
+
+    /// Sets the configured video source.
+    pub async fn set_video_source(&self, source: SourceKind) -> Result<(), DataError> {
+        self.set_setting("video_source", source.as_str()).await
+    }
+}
+
+/// Sets a video's completion status and records the change in its history.
+async fn write_completion(connection: &mut SqliteConnection, video_id: &str, completed: bool) -> Result<(), DataError> {
+    sqlx::query(
+        "INSERT INTO video_completion (id, completed) VALUES (?, ?) ON CONFLICT(id) DO UPDATE SET completed = excluded.completed"
+    )
+    .bind(video_id)
+    .bind(completed)
+    .execute(&mut *connection)
+    .await?;
+
+    let event = CompletionEvent::now(completed);
+    sqlx::query("INSERT INTO completion_history (video_id, completed, changed_at) VALUES (?, ?, ?)")
+        .bind(video_id)
+        .bind(event.completed)
+        .bind(event.changed_at)
+        .execute(&mut *connection)
+        .await?;
+
+    Ok(())
+}
+
+/// Sets the user's notes for a video, removing them if empty.
+async fn write_notes(connection: &mut SqliteConnection, video_id: &str, notes: &str) -> Result<(), DataError> {
+    if notes.trim().is_empty() {
+        sqlx::query("DELETE FROM video_notes WHERE video_id = ?")
+            .bind(video_id)
+            .execute(&mut *connection)
+            .await?;
+    } else {
+        sqlx::query(
+            "INSERT INTO video_notes (video_id, notes) VALUES (?, ?) ON CONFLICT(video_id) DO UPDATE SET notes = excluded.notes"
+        )
+        .bind(video_id)
+        .bind(notes)
+        .execute(&mut *connection)
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Replaces a video's tags.
+async fn write_tags(connection: &mut SqliteConnection, video_id: &str, tags: &[String]) -> Result<(), DataError> {
+    sqlx::query("DELETE FROM video_tags WHERE video_id = ?")
+        .bind(video_id)
+        .execute(&mut *connection)
+        .await?;
+
+    for tag in tags {
+        sqlx::query("INSERT INTO video_tags (video_id, tag) VALUES (?, ?) ON CONFLICT DO NOTHING")
+            .bind(video_id)
+            .bind(tag)
+            .execute(&mut *connection)
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// Adds a column to an existing table if an older version of the schema doesn't have it.
+async fn add_column_if_missing(
+    pool: &sqlx::SqlitePool,
+    table: &str,
+    column: &str,
+    definition: &str,
+) -> Result<(), DataError> {
+    let columns = sqlx::query_as::<_, (String,)>(&format!("SELECT name FROM pragma_table_info('{table}')"))
+        .fetch_all(pool)
+        .await?;
+
+    if !columns.iter().any(|(name,)| name == column) {
+        info!("Adding column {column} to {table}");
+        pool.execute(format!("ALTER TABLE {table} ADD COLUMN {column} {definition};").as_str())
+            .await?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::{
+        fake_source::seed_video,
+        grouping::GroupBy,
+        solve_state::EntryMode,
+        ui_state::{FilterSettings, ViewMode},
+    };
+
+    async fn set_completed(db: &YoutubeDatabase, video_id: &str, completed: bool) {
+        let change = Change::Completion {
+            video_id: VideoId(video_id.to_string()),
+            before: !completed,
+            after: completed,
+        };
+        db.apply_changes(&[change]).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn in_memory_databases_are_isolated() {
+        let first = YoutubeDatabase::in_memory().await.unwrap();
+        let second = YoutubeDatabase::in_memory().await.unwrap();
+
+        first.set_api_key("first-key").await.unwrap();
+
+        assert_eq!(first.get_api_key().await.unwrap().as_deref(), Some("first-key"));
+        assert_eq!(second.get_api_key().await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn stores_video_data_and_completion() {
+        let db = YoutubeDatabase::in_memory().await.unwrap();
+
+        db.save_fetched_videos(&[seed_video("vid-001", "Title", "https://sudokupad.app/abc", 1_700_000_000_000, 1800)]).await.unwrap();
+        set_completed(&db, "vid-001", true).await;
+
+        let videos = db.get_all_video_data().await.unwrap();
+        assert_eq!(videos.len(), 1);
+        assert_eq!(*videos[0].duration, 1800);
+
+        let statuses = db.get_all_video_completion_statuses().await.unwrap();
+        assert_eq!(statuses.len(), 1);
+        assert!(statuses[0].completed);
+    }
+
+    #[tokio::test]
+    async fn imports_catalogue_without_overwriting_existing_videos() {
+        let db = YoutubeDatabase::in_memory().await.unwrap();
+        db.save_fetched_videos(&[seed_video("vid-001", "From the API", "", 1, 0)]).await.unwrap();
+        set_completed(&db, "vid-001", true).await;
+
+        let catalogue = [
+            SeedVideo {
+                id: "vid-001".into(),
+                title: "From the catalogue".into(),
+                description: String::new(),
+                date: 1,
+                duration: 600,
+                channel_id: CTC_CHANNEL_ID.into(),
+            },
+            SeedVideo {
+                id: "vid-002".into(),
+                title: "Older video".into(),
+                description: String::new(),
+                date: 0,
+                duration: 300,
+                channel_id: CTC_CHANNEL_ID.into(),
+            },
+        ];
+
+        assert_eq!(db.import_videos(&catalogue).await.unwrap(), 1);
+        assert_eq!(db.count_videos().await.unwrap(), 2);
+
+        let videos = db.get_all_video_data().await.unwrap();
+        let existing = videos.iter().find(|v| v.id.as_str() == "vid-001").unwrap();
+        assert_eq!(existing.title, "From the API");
+        assert_eq!(*existing.duration, 600);
+
+        let statuses = db.get_all_video_completion_statuses().await.unwrap();
+        assert!(statuses.iter().any(|s| s.id.as_str() == "vid-001" && s.completed));
+        assert!(statuses.iter().any(|s| s.id.as_str() == "vid-002" && !s.completed));
+    }
+
+    #[tokio::test]
+    async fn replaces_playlist_membership() {
+        let db = YoutubeDatabase::in_memory().await.unwrap();
+        db.add_playlist(&Playlist::new("PLseries", "Miracle Sudokus")).await.unwrap();
+
+        let ids = |ids: &[&str]| ids.iter().map(|id| VideoId::new(id)).collect::<Vec<_>>();
+        db.set_playlist_videos("PLseries", &ids(&["vid-002", "vid-001"])).await.unwrap();
+        db.set_playlist_videos("PLseries", &ids(&["vid-003", "vid-002"])).await.unwrap();
+
+        let playlist_videos = db.get_playlist_videos().await.unwrap();
+        assert_eq!(playlist_videos["PLseries"], ids(&["vid-003", "vid-002"]));
+
+        // Partial lists, such as from the feed, only add videos
+        db.add_playlist_videos("PLseries", &ids(&["vid-004", "vid-002"])).await.unwrap();
+        let playlist_videos = db.get_playlist_videos().await.unwrap();
+        assert_eq!(playlist_videos["PLseries"], ids(&["vid-003", "vid-002", "vid-004"]));
+
+        db.remove_playlist("PLseries").await.unwrap();
+        assert!(db.get_playlists().await.unwrap().is_empty());
+        assert!(db.get_playlist_videos().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn finds_puzzles_shared_between_videos() {
+        let db = YoutubeDatabase::in_memory().await.unwrap();
+        let video = |id: &str, description: &str| CtcVideo::from(CtcVideoRow {
+            id: VideoId::new(id),
+            title: String::new(),
+            description: description.to_string(),
+            date: 0,
+            duration: 0,
+            channel_id: CTC_CHANNEL_ID.to_string(),
+        });
+        let videos = [
+            video("vid-001", "https://sudokupad.app/abc123"),
+            video("vid-002", "https://app.crackingthecryptic.com/sudoku/abc123"),
+            video("vid-003", "https://sudokupad.app/other"),
+        ];
+
+        db.set_video_puzzles(&videos).await.unwrap();
+
+        let shared = db.get_shared_puzzles().await.unwrap();
+        assert_eq!(shared.len(), 1);
+        assert_eq!(
+            shared[&PuzzleId("sudokupad:abc123".to_string())],
+            [VideoId::new("vid-001"), VideoId::new("vid-002")]
+        );
+    }
+
+    #[tokio::test]
+    async fn stores_puzzle_descriptions() {
+        let db = YoutubeDatabase::in_memory().await.unwrap();
+        let puzzle_id = PuzzleId("fpuzzles:0123456789abcdef".to_string());
+        let description = PuzzleDescription {
+            size: 9,
+            author: Some("Phistomefel".to_string()),
+            constraints: vec!["killer cage".to_string()],
+            ..PuzzleDescription::default()
+        };
+
+        db.set_puzzle_description(&puzzle_id, &description).await.unwrap();
+
+        assert_eq!(db.get_puzzle_descriptions().await.unwrap()[&puzzle_id], description);
+    }
+
+    #[tokio::test]
+    async fn searches_titles_and_descriptions() {
+        let db = YoutubeDatabase::in_memory().await.unwrap();
+        db.save_fetched_videos(&[seed_video("vid-001", "Renban Madness", "A classic.", 1, 0)]).await.unwrap();
+        db.save_fetched_videos(&[seed_video("vid-002", "Killer Sudoku", "Purple lines are renban lines.", 2, 0)]).await.unwrap();
+        db.save_fetched_videos(&[seed_video("vid-003", "Whispers", "Features Dutch whispers.", 3, 0)]).await.unwrap();
+
+        let hits = db.search("renban", 10).await.unwrap();
+        let ids = hits.iter().map(|hit| hit.video_id.0.as_str()).collect::<Vec<_>>();
+        assert_eq!(ids, ["vid-001", "vid-002"]);
+        assert!(hits[1].highlights().contains(&("renban", true)));
+
+        // Updated descriptions are reindexed
+        db.save_fetched_videos(&[seed_video("vid-003", "Whispers", "German whispers only.", 3, 0)]).await.unwrap();
+        assert!(db.search("\"Dutch whispers\"", 10).await.unwrap().is_empty());
+        assert_eq!(db.search("germ", 10).await.unwrap().len(), 1);
+        assert!(db.search("\"(", 10).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn records_completion_history() {
+        let db = YoutubeDatabase::in_memory().await.unwrap();
+
+        // Fetching a video isn't a change the user made
+        db.save_fetched_videos(&[seed_video("abc", "Title", "", 1, 0)]).await.unwrap();
+        assert!(db.get_completion_history("abc").await.unwrap().is_empty());
+
+        set_completed(&db, "abc", true).await;
+        set_completed(&db, "abc", false).await;
+        set_completed(&db, "def", true).await;
+
+        let history = db.get_completion_history("abc").await.unwrap();
+        assert_eq!(history.iter().map(|event| event.completed).collect::<Vec<_>>(), [true, false]);
+        assert!(history[0].changed_at <= history[1].changed_at);
+    }
+
+    #[tokio::test]
+    async fn stores_notes_and_tags() {
+        let db = YoutubeDatabase::in_memory().await.unwrap();
+        let video_id = VideoId("abc".to_string());
+
+        let edit = |notes: &str, tags: &[&str]| {
+            vec![
+                Change::Notes {
+                    video_id: video_id.clone(),
+                    before: String::new(),
+                    after: notes.to_string(),
+                },
+                Change::Tags {
+                    video_id: video_id.clone(),
+                    before: Vec::new(),
+                    after: tags.iter().map(|tag| tag.to_string()).collect(),
+                },
+            ]
+        };
+
+        db.apply_changes(&edit("Lovely break-in", &["weekend", "killer"])).await.unwrap();
+        assert_eq!(db.get_all_video_notes().await.unwrap()[&video_id], "Lovely break-in");
+        assert_eq!(db.get_all_video_tags().await.unwrap()[&video_id], ["killer", "weekend"]);
+
+        db.apply_changes(&edit(" ", &[])).await.unwrap();
+        assert!(db.get_all_video_notes().await.unwrap().is_empty());
+        assert!(db.get_all_video_tags().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn applies_bulk_changes_together() {
+        let db = YoutubeDatabase::in_memory().await.unwrap();
+        let changes = ["abc", "def", "ghi"]
+            .into_iter()
+            .map(|id| Change::Completion {
+                video_id: VideoId::new(id),
+                before: false,
+                after: true,
+            })
+            .chain([Change::Tags {
+                video_id: VideoId::new("abc"),
+                before: Vec::new(),
+                after: vec!["queue".to_string()],
+            }])
+            .collect::<Vec<_>>();
+
+        db.apply_changes(&changes).await.unwrap();
+
+        let statuses = db.get_all_video_completion_statuses().await.unwrap();
+        assert_eq!(statuses.iter().filter(|status| status.completed).count(), 3);
+        assert_eq!(db.get_completion_history("def").await.unwrap().len(), 1);
+        assert_eq!(db.get_all_video_tags().await.unwrap()[&VideoId::new("abc")], ["queue"]);
+    }
+
+    #[tokio::test]
+    async fn saves_ui_state_and_filter_presets() {
+        let db = YoutubeDatabase::in_memory().await.unwrap();
+        assert_eq!(db.get_ui_state().await.unwrap(), UiState::default());
+        assert!(db.get_filter_presets().await.unwrap().is_empty());
+
+        let filter = FilterSettings {
+            show_completed_videos: true,
+            filter_text: "killer".to_string(),
+            channel_filter: Some(CTC_CHANNEL_ID.to_string()),
+            ..FilterSettings::default()
+        };
+        let state = UiState {
+            filter: filter.clone(),
+            window_size: Some([800.0, 600.0]),
+            scroll_offset: 1234.5,
+            view_mode: ViewMode::Cards,
+            group_by: GroupBy::Setter,
+        };
+        let presets = [FilterPreset {
+            name: "Killers".to_string(),
+            filter,
+        }];
+        db.set_ui_state(&state).await.unwrap();
+        db.set_filter_presets(&presets).await.unwrap();
+
+        assert_eq!(db.get_ui_state().await.unwrap(), state);
+        assert_eq!(db.get_filter_presets().await.unwrap(), presets);
+    }
+
+    #[tokio::test]
+    async fn saves_solve_states_per_video() {
+        let db = YoutubeDatabase::in_memory().await.unwrap();
+        let puzzle = PuzzleDescription {
+            size: 4,
+            ..PuzzleDescription::default()
+        };
+        let mut state = SolveState::new(4);
+        state.enter(&puzzle, &[(1, 2)], EntryMode::Digit, 3);
+
+        db.set_solve_state("abc", &state).await.unwrap();
+        state.enter(&puzzle, &[(1, 2)], EntryMode::Corner, 4);
+        db.set_solve_state("abc", &state).await.unwrap();
+
+        let saved = db.get_solve_state("abc").await.unwrap().unwrap();
+        assert_eq!(saved.cell((1, 2)).digit, Some(3));
+        assert_eq!(saved.cell((1, 2)).corner_marks, [4]);
+        assert!(db.get_solve_state("def").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn seeds_default_filter_rules_once() {
+        let db = YoutubeDatabase::in_memory().await.unwrap();
+        assert_eq!(db.get_filter_rules().await.unwrap(), FilterRule::defaults());
+
+        db.set_filter_rules(&[]).await.unwrap();
+        let reopened = YoutubeDatabase::from_pool(db.db.clone()).await.unwrap();
+
+        assert!(reopened.get_filter_rules().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn migrates_video_data_without_a_channel_column() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        pool.execute("CREATE TABLE video_data (id VARCHAR(10) PRIMARY KEY NOT NULL, title TEXT NOT NULL, description TEXT NOT NULL, date INTEGER NOT NULL, duration INTEGER NOT NULL);")
+            .await
+            .unwrap();
+        pool.execute("INSERT INTO video_data VALUES ('old', 'Old video', '', 0, 60);")
+            .await
+            .unwrap();
+
+        let db = YoutubeDatabase::from_pool(pool).await.unwrap();
+
+        let videos = db.get_all_video_data().await.unwrap();
+        assert_eq!(videos[0].channel_id, CTC_CHANNEL_ID);
+        assert_eq!(db.get_channels().await.unwrap()[0].id, CTC_CHANNEL_ID);
+    }
+
+    #[tokio::test]
+    async fn opens_a_database_file_at_an_explicit_path() {
+        let dir = std::env::temp_dir().join(format!("ctc-tracker-test-{}", std::process::id()));
+        let path = dir.join("nested").join("profile.db");
+
+        let db = YoutubeDatabase::open(&path).await.unwrap();
+        db.set_api_key("key").await.unwrap();
+        db.db.close().await;
+
+        let reopened = YoutubeDatabase::open(&path).await.unwrap();
+        assert_eq!(reopened.get_api_key().await.unwrap().as_deref(), Some("key"));
+        reopened.db.close().await;
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
 }
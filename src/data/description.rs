@@ -0,0 +1,192 @@
+use super::links::urls_in_line;
+
+/// Words in a chapter title marking where the solve starts.
+const SOLVE_START_WORDS: &[&str] = &[
+    "start of solve",
+    "start of the solve",
+    "solve start",
+    "solve begins",
+    "start solving",
+    "let's get cracking",
+    "solving",
+];
+
+/// A chapter marker from a video description, such as "12:40 Start of solve".
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Chapter {
+    /// Where the chapter starts, in seconds from the start of the video.
+    pub start: u64,
+    pub title: String,
+}
+impl Chapter {
+    /// Returns the start time as text such as `4:31` or `1:02:03`.
+    pub fn timestamp(&self) -> String {
+        let (hours, minutes, seconds) = (self.start / 3600, self.start / 60 % 60, self.start % 60);
+        if hours > 0 {
+            format!("{hours}:{minutes:02}:{seconds:02}")
+        } else {
+            format!("{minutes}:{seconds:02}")
+        }
+    }
+}
+
+/// A piece of a line of a video description, split out so links and timestamps can be made
+/// clickable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Segment<'a> {
+    /// Plain text.
+    Text(&'a str),
+
+    /// A URL as written, and the link it opens (with a scheme added if it had none).
+    Url { text: &'a str, href: String },
+
+    /// A timestamp such as `4:31` or `1:02:03`, and the number of seconds it refers to.
+    Timestamp(&'a str, u64),
+}
+
+/// Splits a line of a description into text, URLs and timestamps.
+pub fn segments(line: &str) -> Vec<Segment<'_>> {
+    let mut segments = Vec::new();
+    let mut position = 0;
+    for (offset, url) in urls_in_line(line) {
+        push_text(&line[position..offset], &mut segments);
+        let text = &line[offset..offset + url.len()];
+        let href = if url.contains("://") {
+            url
+        } else {
+            format!("https://{url}")
+        };
+        segments.push(Segment::Url { text, href });
+        position = offset + text.len();
+    }
+    push_text(&line[position..], &mut segments);
+    segments
+}
+
+/// Finds the chapter markers in a description: lines starting with a timestamp, optionally
+/// followed by a separator such as `-` before the title.
+pub fn parse_chapters(description: &str) -> Vec<Chapter> {
+    description
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim_start_matches(|c: char| c.is_whitespace() || "•-*(".contains(c));
+            let end = line.find(|c: char| !(c.is_ascii_digit() || c == ':')).unwrap_or(line.len());
+            let start = parse_timestamp(&line[..end])?;
+            let title = line[end..]
+                .trim_start_matches(|c: char| c.is_whitespace() || ")-–—:|".contains(c))
+                .trim_end();
+            Some(Chapter {
+                start,
+                title: title.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Returns the chapter where the solve starts, going by its title.
+pub fn solve_start(chapters: &[Chapter]) -> Option<&Chapter> {
+    chapters.iter().find(|chapter| {
+        let title = chapter.title.to_lowercase();
+        SOLVE_START_WORDS.iter().any(|word| title.contains(word))
+    })
+}
+
+/// Parses a timestamp such as `4:31` or `1:02:03` into seconds.
+pub fn parse_timestamp(text: &str) -> Option<u64> {
+    let parts = text.split(':').collect::<Vec<_>>();
+    if !(2..=3).contains(&parts.len()) {
+        return None;
+    }
+
+    let (first, rest) = parts.split_first()?;
+    let valid_first = (1..=2).contains(&first.len()) && first.chars().all(|c| c.is_ascii_digit());
+    let valid_rest = rest.iter().all(|part| part.len() == 2 && part.chars().all(|c| c.is_ascii_digit()));
+    if !valid_first || !valid_rest {
+        return None;
+    }
+
+    let mut seconds = 0;
+    for (index, part) in parts.iter().enumerate() {
+        let value = part.parse::<u64>().ok()?;
+        if index > 0 && value >= 60 {
+            return None;
+        }
+        seconds = seconds * 60 + value;
+    }
+    Some(seconds)
+}
+
+/// Adds text to the segments, splitting out any timestamps in it.
+fn push_text<'a>(text: &'a str, segments: &mut Vec<Segment<'a>>) {
+    let is_timestamp_char = |c: char| c.is_ascii_digit() || c == ':';
+    let mut position = 0;
+    let mut search_from = 0;
+    while let Some(start) = text[search_from..].find(|c: char| c.is_ascii_digit()) {
+        let start = search_from + start;
+        let end = text[start..]
+            .find(|c: char| !is_timestamp_char(c))
+            .map_or(text.len(), |end| start + end);
+        let candidate = text[start..end].trim_end_matches(':');
+        let end = start + candidate.len();
+        search_from = end.max(start + 1);
+
+        // Timestamps stand on their own rather than being part of a word
+        let before = text[..start].chars().last();
+        let after = text[end..].chars().next();
+        if before.is_some_and(char::is_alphanumeric) || after.is_some_and(char::is_alphanumeric) {
+            continue;
+        }
+        if let Some(seconds) = parse_timestamp(candidate) {
+            if position < start {
+                segments.push(Segment::Text(&text[position..start]));
+            }
+            segments.push(Segment::Timestamp(candidate, seconds));
+            position = end;
+        }
+    }
+    if position < text.len() {
+        segments.push(Segment::Text(&text[position..]));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_out_urls_and_timestamps() {
+        assert_eq!(
+            segments("4:31 The Rules (see www.example.com/rules) at 1:02:03, not 12:345 or v1:30"),
+            [
+                Segment::Timestamp("4:31", 271),
+                Segment::Text(" The Rules (see "),
+                Segment::Url {
+                    text: "www.example.com/rules",
+                    href: "https://www.example.com/rules".to_string(),
+                },
+                Segment::Text(") at "),
+                Segment::Timestamp("1:02:03", 3723),
+                Segment::Text(", not 12:345 or v1:30"),
+            ]
+        );
+        assert_eq!(parse_timestamp("7:60"), None);
+        assert_eq!(parse_timestamp("123"), None);
+    }
+
+    #[test]
+    fn parses_chapters_and_finds_the_solve_start() {
+        let chapters = parse_chapters(include_str!("fixtures/descriptions/classic.txt"));
+
+        assert_eq!(
+            chapters,
+            [
+                Chapter { start: 0, title: "Introduction".to_string() },
+                Chapter { start: 271, title: "The Rules".to_string() },
+                Chapter { start: 422, title: "Start of solve".to_string() },
+            ]
+        );
+        assert_eq!(solve_start(&chapters).map(Chapter::timestamp).as_deref(), Some("7:02"));
+        assert_eq!(parse_chapters("• 1:02:03 - Finale")[0].timestamp(), "1:02:03");
+        assert!(parse_chapters("Solved in 45 minutes").is_empty());
+    }
+}
@@ -0,0 +1,147 @@
+use serde_json::Value;
+
+/// Errors produced by the data layer (YouTube API and local database).
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum DataError {
+    /// The API key was rejected or is not authorised for the YouTube Data API.
+    #[error("Invalid API key: {0}")]
+    InvalidApiKey(String),
+
+    /// The API key has exhausted its quota or is being rate limited.
+    #[error("YouTube API quota exceeded: {0}")]
+    QuotaExceeded(String),
+
+    /// The server refused the request for a reason other than the API key, such as a private
+    /// or deleted playlist.
+    #[error("Access denied: {0}")]
+    Forbidden(String),
+
+    /// The request could not reach the server or the server returned an unexpected failure.
+    #[error("Network error: {0}")]
+    Network(String),
+
+    /// The server response could not be understood.
+    #[error("Failed to parse response: {0}")]
+    Parse(String),
+
+    /// A local file couldn't be read or written.
+    #[error("File error: {0}")]
+    Io(String),
+
+    /// A local database operation failed.
+    #[error("Database error: {0}")]
+    Database(String),
+
+    /// The requested channel, playlist or video doesn't exist.
+    #[error("Not found: {0}")]
+    NotFound(String),
+
+    /// The configured video source can't perform the requested operation.
+    #[error("Not supported: {0}")]
+    Unsupported(String),
+}
+impl DataError {
+    /// Returns whether this error should prompt the user to update their API key.
+    pub fn is_invalid_api_key(&self) -> bool {
+        matches!(self, Self::InvalidApiKey(_))
+    }
+
+    /// Classifies a Google API error payload (the JSON body of a failed request).
+    ///
+    /// The payload is expected to look like
+    /// `{"error": {"code": 403, "message": "...", "errors": [{"reason": "quotaExceeded"}]}}`.
+    pub fn from_api_payload(payload: &Value) -> Self {
+        let error = payload.get("error").unwrap_or(payload);
+        let code = error.get("code").and_then(Value::as_i64).unwrap_or_default();
+        let message = error
+            .get("message")
+            .and_then(Value::as_str)
+            .unwrap_or("Unknown API error")
+            .to_string();
+
+        // Reasons can appear in the legacy `errors` list or in the newer `details` list.
+        let reasons = ["errors", "details"]
+            .iter()
+            .filter_map(|key| error.get(key).and_then(Value::as_array))
+            .flatten()
+            .filter_map(|entry| entry.get("reason").and_then(Value::as_str))
+            .collect::<Vec<_>>();
+
+        let has_reason = |candidates: &[&str]| {
+            reasons
+                .iter()
+                .any(|reason| candidates.iter().any(|c| reason.eq_ignore_ascii_case(c)))
+        };
+
+        if has_reason(&["quotaExceeded", "dailyLimitExceeded", "rateLimitExceeded", "userRateLimitExceeded"]) {
+            Self::QuotaExceeded(message)
+        } else if has_reason(&[
+            "keyInvalid",
+            "keyExpired",
+            "API_KEY_INVALID",
+            "API_KEY_SERVICE_BLOCKED",
+            "accessNotConfigured",
+            "ipRefererBlocked",
+        ]) {
+            Self::InvalidApiKey(message)
+        } else if code == 401 || code == 403 {
+            // Only the reasons above blame the key; anything else forbidden is about the resource
+            Self::Forbidden(message)
+        } else if code == 429 {
+            Self::QuotaExceeded(message)
+        } else {
+            Self::Network(message)
+        }
+    }
+}
+
+impl From<google_youtube3::Error> for DataError {
+    fn from(error: google_youtube3::Error) -> Self {
+        use google_youtube3::Error;
+
+        match error {
+            Error::BadRequest(payload) => Self::from_api_payload(&payload),
+            Error::MissingAPIKey => Self::InvalidApiKey("No API key was provided".to_string()),
+            Error::JsonDecodeError(_, e) => Self::Parse(e.to_string()),
+            Error::Failure(response) => match response.status().as_u16() {
+                401 | 403 => Self::Forbidden(response.status().to_string()),
+                429 => Self::QuotaExceeded(response.status().to_string()),
+                _ => Self::Network(response.status().to_string()),
+            },
+            other => Self::Network(other.to_string()),
+        }
+    }
+}
+
+impl From<sqlx::Error> for DataError {
+    fn from(error: sqlx::Error) -> Self {
+        Self::Database(error.to_string())
+    }
+}
+
+impl From<serde_json::Error> for DataError {
+    fn from(error: serde_json::Error) -> Self {
+        Self::Parse(error.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::fake_source::fixtures;
+
+    fn classify(fixture: &str) -> DataError {
+        DataError::from_api_payload(&serde_json::from_str(fixture).unwrap())
+    }
+
+    #[test]
+    fn only_blames_the_key_for_key_reasons() {
+        assert!(classify(fixtures::ERROR_INVALID_KEY).is_invalid_api_key());
+        assert!(matches!(classify(fixtures::ERROR_QUOTA_EXCEEDED), DataError::QuotaExceeded(_)));
+
+        // A 403 about the resource, such as a private playlist, leaves the key alone
+        assert!(matches!(classify(fixtures::ERROR_FORBIDDEN), DataError::Forbidden(_)));
+        let bare = r#"{"error": {"code": 401, "message": "Mentions the API key"}}"#;
+        assert!(matches!(classify(bare), DataError::Forbidden(_)));
+    }
+}
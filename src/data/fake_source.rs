@@ -0,0 +1,160 @@
+//! A [`VideoSource`] that replays recorded YouTube Data API responses for offline tests.
+
+use std::{collections::HashMap, sync::Mutex};
+
+use async_trait::async_trait;
+use google_youtube3::api::{PlaylistItemListResponse, VideoListResponse};
+
+use super::{
+    error::DataError,
+    model::{Channel, ChannelRef, CtcVideo, Playlist, VideoId, CTC_CHANNEL_ID},
+    seed::SeedVideo,
+    source::{VideoPage, VideoSource},
+    youtube_api::{get_video_ids_from_playlist, page_from_responses, videos_from_response},
+};
+
+/// Recorded API responses used by the fake video source.
+pub mod fixtures {
+    pub const PLAYLIST_PAGE_1: &str = include_str!("fixtures/playlist_page_1.json");
+    pub const PLAYLIST_PAGE_2: &str = include_str!("fixtures/playlist_page_2.json");
+    pub const PLAYLIST_PAGE_3: &str = include_str!("fixtures/playlist_page_3.json");
+    pub const VIDEOS: &str = include_str!("fixtures/videos.json");
+    pub const ERROR_QUOTA_EXCEEDED: &str = include_str!("fixtures/error_quota_exceeded.json");
+    pub const ERROR_INVALID_KEY: &str = include_str!("fixtures/error_invalid_key.json");
+    pub const ERROR_FORBIDDEN: &str = include_str!("fixtures/error_forbidden.json");
+}
+
+/// Creates a catalogue entry for a Cracking the Cryptic video.
+pub fn seed_video(id: &str, title: &str, description: &str, date: i64, duration: u64) -> SeedVideo {
+    SeedVideo {
+        id: id.to_string(),
+        title: title.to_string(),
+        description: description.to_string(),
+        date,
+        duration,
+        channel_id: CTC_CHANNEL_ID.to_string(),
+    }
+}
+
+/// Response recorded for a page token.
+enum FakeResponse {
+    Page(&'static str),
+    Error(&'static str),
+}
+
+/// Fake video source serving playlist pages from JSON fixtures.
+pub struct FakeVideoSource {
+    videos: &'static str,
+    pages: HashMap<Option<String>, FakeResponse>,
+    channels: Vec<Channel>,
+    playlists: Vec<Playlist>,
+    requested_pages: Mutex<Vec<Option<String>>>,
+}
+impl FakeVideoSource {
+    /// Creates a fake source whose video details are looked up in the given `videos.list` fixture.
+    pub fn new(videos: &'static str) -> Self {
+        Self {
+            videos,
+            pages: HashMap::new(),
+            channels: Vec::new(),
+            playlists: Vec::new(),
+            requested_pages: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Serves a `playlistItems.list` fixture for the given page token.
+    pub fn with_page(mut self, page_token: Option<&str>, fixture: &'static str) -> Self {
+        self.pages
+            .insert(page_token.map(String::from), FakeResponse::Page(fixture));
+        self
+    }
+
+    /// Serves an API error payload for the given page token.
+    pub fn with_error(mut self, page_token: Option<&str>, fixture: &'static str) -> Self {
+        self.pages
+            .insert(page_token.map(String::from), FakeResponse::Error(fixture));
+        self
+    }
+
+    /// Makes a channel resolvable by its ID or by a handle matching its display name.
+    pub fn with_channel(mut self, channel: Channel) -> Self {
+        self.channels.push(channel);
+        self
+    }
+
+    /// Makes a playlist resolvable by its ID.
+    pub fn with_playlist(mut self, playlist: Playlist) -> Self {
+        self.playlists.push(playlist);
+        self
+    }
+
+    /// Emulates `videos.list` by returning the catalogue entries for the given IDs.
+    fn catalogue_videos(&self, video_ids: &[VideoId]) -> Result<Vec<CtcVideo>, DataError> {
+        let mut catalogue: VideoListResponse = serde_json::from_str(self.videos)?;
+        catalogue.items = catalogue.items.map(|items| {
+            items
+                .into_iter()
+                .filter(|video| {
+                    video
+                        .id
+                        .as_ref()
+                        .is_some_and(|id| video_ids.iter().any(|video_id| **video_id == *id))
+                })
+                .collect()
+        });
+        Ok(videos_from_response(catalogue))
+    }
+
+    /// Returns the page tokens requested so far, in order.
+    pub fn requested_pages(&self) -> Vec<Option<String>> {
+        self.requested_pages.lock().unwrap().clone()
+    }
+}
+#[async_trait]
+impl VideoSource for FakeVideoSource {
+    async fn get_playlist_page(
+        &self,
+        _playlist_id: &str,
+        page_token: Option<String>,
+    ) -> Result<VideoPage, DataError> {
+        self.requested_pages.lock().unwrap().push(page_token.clone());
+
+        let playlist_items: PlaylistItemListResponse = match self.pages.get(&page_token) {
+            Some(FakeResponse::Page(fixture)) => serde_json::from_str(fixture)?,
+            Some(FakeResponse::Error(fixture)) => {
+                return Err(DataError::from_api_payload(&serde_json::from_str(fixture)?));
+            }
+            None => {
+                return Err(DataError::Network(format!(
+                    "No fixture recorded for page {page_token:?}"
+                )));
+            }
+        };
+
+        let videos = self.catalogue_videos(&get_video_ids_from_playlist(&playlist_items))?;
+        Ok(page_from_responses(&playlist_items, videos))
+    }
+
+    async fn get_videos(&self, video_ids: &[VideoId]) -> Result<Vec<CtcVideo>, DataError> {
+        self.catalogue_videos(video_ids)
+    }
+
+    async fn resolve_channel(&self, channel: &ChannelRef) -> Result<Channel, DataError> {
+        self.channels
+            .iter()
+            .find(|candidate| match channel {
+                ChannelRef::Id(id) => candidate.id == *id,
+                ChannelRef::Handle(handle) => candidate.display_name == *handle,
+            })
+            .cloned()
+            .ok_or_else(|| DataError::NotFound(format!("No channel found for {channel:?}")))
+    }
+
+    async fn resolve_playlist(&self, playlist_id: &str) -> Result<Playlist, DataError> {
+        self.playlists
+            .iter()
+            .find(|playlist| playlist.id == playlist_id)
+            .cloned()
+            .ok_or_else(|| DataError::NotFound(format!("No playlist found for {playlist_id}")))
+    }
+}
@@ -0,0 +1,263 @@
+use async_trait::async_trait;
+use log::debug;
+use quick_xml::{events::Event, Reader};
+
+use super::{
+    description::parse_chapters,
+    error::DataError,
+    http::{download, new_http_client, HttpClient},
+    model::{
+        extract_links_from_description, Channel, ChannelRef, CtcVideo, Playlist, VideoDuration,
+        VideoId, VideoPublishDate,
+    },
+    source::{VideoPage, VideoSource},
+    youtube_api::get_upload_playlist,
+};
+
+/// Video source backed by YouTube's public Atom feeds, which need no API key.
+///
+/// Feeds only list the latest ~15 uploads and don't include durations, so videos from this
+/// source have an unknown duration until they are refreshed through the Data API.
+#[derive(Clone)]
+pub struct FeedClient {
+    client: HttpClient,
+}
+impl FeedClient {
+    /// Creates a new instance of `FeedClient`.
+    pub fn new() -> Self {
+        Self {
+            client: new_http_client(),
+        }
+    }
+
+    /// Downloads the raw Atom feed for a playlist.
+    async fn get_feed(&self, playlist_id: &str) -> Result<String, DataError> {
+        let url = feed_url(playlist_id);
+        debug!("Fetching feed {url}");
+
+        let body = download(&self.client, &url).await?;
+        String::from_utf8(body.to_vec()).map_err(|e| DataError::Parse(e.to_string()))
+    }
+}
+#[async_trait]
+impl VideoSource for FeedClient {
+    async fn get_playlist_page(
+        &self,
+        playlist_id: &str,
+        _page_token: Option<String>,
+    ) -> Result<VideoPage, DataError> {
+        let feed = self.get_feed(playlist_id).await?;
+        let videos = parse_feed(&feed)?;
+
+        // Feeds aren't paginated; everything available is on the first page.
+        Ok(VideoPage {
+            video_ids: videos.iter().map(|video| video.id.clone()).collect(),
+            videos,
+            next_page_token: None,
+        })
+    }
+
+    fn lists_whole_playlists(&self) -> bool {
+        false
+    }
+
+    async fn resolve_channel(&self, channel: &ChannelRef) -> Result<Channel, DataError> {
+        let ChannelRef::Id(channel_id) = channel else {
+            return Err(DataError::Unsupported(
+                "The public feed can only add channels by ID or /channel/ URL".to_string(),
+            ));
+        };
+
+        let feed = self.get_feed(&get_upload_playlist(channel_id)).await?;
+        let display_name = parse_feed_author(&feed)?.unwrap_or_else(|| channel_id.clone());
+        Ok(Channel::new(channel_id, &display_name))
+    }
+
+    async fn resolve_playlist(&self, playlist_id: &str) -> Result<Playlist, DataError> {
+        let feed = self.get_feed(playlist_id).await?;
+        let title = parse_feed_title(&feed)?.unwrap_or_else(|| playlist_id.to_string());
+        Ok(Playlist::new(playlist_id, &title))
+    }
+}
+
+/// Builds the public feed URL for a playlist.
+fn feed_url(playlist_id: &str) -> String {
+    format!("https://www.youtube.com/feeds/videos.xml?playlist_id={playlist_id}")
+}
+
+/// Parses a YouTube Atom feed into videos with unknown durations.
+pub fn parse_feed(xml: &str) -> Result<Vec<CtcVideo>, DataError> {
+    let mut reader = Reader::from_str(xml);
+    reader.config_mut().trim_text(true);
+
+    let mut videos = Vec::new();
+    let mut entry: Option<FeedEntry> = None;
+    let mut current_tag = Vec::new();
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(tag)) => {
+                if tag.name().as_ref() == b"entry" {
+                    entry = Some(FeedEntry::default());
+                }
+                current_tag = tag.name().as_ref().to_vec();
+            }
+            Ok(Event::Text(text)) => {
+                if let Some(entry) = entry.as_mut() {
+                    let text = text.unescape().map_err(|e| DataError::Parse(e.to_string()))?;
+                    entry.set(&current_tag, &text);
+                }
+            }
+            Ok(Event::End(tag)) => {
+                if tag.name().as_ref() == b"entry" {
+                    if let Some(video) = entry.take().and_then(FeedEntry::into_video) {
+                        videos.push(video);
+                    }
+                }
+                current_tag.clear();
+            }
+            Ok(Event::Eof) => break,
+            Ok(_) => {}
+            Err(e) => return Err(DataError::Parse(e.to_string())),
+        }
+    }
+
+    Ok(videos)
+}
+
+/// Parses the feed-level `<title>` element (the playlist title).
+pub fn parse_feed_title(xml: &str) -> Result<Option<String>, DataError> {
+    let mut reader = Reader::from_str(xml);
+    reader.config_mut().trim_text(true);
+
+    let mut in_title = false;
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(tag)) => match tag.name().as_ref() {
+                b"entry" => return Ok(None),
+                b"title" => in_title = true,
+                _ => {}
+            },
+            Ok(Event::Text(text)) if in_title => {
+                let title = text.unescape().map_err(|e| DataError::Parse(e.to_string()))?;
+                return Ok(Some(title.into_owned()));
+            }
+            Ok(Event::Eof) => return Ok(None),
+            Ok(_) => {}
+            Err(e) => return Err(DataError::Parse(e.to_string())),
+        }
+    }
+}
+
+/// Parses the channel name from the feed-level `<author>` element.
+pub fn parse_feed_author(xml: &str) -> Result<Option<String>, DataError> {
+    let mut reader = Reader::from_str(xml);
+    reader.config_mut().trim_text(true);
+
+    let mut in_author = false;
+    let mut in_name = false;
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(tag)) => match tag.name().as_ref() {
+                b"entry" => return Ok(None),
+                b"author" => in_author = true,
+                b"name" => in_name = in_author,
+                _ => {}
+            },
+            Ok(Event::Text(text)) if in_name => {
+                let name = text.unescape().map_err(|e| DataError::Parse(e.to_string()))?;
+                return Ok(Some(name.into_owned()));
+            }
+            Ok(Event::End(tag)) if tag.name().as_ref() == b"author" => in_author = false,
+            Ok(Event::Eof) => return Ok(None),
+            Ok(_) => {}
+            Err(e) => return Err(DataError::Parse(e.to_string())),
+        }
+    }
+}
+
+/// Fields collected from a single `<entry>` element.
+#[derive(Default)]
+struct FeedEntry {
+    video_id: String,
+    channel_id: String,
+    title: String,
+    description: String,
+    published: String,
+}
+impl FeedEntry {
+    fn set(&mut self, tag: &[u8], text: &str) {
+        match tag {
+            b"yt:videoId" => self.video_id = text.to_string(),
+            b"yt:channelId" => self.channel_id = text.to_string(),
+            b"title" => self.title = text.to_string(),
+            b"media:description" => self.description = text.to_string(),
+            b"published" => self.published = text.to_string(),
+            _ => {}
+        }
+    }
+
+    fn into_video(self) -> Option<CtcVideo> {
+        if self.video_id.is_empty() {
+            return None;
+        }
+        let date = chrono::DateTime::parse_from_rfc3339(&self.published)
+            .map(|date| date.timestamp_millis())
+            .unwrap_or_default();
+        let extracted_links = extract_links_from_description(&self.description);
+        let chapters = parse_chapters(&self.description);
+        Some(CtcVideo {
+            id: VideoId::new(&self.video_id),
+            title: self.title,
+            description: self.description,
+            date: VideoPublishDate::new(date),
+            duration: VideoDuration::unknown(),
+            extracted_links,
+            chapters,
+            channel_id: self.channel_id,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FEED: &str = include_str!("fixtures/feed.xml");
+
+    #[test]
+    fn parses_feed_entries() {
+        let videos = parse_feed(FEED).unwrap();
+
+        assert_eq!(videos.len(), 2);
+        assert_eq!(videos[0].id.as_str(), "vid-101");
+        assert_eq!(videos[0].title, "The Sudoku That Broke Simon & Mark");
+        assert_eq!(videos[0].extracted_links[0].url, "https://sudokupad.app/feed-puzzle");
+        assert_eq!(videos[0].date.to_string(), "2024-03-11");
+        assert!(videos[0].duration.is_unknown());
+        assert_eq!(videos[0].channel_id, "UCC-UOdK8-mIjxBQm_ot1T-Q");
+    }
+
+    #[test]
+    fn parses_feed_title() {
+        assert_eq!(
+            parse_feed_title(FEED).unwrap().as_deref(),
+            Some("Uploads from Cracking The Cryptic")
+        );
+    }
+
+    #[test]
+    fn parses_feed_author() {
+        assert_eq!(parse_feed_author(FEED).unwrap().as_deref(), Some("Cracking The Cryptic"));
+    }
+
+    #[test]
+    fn rejects_malformed_feeds() {
+        assert!(matches!(
+            parse_feed("<feed><entry><title>Broken</entry></feed>"),
+            Err(DataError::Parse(_))
+        ));
+    }
+}
@@ -0,0 +1,117 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// What the videos in the grid are grouped by.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum GroupBy {
+    #[default]
+    None,
+    Year,
+    Month,
+    Setter,
+
+    /// The constraint types of the video's puzzles, such as "killer cage".
+    Genre,
+    Playlist,
+}
+impl GroupBy {
+    /// Every way of grouping, in the order they're listed in the menu.
+    pub const ALL: [Self; 6] = [
+        Self::None,
+        Self::Year,
+        Self::Month,
+        Self::Setter,
+        Self::Genre,
+        Self::Playlist,
+    ];
+
+    /// Returns the name shown in the group-by menu.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::None => "No grouping",
+            Self::Year => "Year",
+            Self::Month => "Month",
+            Self::Setter => "Setter",
+            Self::Genre => "Genre",
+            Self::Playlist => "Playlist",
+        }
+    }
+
+    /// Returns the heading of the group of items that have nothing to group them by.
+    fn ungrouped_label(self) -> &'static str {
+        match self {
+            Self::None => "All videos",
+            Self::Year | Self::Month => "Unknown date",
+            Self::Setter => "Unknown setter",
+            Self::Genre => "Unknown genre",
+            Self::Playlist => "Not in a playlist",
+        }
+    }
+
+    /// Puts each item in the group of every name `keys` returns for it. Dates are listed newest
+    /// first and other names alphabetically, followed by the items `keys` returned no names for.
+    pub fn group<T: Copy>(self, items: &[T], keys: impl Fn(&T) -> Vec<String>) -> Vec<Group<T>> {
+        let mut groups: BTreeMap<String, Vec<T>> = BTreeMap::new();
+        let mut ungrouped = Vec::new();
+        for item in items {
+            let mut names = keys(item);
+            if names.is_empty() {
+                ungrouped.push(*item);
+            }
+            names.sort();
+            names.dedup();
+            for name in names {
+                groups.entry(name).or_default().push(*item);
+            }
+        }
+
+        let mut groups = groups
+            .into_iter()
+            .map(|(name, items)| Group { name, items })
+            .collect::<Vec<_>>();
+        if matches!(self, Self::Year | Self::Month) {
+            groups.reverse();
+        }
+        if !ungrouped.is_empty() {
+            groups.push(Group {
+                name: self.ungrouped_label().to_string(),
+                items: ungrouped,
+            });
+        }
+        groups
+    }
+}
+
+/// Items sharing a year, month, setter, genre or playlist.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Group<T> {
+    pub name: String,
+    pub items: Vec<T>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn groups_items_under_each_of_their_names() {
+        let genres = |item: &u32| match item {
+            1 => vec!["thermometer".to_string(), "killer cage".to_string()],
+            2 => vec!["killer cage".to_string(), "killer cage".to_string()],
+            _ => Vec::new(),
+        };
+        let groups = GroupBy::Genre.group(&[1, 2, 3], genres);
+        let names = groups.iter().map(|group| group.name.as_str()).collect::<Vec<_>>();
+        assert_eq!(names, ["killer cage", "thermometer", "Unknown genre"]);
+        assert_eq!(groups[0].items, [1, 2]);
+        assert_eq!(groups[2].items, [3]);
+
+        // Newest dates come first
+        let years = |item: &u32| vec![format!("{}", 2020 + item)];
+        let groups = GroupBy::Year.group(&[1, 3, 2, 3], years);
+        let names = groups.iter().map(|group| group.name.as_str()).collect::<Vec<_>>();
+        assert_eq!(names, ["2023", "2022", "2021"]);
+        assert_eq!(groups[0].items, [3, 3]);
+    }
+}
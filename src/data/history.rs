@@ -0,0 +1,205 @@
+use super::model::VideoId;
+
+/// Number of edits kept for undoing.
+const MAX_EDITS: usize = 100;
+
+/// A single change to the user's data about a video, with the values before and after it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change {
+    Completion {
+        video_id: VideoId,
+        before: bool,
+        after: bool,
+    },
+    Notes {
+        video_id: VideoId,
+        before: String,
+        after: String,
+    },
+    Tags {
+        video_id: VideoId,
+        before: Vec<String>,
+        after: Vec<String>,
+    },
+}
+impl Change {
+    /// Returns the video the change is about.
+    pub fn video_id(&self) -> &VideoId {
+        match self {
+            Change::Completion { video_id, .. } | Change::Notes { video_id, .. } | Change::Tags { video_id, .. } => {
+                video_id
+            }
+        }
+    }
+
+    /// Returns whether the change leaves the value as it was.
+    pub fn is_noop(&self) -> bool {
+        match self {
+            Change::Completion { before, after, .. } => before == after,
+            Change::Notes { before, after, .. } => before == after,
+            Change::Tags { before, after, .. } => before == after,
+        }
+    }
+
+    /// Returns the change that puts the value back.
+    fn inverse(&self) -> Self {
+        match self.clone() {
+            Change::Completion { video_id, before, after } => Change::Completion {
+                video_id,
+                before: after,
+                after: before,
+            },
+            Change::Notes { video_id, before, after } => Change::Notes {
+                video_id,
+                before: after,
+                after: before,
+            },
+            Change::Tags { video_id, before, after } => Change::Tags {
+                video_id,
+                before: after,
+                after: before,
+            },
+        }
+    }
+}
+
+/// One user action, such as ticking a checkbox or a bulk action, undone as a whole.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Edit {
+    pub changes: Vec<Change>,
+}
+impl Edit {
+    /// Creates an edit from the changes that actually change something.
+    pub fn new(changes: Vec<Change>) -> Self {
+        Self {
+            changes: changes.into_iter().filter(|change| !change.is_noop()).collect(),
+        }
+    }
+
+    /// Returns whether the edit changes nothing.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Returns the edit that undoes this one, reverting the changes in reverse order.
+    pub fn inverse(&self) -> Self {
+        Self {
+            changes: self.changes.iter().rev().map(Change::inverse).collect(),
+        }
+    }
+
+    /// Describes the edit, such as "Marked The Miracle Sudoku complete", naming videos with `title`.
+    pub fn describe(&self, title: impl Fn(&VideoId) -> String) -> String {
+        let completion = |completed: bool| if completed { "complete" } else { "not complete" };
+        let all = |check: fn(&Change) -> bool| self.changes.iter().all(check);
+        match self.changes.as_slice() {
+            [Change::Completion { video_id, after, .. }] => {
+                format!("Marked {} {}", title(video_id), completion(*after))
+            }
+            [Change::Notes { video_id, .. }] => format!("Edited notes for {}", title(video_id)),
+            [Change::Tags { video_id, .. }] => format!("Edited tags for {}", title(video_id)),
+            changes if all(|change| matches!(change, Change::Completion { after: true, .. })) => {
+                format!("Marked {} videos {}", changes.len(), completion(true))
+            }
+            changes if all(|change| matches!(change, Change::Completion { after: false, .. })) => {
+                format!("Marked {} videos {}", changes.len(), completion(false))
+            }
+            changes if all(|change| matches!(change, Change::Tags { .. })) => {
+                format!("Edited tags for {} videos", changes.len())
+            }
+            changes => format!("Made {} changes", changes.len()),
+        }
+    }
+}
+
+/// Edits that can be undone and redone, most recent last.
+#[derive(Debug, Default)]
+pub struct EditHistory {
+    undo_stack: Vec<Edit>,
+    redo_stack: Vec<Edit>,
+}
+impl EditHistory {
+    /// Records an edit that has just been made. Anything that was undone can no longer be redone.
+    pub fn push(&mut self, edit: Edit) {
+        self.redo_stack.clear();
+        self.undo_stack.push(edit);
+        if self.undo_stack.len() > MAX_EDITS {
+            self.undo_stack.remove(0);
+        }
+    }
+
+    /// Returns the edit `undo` would undo.
+    pub fn next_undo(&self) -> Option<&Edit> {
+        self.undo_stack.last()
+    }
+
+    /// Returns the edit `redo` would redo.
+    pub fn next_redo(&self) -> Option<&Edit> {
+        self.redo_stack.last()
+    }
+
+    /// Takes the most recent edit to undo, keeping it for redoing. Apply its inverse to undo it.
+    pub fn undo(&mut self) -> Option<Edit> {
+        let edit = self.undo_stack.pop()?;
+        self.redo_stack.push(edit.clone());
+        Some(edit)
+    }
+
+    /// Takes the most recently undone edit to make again.
+    pub fn redo(&mut self) -> Option<Edit> {
+        let edit = self.redo_stack.pop()?;
+        self.undo_stack.push(edit.clone());
+        Some(edit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn completion(id: &str, after: bool) -> Change {
+        Change::Completion {
+            video_id: VideoId::new(id),
+            before: !after,
+            after,
+        }
+    }
+
+    #[test]
+    fn undoes_and_redoes_edits_in_order() {
+        let mut history = EditHistory::default();
+        history.push(Edit::new(vec![completion("a", true)]));
+        history.push(Edit::new(vec![completion("b", true), completion("c", true)]));
+
+        let undone = history.undo().unwrap();
+        assert_eq!(undone.inverse().changes, [completion("c", false), completion("b", false)]);
+        assert_eq!(history.next_redo(), Some(&undone));
+        assert_eq!(history.redo(), Some(undone));
+        assert!(history.next_redo().is_none());
+
+        // A new edit after undoing drops the redo stack
+        history.undo();
+        history.push(Edit::new(vec![completion("d", false)]));
+        assert!(history.redo().is_none());
+        assert_eq!(history.undo().unwrap().changes, [completion("d", false)]);
+        assert_eq!(history.undo().unwrap().changes, [completion("a", true)]);
+        assert!(history.undo().is_none());
+    }
+
+    #[test]
+    fn describes_edits_and_drops_noops() {
+        let title = |id: &VideoId| format!("Video {}", id.0);
+        let notes = Change::Notes {
+            video_id: VideoId::new("a"),
+            before: "same".to_string(),
+            after: "same".to_string(),
+        };
+
+        assert!(Edit::new(vec![notes]).is_empty());
+        assert_eq!(Edit::new(vec![completion("a", true)]).describe(title), "Marked Video a complete");
+        assert_eq!(
+            Edit::new(vec![completion("a", false), completion("b", false)]).describe(title),
+            "Marked 2 videos not complete"
+        );
+    }
+}
@@ -0,0 +1,47 @@
+use google_youtube3::{
+    hyper::{body::Bytes, Request},
+    hyper_rustls::{self, HttpsConnector},
+    hyper_util::{self, client::legacy::connect::HttpConnector},
+};
+use http_body_util::{BodyExt, Empty};
+
+use super::error::DataError;
+
+/// HTTPS client for plain GET requests outside the YouTube Data API.
+pub type HttpClient = hyper_util::client::legacy::Client<HttpsConnector<HttpConnector>, Empty<Bytes>>;
+
+/// Creates an HTTPS client using the platform's root certificates.
+pub fn new_http_client() -> HttpClient {
+    hyper_util::client::legacy::Client::builder(hyper_util::rt::TokioExecutor::new()).build(
+        hyper_rustls::HttpsConnectorBuilder::new()
+            .with_native_roots()
+            .unwrap()
+            .https_or_http()
+            .enable_http1()
+            .build(),
+    )
+}
+
+/// Downloads the body of a URL, failing on non-success statuses.
+pub async fn download(client: &HttpClient, url: &str) -> Result<Bytes, DataError> {
+    let request = Request::get(url)
+        .body(Empty::new())
+        .map_err(|e| DataError::Network(e.to_string()))?;
+    let response = client
+        .request(request)
+        .await
+        .map_err(|e| DataError::Network(e.to_string()))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        return Err(DataError::Network(format!("Request for {url} failed: {status}")));
+    }
+
+    let body = response
+        .into_body()
+        .collect()
+        .await
+        .map_err(|e| DataError::Network(e.to_string()))?
+        .to_bytes();
+    Ok(body)
+}
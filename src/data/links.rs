@@ -0,0 +1,567 @@
+use super::puzzle_id::PuzzleId;
+
+/// Hosts whose links are treated as puzzle links unless the user configures their own list.
+///
+/// An entry may include a path prefix (e.g. `theguardian.com/crosswords`) to match only part
+/// of a site. Subdomains of a host match too, so `crackingthecryptic.com` also covers
+/// `app.crackingthecryptic.com`.
+pub const DEFAULT_PUZZLE_HOSTS: &[&str] = &[
+    "sudokupad.app",
+    "crackingthecryptic.com",
+    "cracking-the-cryptic.web.app",
+    "f-puzzles.com",
+    "logic-masters.de",
+    "puzz.link",
+    "pzv.jp",
+    "theguardian.com/crosswords",
+    "puzzles.telegraph.co.uk",
+];
+
+/// Rules classifying links by `host` or `host/path-prefix`, checked in order before the puzzle
+/// hosts so that e.g. the shop on a puzzle site isn't treated as a puzzle.
+pub const LINK_RULES: &[(&str, LinkKind)] = &[
+    ("apps.apple.com", LinkKind::AppStore),
+    ("itunes.apple.com", LinkKind::AppStore),
+    ("play.google.com/store", LinkKind::AppStore),
+    ("store.steampowered.com", LinkKind::AppStore),
+    ("patreon.com", LinkKind::Patreon),
+    ("crackingthecryptic.com/shop", LinkKind::Merch),
+    ("crackingthecryptic.com/merch", LinkKind::Merch),
+    ("crackingthecryptic.com/collections", LinkKind::Merch),
+    ("crackingthecryptic.com/products", LinkKind::Merch),
+    ("teespring.com", LinkKind::Merch),
+    ("creator-spring.com", LinkKind::Merch),
+    ("redbubble.com", LinkKind::Merch),
+    ("amazon.com", LinkKind::Merch),
+    ("amazon.co.uk", LinkKind::Merch),
+    ("amzn.to", LinkKind::Merch),
+    ("discord.gg", LinkKind::Social),
+    ("discord.com", LinkKind::Social),
+    ("twitter.com", LinkKind::Social),
+    ("x.com", LinkKind::Social),
+    ("facebook.com", LinkKind::Social),
+    ("instagram.com", LinkKind::Social),
+    ("reddit.com", LinkKind::Social),
+    ("tiktok.com", LinkKind::Social),
+    ("bsky.app", LinkKind::Social),
+];
+
+/// Characters that can't be part of a URL in free text.
+const URL_DELIMITERS: &[char] = &['<', '>', '"', '`', '{', '}', '|', '\\', '^'];
+
+/// Characters that are usually sentence punctuation rather than part of a URL when they end one.
+const TRAILING_PUNCTUATION: &[char] = &['.', ',', ';', ':', '!', '?', '\'', '*', '…'];
+
+/// Words in a link's label suggesting it's the puzzle solved in the video.
+const PRIMARY_LABEL_WORDS: &[&str] = &["today", "this video", "solved", "play the puzzle", "puzzle here", "puzzle link"];
+
+/// Words in a link's label suggesting it's an extra puzzle rather than the one solved.
+const SECONDARY_LABEL_WORDS: &[&str] = &[
+    "bonus", "also", "sequel", "previous", "next", "another", "companion", "try", "last week", "earlier",
+];
+
+/// What a link in a video description points at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LinkKind {
+    Puzzle,
+    AppStore,
+    Patreon,
+    Merch,
+    Social,
+    Other,
+}
+impl LinkKind {
+    /// Returns the name shown for links of this kind.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Puzzle => "Puzzle",
+            Self::AppStore => "App store",
+            Self::Patreon => "Patreon",
+            Self::Merch => "Merch",
+            Self::Social => "Social",
+            Self::Other => "Other",
+        }
+    }
+}
+
+/// A link found in a video description.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DescriptionLink {
+    /// The link itself.
+    pub url: String,
+
+    /// Text introducing the link in the description (e.g. "Today's puzzle"), possibly empty.
+    pub label: String,
+
+    /// What the link points at.
+    pub kind: LinkKind,
+
+    /// Canonical identifier of the linked puzzle, for puzzle links.
+    pub puzzle_id: Option<PuzzleId>,
+}
+impl DescriptionLink {
+    /// Returns whether the link points at a puzzle.
+    pub fn is_puzzle(&self) -> bool {
+        self.kind == LinkKind::Puzzle
+    }
+
+    /// Returns the label, falling back to the URL when the description has none.
+    pub fn display_label(&self) -> &str {
+        if self.label.is_empty() {
+            &self.url
+        } else {
+            &self.label
+        }
+    }
+
+    /// Scores how likely this is the puzzle solved in the video; higher is more likely.
+    ///
+    /// Label words are matched whole, so "try" doesn't match "geometry", and a label that names
+    /// today's puzzle counts as primary even if it also has a secondary word.
+    fn primary_score(&self) -> i32 {
+        let label = self.label.to_lowercase();
+        let words = label
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .collect::<Vec<_>>();
+        let has_any = |phrases: &[&str]| {
+            phrases.iter().any(|phrase| {
+                let phrase = phrase.split(' ').collect::<Vec<_>>();
+                words.windows(phrase.len()).any(|window| window == phrase)
+            })
+        };
+        if has_any(PRIMARY_LABEL_WORDS) {
+            1
+        } else if has_any(SECONDARY_LABEL_WORDS) {
+            -1
+        } else {
+            0
+        }
+    }
+}
+
+/// Picks the puzzle link most likely to be the puzzle solved in the video.
+///
+/// Links labelled like "Today's puzzle" win over unlabelled ones, which win over links labelled
+/// as bonus or related puzzles. Ties go to the link that appears first.
+pub fn primary_link(links: &[DescriptionLink]) -> Option<&DescriptionLink> {
+    links
+        .iter()
+        .filter(|link| link.is_puzzle())
+        .enumerate()
+        .max_by_key(|(index, link)| (link.primary_score(), std::cmp::Reverse(*index)))
+        .map(|(_, link)| link)
+}
+
+/// Finds links in free text, classifying them with [`LINK_RULES`] and an allowlist of puzzle
+/// hosts.
+#[derive(Debug, Clone)]
+pub struct LinkExtractor {
+    rules: Vec<(HostPattern, LinkKind)>,
+    hosts: Vec<HostPattern>,
+}
+impl LinkExtractor {
+    /// Creates an extractor treating the given host entries (`host` or `host/path-prefix`) as
+    /// puzzle hosts.
+    pub fn new<S: AsRef<str>>(hosts: &[S]) -> Self {
+        Self {
+            rules: LINK_RULES
+                .iter()
+                .filter_map(|(entry, kind)| Some((HostPattern::parse(entry)?, *kind)))
+                .collect(),
+            hosts: hosts
+                .iter()
+                .filter_map(|entry| HostPattern::parse(entry.as_ref()))
+                .collect(),
+        }
+    }
+
+    /// Returns the links in `text`, in order of appearance and without duplicates. Puzzle links
+    /// count as duplicates when they point at the same puzzle, even through another host.
+    pub fn extract(&self, text: &str) -> Vec<DescriptionLink> {
+        let mut links: Vec<DescriptionLink> = Vec::new();
+        let mut previous_line = "";
+        for line in text.lines() {
+            for (offset, url) in urls_in_line(line) {
+                let url = if url.contains("://") {
+                    url
+                } else {
+                    format!("https://{url}")
+                };
+                let kind = self.classify(&url);
+                let puzzle_id = (kind == LinkKind::Puzzle)
+                    .then(|| PuzzleId::from_url(&url))
+                    .flatten();
+                let duplicate = links
+                    .iter()
+                    .any(|link| link.url == url || (puzzle_id.is_some() && link.puzzle_id == puzzle_id));
+                if !duplicate {
+                    let label = link_label(&line[..offset], previous_line);
+                    links.push(DescriptionLink { url, label, kind, puzzle_id });
+                }
+            }
+            if !line.trim().is_empty() {
+                previous_line = line;
+            }
+        }
+        links
+    }
+
+    /// Works out what a URL points at: the first matching rule wins, then puzzle hosts.
+    pub fn classify(&self, url: &str) -> LinkKind {
+        let (host, path) = split_url(url);
+        let path = path.to_lowercase();
+        if let Some((_, kind)) = self.rules.iter().find(|(pattern, _)| pattern.matches(&host, &path)) {
+            *kind
+        } else if self.hosts.iter().any(|pattern| pattern.matches(&host, &path)) {
+            LinkKind::Puzzle
+        } else {
+            LinkKind::Other
+        }
+    }
+}
+impl Default for LinkExtractor {
+    fn default() -> Self {
+        Self::new(DEFAULT_PUZZLE_HOSTS)
+    }
+}
+
+/// A `host` or `host/path-prefix` entry matching a site, its subdomains and optionally only
+/// part of it.
+#[derive(Debug, Clone)]
+struct HostPattern {
+    host: String,
+    path: String,
+}
+impl HostPattern {
+    /// Parses an entry, ignoring any scheme and `www.`; returns `None` for blank entries.
+    fn parse(entry: &str) -> Option<Self> {
+        let entry = entry.trim().to_lowercase();
+        let entry = strip_scheme(&entry).trim_start_matches("www.");
+        if entry.is_empty() {
+            return None;
+        }
+        Some(match entry.split_once('/') {
+            Some((host, path)) => Self {
+                host: host.to_string(),
+                path: format!("/{}", path.trim_end_matches('/')),
+            },
+            None => Self {
+                host: entry.to_string(),
+                path: String::new(),
+            },
+        })
+    }
+
+    /// Returns whether a lowercase host and path match the pattern.
+    fn matches(&self, host: &str, path: &str) -> bool {
+        let host_matches = host == self.host
+            || host
+                .strip_suffix(self.host.as_str())
+                .is_some_and(|prefix| prefix.ends_with('.'));
+        host_matches && path.starts_with(self.path.as_str())
+    }
+}
+
+/// Finds every URL-like token in `text`.
+///
+/// Tokens start at `http://`, `https://` or `www.`, or look like `host.tld/path` without a
+/// scheme. Surrounding brackets and trailing sentence punctuation are removed.
+pub fn find_urls(text: &str) -> Vec<String> {
+    text.lines()
+        .flat_map(urls_in_line)
+        .map(|(_, url)| url)
+        .collect()
+}
+
+/// Finds the URL-like tokens in a line along with the byte offset each starts at.
+pub(crate) fn urls_in_line(line: &str) -> Vec<(usize, String)> {
+    let mut urls = Vec::new();
+    let mut token_start = 0;
+    for (index, c) in line.char_indices().chain([(line.len(), ' ')]) {
+        if c.is_whitespace() || URL_DELIMITERS.contains(&c) {
+            if let Some((offset, url)) = url_in_token(&line[token_start..index]) {
+                urls.push((token_start + offset, url));
+            }
+            token_start = index + c.len_utf8();
+        }
+    }
+    urls
+}
+
+/// Builds a link's label from the text before it on its line, or from the previous line if the
+/// link stands on its own (e.g. "Today's puzzle:" followed by the link on the next line).
+fn link_label(before: &str, previous_line: &str) -> String {
+    let trim = |text: &str| {
+        // Keep only the clause closest to the link
+        let clause = text
+            .rsplit(['.', '!', '?', '|'])
+            .next()
+            .unwrap_or_default();
+        clause
+            .trim_end_matches(|c: char| c.is_whitespace() || ":-–—=>([".contains(c))
+            .trim_start()
+            .to_string()
+    };
+
+    let label = trim(before);
+    if label.is_empty() && previous_line.trim_end().ends_with(':') && find_urls(previous_line).is_empty() {
+        trim(previous_line)
+    } else {
+        label
+    }
+}
+
+/// Extracts the URL from a single whitespace-delimited token, if it contains one, along with
+/// its byte offset in the token.
+fn url_in_token(token: &str) -> Option<(usize, String)> {
+    let start = ["https://", "http://", "www."]
+        .iter()
+        .filter_map(|prefix| find_ignore_ascii_case(token, prefix))
+        .min()
+        .or_else(|| {
+            // A bare `host.tld/path`, possibly after an opening bracket
+            let start = token.find(|c: char| c.is_ascii_alphanumeric())?;
+            let host = token[start..].split('/').next()?;
+            let is_bare_url = token[start..].contains('/')
+                && host.contains('.')
+                && host
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-')
+                && host.rsplit('.').next().is_some_and(|tld| tld.len() >= 2 && tld.chars().all(|c| c.is_ascii_alphabetic()));
+            is_bare_url.then_some(start)
+        })?;
+
+    let url = trim_trailing_punctuation(&token[start..]);
+    let (host, _) = split_url(url);
+    (host.contains('.') && !host.starts_with('.')).then(|| (start, url.to_string()))
+}
+
+/// Returns the byte offset of the first match of the ASCII `needle` in `haystack`, ignoring case.
+///
+/// Lowercasing the whole haystack first would change the byte length of characters such as K
+/// (the Kelvin sign), so offsets wouldn't line up with the original text.
+fn find_ignore_ascii_case(haystack: &str, needle: &str) -> Option<usize> {
+    haystack
+        .as_bytes()
+        .windows(needle.len())
+        .position(|window| window.eq_ignore_ascii_case(needle.as_bytes()))
+}
+
+/// Removes trailing punctuation and closing brackets that don't belong to the URL.
+fn trim_trailing_punctuation(url: &str) -> &str {
+    let mut url = url;
+    loop {
+        let Some(last) = url.chars().last() else {
+            return url;
+        };
+        let unbalanced = |open: char, close: char| {
+            last == close && url.matches(close).count() > url.matches(open).count()
+        };
+        if TRAILING_PUNCTUATION.contains(&last) || unbalanced('(', ')') || unbalanced('[', ']') {
+            url = &url[..url.len() - last.len_utf8()];
+        } else {
+            return url;
+        }
+    }
+}
+
+/// Removes the scheme (e.g. `https://`) from a URL.
+fn strip_scheme(url: &str) -> &str {
+    url.split_once("://").map_or(url, |(_, rest)| rest)
+}
+
+/// Splits a URL into its lowercase host (without `www.` or a port) and the rest of it.
+pub(crate) fn split_url(url: &str) -> (String, &str) {
+    let rest = strip_scheme(url);
+    let end = rest.find(['/', '?', '#']).unwrap_or(rest.len());
+    let host = rest[..end]
+        .rsplit('@')
+        .next()
+        .unwrap_or_default()
+        .split(':')
+        .next()
+        .unwrap_or_default()
+        .to_lowercase();
+    let host = host.strip_prefix("www.").map(str::to_string).unwrap_or(host);
+    (host, &rest[end..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn extract_puzzle_links(text: &str) -> Vec<String> {
+        puzzle_links(LinkExtractor::default(), text)
+            .into_iter()
+            .map(|link| link.url)
+            .collect()
+    }
+
+    fn puzzle_links(extractor: LinkExtractor, text: &str) -> Vec<DescriptionLink> {
+        extractor
+            .extract(text)
+            .into_iter()
+            .filter(DescriptionLink::is_puzzle)
+            .collect()
+    }
+
+    fn link(url: &str, label: &str) -> DescriptionLink {
+        DescriptionLink {
+            url: url.to_string(),
+            label: label.to_string(),
+            kind: LinkKind::Puzzle,
+            puzzle_id: PuzzleId::from_url(url),
+        }
+    }
+
+    mod fixtures {
+        pub const CLASSIC: &str = include_str!("fixtures/descriptions/classic.txt");
+        pub const PARENTHESISED: &str = include_str!("fixtures/descriptions/parenthesised.txt");
+        pub const MULTIPLE_HOSTS: &str = include_str!("fixtures/descriptions/multiple_hosts.txt");
+        pub const CROSSWORD: &str = include_str!("fixtures/descriptions/crossword.txt");
+        pub const BONUS: &str = include_str!("fixtures/descriptions/bonus.txt");
+        pub const SUPPORT: &str = include_str!("fixtures/descriptions/support.txt");
+        pub const MIRRORED: &str = include_str!("fixtures/descriptions/mirrored.txt");
+    }
+
+    #[test]
+    fn extracts_the_puzzle_link_from_a_classic_description() {
+        assert_eq!(
+            extract_puzzle_links(fixtures::CLASSIC),
+            ["https://sudokupad.app/4u6w7c9lh4"]
+        );
+    }
+
+    #[test]
+    fn trims_brackets_and_trailing_punctuation() {
+        assert_eq!(
+            find_urls(fixtures::PARENTHESISED),
+            [
+                "https://sudokupad.app/clover/miracle-killer",
+                "app.crackingthecryptic.com/sudoku/jH8ngjD3Qm",
+                "https://en.wikipedia.org/wiki/Sudoku_(disambiguation)",
+            ]
+        );
+        assert_eq!(
+            extract_puzzle_links(fixtures::PARENTHESISED),
+            [
+                "https://sudokupad.app/clover/miracle-killer",
+                "https://app.crackingthecryptic.com/sudoku/jH8ngjD3Qm",
+            ]
+        );
+    }
+
+    #[test]
+    fn finds_links_on_every_puzzle_host_without_duplicates() {
+        assert_eq!(
+            extract_puzzle_links(fixtures::MULTIPLE_HOSTS),
+            [
+                "https://sudokupad.app/psq8nvq7kd",
+                "http://f-puzzles.com/?id=yx7l8mh2",
+                "https://logic-masters.de/Raetselportal/Raetsel/zeigen.php?id=000FQ5",
+                "https://puzz.link/p?nurikabe/10/10/h5g3",
+                "https://sudokupad.app/scl/abc",
+            ]
+        );
+    }
+
+    #[test]
+    fn keeps_one_link_per_puzzle_across_mirrors() {
+        assert_eq!(
+            extract_puzzle_links(fixtures::MIRRORED),
+            ["https://sudokupad.app/jH8ngjD3Qm", "https://sudokupad.app/psq8nvq7kd"]
+        );
+    }
+
+    #[test]
+    fn matches_hosts_with_a_path_prefix() {
+        assert_eq!(
+            extract_puzzle_links(fixtures::CROSSWORD),
+            ["https://www.theguardian.com/crosswords/cryptic/29123"]
+        );
+    }
+
+    #[test]
+    fn finds_urls_after_characters_that_change_length_when_lowercased() {
+        assert_eq!(find_urls("see \u{212A}https://sudokupad.app/abc"), ["https://sudokupad.app/abc"]);
+        assert_eq!(find_urls("İHTTPS://sudokupad.app/abc"), ["HTTPS://sudokupad.app/abc"]);
+    }
+
+    #[test]
+    fn ignores_links_outside_the_allowlist() {
+        let extractor = LinkExtractor::new(&["f-puzzles.com"]);
+
+        assert_eq!(
+            puzzle_links(extractor.clone(), fixtures::MULTIPLE_HOSTS),
+            [link("http://f-puzzles.com/?id=yx7l8mh2", "F-Puzzles")]
+        );
+        assert!(puzzle_links(extractor, "https://notf-puzzles.com/x").is_empty());
+        assert!(puzzle_links(LinkExtractor::new::<&str>(&[]), fixtures::CLASSIC).is_empty());
+    }
+
+    #[test]
+    fn classifies_non_puzzle_links() {
+        let kinds = LinkExtractor::default()
+            .extract(fixtures::SUPPORT)
+            .into_iter()
+            .map(|link| (link.kind, link.puzzle_id.is_some()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            kinds,
+            [
+                (LinkKind::Puzzle, true),
+                (LinkKind::AppStore, false),
+                (LinkKind::AppStore, false),
+                (LinkKind::Merch, false),
+                (LinkKind::Patreon, false),
+                (LinkKind::Social, false),
+                (LinkKind::Other, false),
+            ]
+        );
+        assert_eq!(
+            LinkExtractor::default().classify("https://app.crackingthecryptic.com/sudoku/jH8ngjD3Qm"),
+            LinkKind::Puzzle
+        );
+    }
+
+    #[test]
+    fn labels_links_with_the_text_introducing_them() {
+        let links = puzzle_links(LinkExtractor::default(), fixtures::BONUS);
+
+        assert_eq!(
+            links,
+            [
+                link("https://sudokupad.app/bonus-thermo", "Bonus puzzle"),
+                link("https://sudokupad.app/todays-killer", "Today's puzzle"),
+                link("https://sudokupad.app/part-one", "If you missed it, the previous puzzle in the series"),
+            ]
+        );
+    }
+
+    #[test]
+    fn prefers_the_puzzle_solved_in_the_video() {
+        let links = LinkExtractor::default().extract(fixtures::BONUS);
+        assert_eq!(primary_link(&links).unwrap().url, "https://sudokupad.app/todays-killer");
+
+        let unlabelled = [link("https://sudokupad.app/a", "Bonus"), link("https://sudokupad.app/b", "")];
+        assert_eq!(primary_link(&unlabelled).unwrap().url, "https://sudokupad.app/b");
+        assert_eq!(primary_link(&[]), None);
+    }
+
+    #[test]
+    fn matches_label_words_whole() {
+        let links = [
+            link("https://sudokupad.app/a", "Also available"),
+            link("https://sudokupad.app/b", "Try today's puzzle"),
+        ];
+        assert_eq!(primary_link(&links).unwrap().url, "https://sudokupad.app/b");
+
+        let links = [
+            link("https://sudokupad.app/a", "Sequel"),
+            link("https://sudokupad.app/b", "Geometry entry"),
+        ];
+        assert_eq!(primary_link(&links).unwrap().url, "https://sudokupad.app/b");
+    }
+}
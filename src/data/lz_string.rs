@@ -0,0 +1,152 @@
+//! Decompression for the [lz-string](https://github.com/pieroxy/lz-string) format used by
+//! f-puzzles and SudokuPad to embed puzzles in links.
+
+/// Decompresses a string produced by lz-string's `compressToBase64` or
+/// `compressToEncodedURIComponent`.
+///
+/// Returns `None` if the input isn't valid lz-string data.
+pub fn decompress_from_base64(input: &str) -> Option<String> {
+    let values = input
+        .trim_end_matches('=')
+        .chars()
+        .map(base64_value)
+        .collect::<Option<Vec<_>>>()?;
+    if values.is_empty() {
+        return None;
+    }
+
+    let code_units = decompress(&values, 32)?;
+    String::from_utf16(&code_units).ok()
+}
+
+/// Maps a character of either lz-string alphabet to its 6-bit value.
+fn base64_value(c: char) -> Option<u32> {
+    match c {
+        'A'..='Z' => Some(c as u32 - 'A' as u32),
+        'a'..='z' => Some(c as u32 - 'a' as u32 + 26),
+        '0'..='9' => Some(c as u32 - '0' as u32 + 52),
+        '+' | ' ' => Some(62),
+        '/' | '-' => Some(63),
+        '$' => Some(64),
+        _ => None,
+    }
+}
+
+/// Reads bits from the input values, least significant bit of each value last.
+struct BitReader<'a> {
+    values: &'a [u32],
+    reset_value: u32,
+    value: u32,
+    position: u32,
+    index: usize,
+}
+impl<'a> BitReader<'a> {
+    fn new(values: &'a [u32], reset_value: u32) -> Self {
+        Self {
+            values,
+            reset_value,
+            value: values[0],
+            position: reset_value,
+            index: 1,
+        }
+    }
+
+    /// Reads `count` bits as a number, least significant bit first.
+    fn read(&mut self, count: u32) -> u32 {
+        let mut bits = 0;
+        for power in 0..count {
+            let bit = self.value & self.position;
+            self.position >>= 1;
+            if self.position == 0 {
+                self.position = self.reset_value;
+                self.value = self.values.get(self.index).copied().unwrap_or(0);
+                self.index += 1;
+            }
+            if bit > 0 {
+                bits |= 1 << power;
+            }
+        }
+        bits
+    }
+}
+
+/// The LZW-style decoder shared by every lz-string encoding, producing UTF-16 code units.
+fn decompress(values: &[u32], reset_value: u32) -> Option<Vec<u16>> {
+    let mut reader = BitReader::new(values, reset_value);
+    let mut dictionary: Vec<Vec<u16>> = vec![Vec::new(); 3];
+    let mut enlarge_in = 4u32;
+    let mut num_bits = 3u32;
+
+    let first = match reader.read(2) {
+        0 => reader.read(8),
+        1 => reader.read(16),
+        _ => return Some(Vec::new()),
+    };
+    let mut word = vec![first as u16];
+    dictionary.push(word.clone());
+    let mut result = word.clone();
+
+    loop {
+        if reader.index > values.len() {
+            return None;
+        }
+
+        let mut code = reader.read(num_bits) as usize;
+        match code {
+            0 | 1 => {
+                let bits = if code == 0 { 8 } else { 16 };
+                dictionary.push(vec![reader.read(bits) as u16]);
+                code = dictionary.len() - 1;
+                enlarge_in -= 1;
+            }
+            2 => return Some(result),
+            _ => {}
+        }
+
+        if enlarge_in == 0 {
+            enlarge_in = 1 << num_bits;
+            num_bits += 1;
+        }
+
+        let entry = if let Some(entry) = dictionary.get(code) {
+            entry.clone()
+        } else if code == dictionary.len() {
+            let mut entry = word.clone();
+            entry.push(word[0]);
+            entry
+        } else {
+            return None;
+        };
+        result.extend_from_slice(&entry);
+
+        let mut new_word = word;
+        new_word.push(entry[0]);
+        dictionary.push(new_word);
+        enlarge_in -= 1;
+        word = entry;
+
+        if enlarge_in == 0 {
+            enlarge_in = 1 << num_bits;
+            num_bits += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decompresses_base64_data() {
+        assert_eq!(
+            decompress_from_base64("N4IghiBcIBYKYBsEHsAE8loykBfIA===").as_deref(),
+            Some(r#"{"a":"hello hello hello"}"#)
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_data() {
+        assert_eq!(decompress_from_base64(""), None);
+        assert_eq!(decompress_from_base64("not*base64"), None);
+    }
+}
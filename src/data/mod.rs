@@ -1,4 +1,27 @@
 pub mod db;
+pub mod description;
+pub mod error;
+#[cfg(test)]
+mod fake_source;
+pub mod feed;
+pub mod grouping;
+pub mod history;
+mod http;
+pub mod links;
+mod lz_string;
 pub mod model;
+pub mod puzzle;
+pub mod puzzle_id;
+pub mod query;
+pub mod rules;
+pub mod search;
+pub mod seed;
+pub mod shortcuts;
+pub mod solve_state;
+pub mod solver;
+pub mod source;
+pub mod sync;
+pub mod thumbnail;
 mod traits;
+pub mod ui_state;
 pub mod youtube_api;
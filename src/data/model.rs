@@ -1,17 +1,17 @@
 use sqlx::prelude::FromRow;
 
-/// Extracts puzzle links from the video description.
-pub fn extract_links_from_description(description: &str) -> Vec<String> {
-    description
-        .split_whitespace()
-        .filter(|word| {
-            word.starts_with("http")
-                && (word.contains("sudokupad.app")
-                    || word.contains("crackingthecryptic.com")
-                    || word.contains("cracking-the-cryptic.web.app"))
-        })
-        .map(|word| word.to_string())
-        .collect::<Vec<_>>()
+use super::{
+    description::{solve_start, Chapter},
+    links::{primary_link, DescriptionLink, LinkExtractor},
+    youtube_api::get_upload_playlist,
+};
+
+/// YouTube channel ID for Cracking the Cryptic, the channel tracked by default.
+pub const CTC_CHANNEL_ID: &str = "UCC-UOdK8-mIjxBQm_ot1T-Q";
+
+/// Extracts and classifies the links in a video description using the default puzzle hosts.
+pub fn extract_links_from_description(description: &str) -> Vec<DescriptionLink> {
+    LinkExtractor::default().extract(description)
 }
 
 /// Creates a YouTube URL from a video ID.
@@ -19,6 +19,11 @@ pub fn youtube_url_from_id(id: &str) -> String {
     format!("https://www.youtube.com/watch?v={}", id)
 }
 
+/// Creates a YouTube URL that starts playing the video at the given number of seconds.
+pub fn youtube_url_at(id: &str, seconds: u64) -> String {
+    format!("{}&t={seconds}s", youtube_url_from_id(id))
+}
+
 /// Represents a YouTube video ID.
 #[derive(Debug, Clone, Hash, PartialEq, Eq, sqlx::Type)]
 #[sqlx(transparent)]
@@ -37,6 +42,11 @@ impl VideoPublishDate {
     pub fn new(date: i64) -> Self {
         Self(date)
     }
+
+    /// Returns the publish date in UTC, or `None` if it isn't known.
+    pub fn known(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        chrono::DateTime::from_timestamp_millis(self.0).filter(|_| self.0 > 0)
+    }
 }
 
 /// Represents a YouTube video duration in seconds.
@@ -47,6 +57,16 @@ impl VideoDuration {
     pub fn new(duration: u64) -> Self {
         Self(duration)
     }
+
+    /// Creates a duration for a video whose length isn't known yet.
+    pub fn unknown() -> Self {
+        Self(0)
+    }
+
+    /// Returns whether the duration is unknown (e.g. the video came from a feed without durations).
+    pub fn is_unknown(&self) -> bool {
+        self.0 == 0
+    }
 }
 
 /// Represents a video from the Cracking the Cryptic YouTube channel.
@@ -64,17 +84,48 @@ pub struct CtcVideo {
     /// Date when the video was published as a Unix timestamp in milliseconds.
     pub date: VideoPublishDate,
 
-    /// Duration of the video in seconds.
+    /// Duration of the video in seconds, or zero if unknown.
     pub duration: VideoDuration,
 
-    /// Links extracted from the video description.
-    pub extracted_links: Vec<String>,
+    /// Links extracted from the video description, with their labels and kinds.
+    pub extracted_links: Vec<DescriptionLink>,
+
+    /// Chapter markers from the video description.
+    pub chapters: Vec<Chapter>,
+
+    /// ID of the channel that uploaded the video.
+    pub channel_id: String,
 }
 impl CtcVideo {
     /// Returns the YouTube URL for the video.
     pub fn get_video_url(&self) -> String {
         youtube_url_from_id(&self.id)
     }
+
+    /// Returns the YouTube URL for the video starting at the given number of seconds.
+    pub fn get_video_url_at(&self, seconds: u64) -> String {
+        youtube_url_at(&self.id, seconds)
+    }
+
+    /// Returns the chapter where the solve starts, if the description marks it.
+    pub fn solve_start(&self) -> Option<&Chapter> {
+        solve_start(&self.chapters)
+    }
+
+    /// Returns the puzzle link most likely to be the one solved in the video.
+    pub fn primary_link(&self) -> Option<&DescriptionLink> {
+        primary_link(&self.extracted_links)
+    }
+
+    /// Returns the links in the description that point at puzzles.
+    pub fn puzzle_links(&self) -> impl Iterator<Item = &DescriptionLink> {
+        self.extracted_links.iter().filter(|link| link.is_puzzle())
+    }
+
+    /// Returns the links in the description that don't point at puzzles (Patreon, merch, ...).
+    pub fn other_links(&self) -> impl Iterator<Item = &DescriptionLink> {
+        self.extracted_links.iter().filter(|link| !link.is_puzzle())
+    }
 }
 
 /// Represents a row in the video data table.
@@ -85,6 +136,7 @@ pub struct CtcVideoRow {
     pub description: String,
     pub date: i64,
     pub duration: u64,
+    pub channel_id: String,
 }
 
 /// Represents the completion status of a video.
@@ -93,3 +145,175 @@ pub struct CtcVideoCompletionRow {
     pub id: VideoId,
     pub completed: bool,
 }
+
+/// A change to a video's completion status.
+#[derive(Debug, Clone, PartialEq, Eq, FromRow)]
+pub struct CompletionEvent {
+    pub completed: bool,
+
+    /// When the status changed, as a Unix timestamp in milliseconds.
+    pub changed_at: i64,
+}
+impl CompletionEvent {
+    /// Creates an event for a status change happening now.
+    pub fn now(completed: bool) -> Self {
+        Self {
+            completed,
+            changed_at: chrono::Utc::now().timestamp_millis(),
+        }
+    }
+
+    /// Returns when the status changed as local date and time text.
+    pub fn changed_at_text(&self) -> String {
+        chrono::DateTime::from_timestamp_millis(self.changed_at)
+            .map(|date| date.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_default()
+    }
+}
+
+/// Represents a YouTube channel whose uploads are tracked.
+#[derive(Debug, Clone, PartialEq, Eq, FromRow)]
+pub struct Channel {
+    /// YouTube channel ID (starts with `UC`).
+    pub id: String,
+
+    /// Name shown in the grid.
+    pub display_name: String,
+
+    /// ID of the channel's uploads playlist.
+    pub uploads_playlist: String,
+
+    /// Whether the channel is synced and shown.
+    pub enabled: bool,
+}
+
+impl Channel {
+    /// Creates an enabled channel, deriving its uploads playlist from the channel ID.
+    pub fn new(id: &str, display_name: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            display_name: display_name.to_string(),
+            uploads_playlist: get_upload_playlist(id),
+            enabled: true,
+        }
+    }
+}
+
+/// A reference to a channel entered by the user.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChannelRef {
+    /// A channel ID such as `UCC-UOdK8-mIjxBQm_ot1T-Q`.
+    Id(String),
+
+    /// A handle such as `@CrackingTheCryptic`, stored without the `@`.
+    Handle(String),
+}
+impl ChannelRef {
+    /// Parses a channel URL, handle or ID as entered in settings.
+    ///
+    /// Accepts `https://www.youtube.com/channel/UC...`, `https://www.youtube.com/@handle`,
+    /// `@handle` and bare `UC...` IDs.
+    pub fn parse(input: &str) -> Option<Self> {
+        let input = input.trim();
+        let path = input
+            .split_once("youtube.com/")
+            .map(|(_, path)| path)
+            .unwrap_or(input);
+        let segment = |path: &str| {
+            path.split(['/', '?', '#'])
+                .next()
+                .filter(|segment| !segment.is_empty())
+                .map(str::to_string)
+        };
+
+        if let Some(id) = path.strip_prefix("channel/") {
+            return segment(id).map(Self::Id);
+        }
+        if let Some(handle) = path.strip_prefix('@') {
+            return segment(handle).map(Self::Handle);
+        }
+        if path.starts_with("UC") && path.len() == 24 && !path.contains('/') {
+            return Some(Self::Id(path.to_string()));
+        }
+        None
+    }
+}
+
+/// Represents a YouTube playlist tracked as a collection (e.g. a puzzle series).
+#[derive(Debug, Clone, PartialEq, Eq, FromRow)]
+pub struct Playlist {
+    /// YouTube playlist ID.
+    pub id: String,
+
+    /// Title of the playlist.
+    pub title: String,
+
+    /// Whether the playlist is synced and offered as a filter.
+    pub enabled: bool,
+}
+impl Playlist {
+    /// Creates an enabled playlist.
+    pub fn new(id: &str, title: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            title: title.to_string(),
+            enabled: true,
+        }
+    }
+
+    /// Parses a playlist URL (anything with a `list=` parameter) or a bare playlist ID.
+    pub fn parse_id(input: &str) -> Option<String> {
+        let input = input.trim();
+        let id = match input.split_once("list=") {
+            Some((_, rest)) => rest.split(['&', '#']).next().unwrap_or_default(),
+            None if !input.contains(['/', '?', ' ']) => input,
+            None => "",
+        };
+        (!id.is_empty()).then(|| id.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_channel_references() {
+        let id = ChannelRef::Id(CTC_CHANNEL_ID.to_string());
+        assert_eq!(ChannelRef::parse(CTC_CHANNEL_ID), Some(id.clone()));
+        assert_eq!(
+            ChannelRef::parse("https://www.youtube.com/channel/UCC-UOdK8-mIjxBQm_ot1T-Q/videos"),
+            Some(id)
+        );
+        assert_eq!(
+            ChannelRef::parse("https://youtube.com/@CrackingTheCryptic?si=abc"),
+            Some(ChannelRef::Handle("CrackingTheCryptic".to_string()))
+        );
+        assert_eq!(
+            ChannelRef::parse(" @Mark "),
+            Some(ChannelRef::Handle("Mark".to_string()))
+        );
+        assert_eq!(ChannelRef::parse("not a channel"), None);
+    }
+
+    #[test]
+    fn treats_a_zero_publish_date_as_unknown() {
+        assert_eq!(VideoPublishDate::new(0).known(), None);
+        let date = VideoPublishDate::new(1_700_000_000_000).known().unwrap();
+        assert_eq!(date.format("%Y-%m").to_string(), "2023-11");
+    }
+
+    #[test]
+    fn parses_playlist_ids() {
+        assert_eq!(
+            Playlist::parse_id("https://www.youtube.com/playlist?list=PLabc123&si=xyz").as_deref(),
+            Some("PLabc123")
+        );
+        assert_eq!(
+            Playlist::parse_id("https://www.youtube.com/watch?v=vid&list=PLabc123").as_deref(),
+            Some("PLabc123")
+        );
+        assert_eq!(Playlist::parse_id("PLabc123").as_deref(), Some("PLabc123"));
+        assert_eq!(Playlist::parse_id("https://www.youtube.com/watch?v=vid"), None);
+    }
+}
@@ -0,0 +1,518 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::{error::DataError, lz_string::decompress_from_base64, puzzle_id::PuzzleRef};
+
+/// A grid cell as a zero-based `(row, column)` pair.
+pub type Cell = (usize, usize);
+
+/// f-puzzles constraint keys and the names they're shown and searched under.
+const FPUZZLES_CONSTRAINTS: &[(&str, &str)] = &[
+    ("killercage", "killer cage"),
+    ("thermometer", "thermometer"),
+    ("arrow", "arrow"),
+    ("littlekillersum", "little killer"),
+    ("sandwichsum", "sandwich"),
+    ("difference", "kropki"),
+    ("ratio", "kropki"),
+    ("xv", "XV"),
+    ("clone", "clone"),
+    ("palindrome", "palindrome"),
+    ("renban", "renban"),
+    ("whispers", "German whispers"),
+    ("betweenline", "between line"),
+    ("regionsumline", "region sum line"),
+    ("quadruple", "quadruple"),
+    ("odd", "odd"),
+    ("even", "even"),
+    ("minimum", "minimum"),
+    ("maximum", "maximum"),
+    ("extraregion", "extra region"),
+    ("cage", "cage"),
+    ("diagonal+", "diagonal"),
+    ("diagonal-", "diagonal"),
+    ("antiknight", "anti-knight"),
+    ("antiking", "anti-king"),
+    ("disjointgroups", "disjoint groups"),
+    ("nonconsecutive", "nonconsecutive"),
+];
+
+/// Largest grid the sudoku player supports, as digits are entered with the keys 1 to 9.
+pub const MAX_PLAYABLE_SIZE: usize = 9;
+
+/// A killer cage, with its sum if one is given.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Cage {
+    pub cells: Vec<Cell>,
+    pub sum: Option<u32>,
+}
+
+/// Structured description of a puzzle decoded from a link.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PuzzleDescription {
+    /// Number of rows and columns.
+    pub size: usize,
+
+    pub title: Option<String>,
+    pub author: Option<String>,
+    pub rules: Option<String>,
+
+    /// Given digits as `(cell, digit)` pairs.
+    pub givens: Vec<(Cell, u8)>,
+
+    /// Cells of each region (boxes, or irregular regions), if the puzzle defines them.
+    #[serde(default)]
+    pub regions: Vec<Vec<Cell>>,
+
+    /// Names of the constraint types used, sorted and without duplicates.
+    pub constraints: Vec<String>,
+
+    #[serde(default)]
+    pub cages: Vec<Cage>,
+
+    /// Thermometers as cells from the bulb to the tip.
+    #[serde(default)]
+    pub thermometers: Vec<Vec<Cell>>,
+}
+impl PuzzleDescription {
+    /// Decodes the puzzle embedded in a link, if it embeds one.
+    ///
+    /// Returns `Ok(None)` for links that only reference a puzzle stored elsewhere.
+    pub fn from_url(url: &str) -> Result<Option<Self>, DataError> {
+        let Some(PuzzleRef::Encoded(encoding, payload)) = PuzzleRef::parse(url) else {
+            return Ok(None);
+        };
+
+        let json = decompress_from_base64(&payload)
+            .ok_or_else(|| DataError::Parse(format!("Invalid {encoding} payload")))?;
+        let value = serde_json::from_str::<Value>(&json)?;
+        let description = match encoding {
+            "fpuzzles" => Self::from_fpuzzles(&value)?,
+            _ => Self::from_scl(&value)?,
+        };
+        Ok(Some(description))
+    }
+
+    /// Returns whether the puzzle can be solved in the sudoku player.
+    pub fn is_playable(&self) -> bool {
+        (1..=MAX_PLAYABLE_SIZE).contains(&self.size)
+    }
+
+    /// Returns a one-line summary such as "Title by Author — 9×9, killer cage".
+    pub fn summary(&self) -> String {
+        let mut summary = self.title.clone().unwrap_or_else(|| "Untitled puzzle".to_string());
+        if let Some(author) = &self.author {
+            summary.push_str(&format!(" by {author}"));
+        }
+        summary.push_str(&format!(" — {}×{}", self.size, self.size));
+        for constraint in &self.constraints {
+            summary.push_str(&format!(", {constraint}"));
+        }
+        summary
+    }
+
+    /// Returns whether the title, author, rules or constraint names contain `text` (lowercase).
+    pub fn matches_text(&self, text: &str) -> bool {
+        [&self.title, &self.author, &self.rules]
+            .into_iter()
+            .flatten()
+            .chain(&self.constraints)
+            .any(|field| field.to_lowercase().contains(text))
+    }
+
+    /// Returns the given digit in a cell, ignoring any given outside the grid or its digits.
+    pub fn given(&self, cell: Cell) -> Option<u8> {
+        let fits = |((row, col), digit): &(Cell, u8)| {
+            *row < self.size && *col < self.size && (1..=self.size).contains(&usize::from(*digit))
+        };
+        self.givens
+            .iter()
+            .filter(|given| fits(given))
+            .find(|(given_cell, _)| *given_cell == cell)
+            .map(|(_, digit)| *digit)
+    }
+
+    /// Returns the puzzle's regions, or the standard boxes for its size if it doesn't define any.
+    pub fn regions_or_boxes(&self) -> Vec<Vec<Cell>> {
+        if !self.regions.is_empty() {
+            return self.regions.clone();
+        }
+
+        // Boxes are as close to square as possible, wider than tall (e.g. 2×3 for 6×6)
+        let box_rows = (1..=self.size)
+            .filter(|rows| rows * rows <= self.size && self.size.is_multiple_of(*rows))
+            .max()
+            .unwrap_or(1);
+        let box_cols = self.size / box_rows.max(1);
+        (0..self.size)
+            .map(|index| {
+                let (top, left) = ((index / box_rows) * box_rows, (index % box_rows) * box_cols);
+                (0..self.size)
+                    .map(|cell| (top + cell / box_cols, left + cell % box_cols))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Returns whether a completely filled grid (row by row) obeys the puzzle's rules: each row,
+    /// column and region contains every digit once, cages add up without repeating digits, and
+    /// thermometers increase from the bulb.
+    pub fn is_valid_solution(&self, grid: &[u8]) -> bool {
+        let size = self.size;
+        if size == 0 || grid.len() != size * size {
+            return false;
+        }
+        let digit = |(row, col): Cell| grid.get(row * size + col).copied().unwrap_or(0);
+        let all_different = |cells: &[Cell]| {
+            let mut seen = vec![false; size + 1];
+            cells.iter().all(|cell| {
+                let digit = digit(*cell) as usize;
+                (1..=size).contains(&digit) && !std::mem::replace(&mut seen[digit], true)
+            })
+        };
+
+        let rows = (0..size).map(|row| (0..size).map(|col| (row, col)).collect::<Vec<_>>());
+        let cols = (0..size).map(|col| (0..size).map(|row| (row, col)).collect::<Vec<_>>());
+        let units_valid = rows
+            .chain(cols)
+            .chain(self.regions_or_boxes())
+            .all(|unit| all_different(&unit));
+
+        let cages_valid = self.cages.iter().all(|cage| {
+            let total = cage.cells.iter().map(|cell| u32::from(digit(*cell))).sum::<u32>();
+            all_different(&cage.cells) && cage.sum.is_none_or(|sum| sum == total)
+        });
+
+        let thermometers_valid = self
+            .thermometers
+            .iter()
+            .all(|thermometer| thermometer.windows(2).all(|pair| digit(pair[0]) < digit(pair[1])));
+
+        units_valid && cages_valid && thermometers_valid
+    }
+
+    /// Decodes f-puzzles JSON.
+    fn from_fpuzzles(value: &Value) -> Result<Self, DataError> {
+        let size = value.get("size").and_then(Value::as_u64).unwrap_or(9) as usize;
+
+        let mut givens = Vec::new();
+        let mut irregular = false;
+        for (row, cells) in array(value, "grid").iter().enumerate() {
+            for (col, cell) in cells.as_array().into_iter().flatten().enumerate() {
+                let digit = cell.get("value").and_then(Value::as_u64);
+                let given = cell.get("given").and_then(Value::as_bool).unwrap_or(false);
+                if let (Some(digit), true) = (digit, given) {
+                    givens.push(checked_given(size, (row, col), digit)?);
+                }
+                irregular |= cell.get("region").is_some();
+            }
+        }
+
+        let mut constraints = FPUZZLES_CONSTRAINTS
+            .iter()
+            .filter(|(key, _)| match value.get(*key) {
+                Some(Value::Bool(enabled)) => *enabled,
+                Some(Value::Array(items)) => !items.is_empty(),
+                _ => false,
+            })
+            .map(|(_, name)| name.to_string())
+            .collect::<Vec<_>>();
+        if irregular {
+            constraints.push("irregular".to_string());
+        }
+        constraints.sort();
+        constraints.dedup();
+
+        let cages = array(value, "killercage")
+            .iter()
+            .map(|cage| Cage {
+                cells: array(cage, "cells").iter().filter_map(fpuzzles_cell).collect(),
+                sum: cage
+                    .get("value")
+                    .and_then(Value::as_str)
+                    .and_then(|sum| sum.trim().parse().ok()),
+            })
+            .collect();
+
+        let thermometers = array(value, "thermometer")
+            .iter()
+            .flat_map(|thermometer| array(thermometer, "lines"))
+            .map(|line| {
+                line.as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(fpuzzles_cell)
+                    .collect()
+            })
+            .collect();
+
+        Ok(Self {
+            size,
+            title: string(value, "title"),
+            author: string(value, "author"),
+            rules: string(value, "ruleset"),
+            givens,
+            regions: Vec::new(),
+            constraints,
+            cages,
+            thermometers,
+        })
+    }
+
+    /// Decodes SudokuPad (SCL) JSON.
+    ///
+    /// SCL stores the title, author and rules as cell-less cages with values such as
+    /// `title: ...`, and thermometers as thick grey lines starting at a round bulb.
+    fn from_scl(value: &Value) -> Result<Self, DataError> {
+        let rows = array(value, "cells");
+        let size = rows.len();
+
+        let mut givens = Vec::new();
+        for (row, cells) in rows.iter().enumerate() {
+            for (col, cell) in cells.as_array().into_iter().flatten().enumerate() {
+                let digit = cell
+                    .get("value")
+                    .and_then(|digit| digit.as_u64().or_else(|| digit.as_str()?.parse().ok()));
+                if let Some(digit) = digit {
+                    givens.push(checked_given(size, (row, col), digit)?);
+                }
+            }
+        }
+
+        let regions = array(value, "regions")
+            .iter()
+            .map(|region| region.as_array().into_iter().flatten().filter_map(scl_cell).collect())
+            .collect();
+
+        let mut description = Self {
+            size,
+            givens,
+            regions,
+            ..Self::default()
+        };
+
+        for cage in array(value, "cages") {
+            let cage_value = cage.get("value").and_then(|v| {
+                v.as_str().map(str::to_string).or_else(|| v.as_u64().map(|n| n.to_string()))
+            });
+            let cells = array(cage, "cells").iter().filter_map(scl_cell).collect::<Vec<_>>();
+
+            if cells.is_empty() {
+                let Some((key, text)) = cage_value.as_deref().and_then(|v| v.split_once(':')) else {
+                    continue;
+                };
+                let text = Some(text.trim().to_string());
+                match key.trim() {
+                    "title" => description.title = text,
+                    "author" => description.author = text,
+                    "rules" => description.rules = text,
+                    _ => {}
+                }
+            } else {
+                description.cages.push(Cage {
+                    cells,
+                    sum: cage_value.and_then(|sum| sum.trim().parse().ok()),
+                });
+            }
+        }
+
+        // Thermometer bulbs are round grey underlays at the start of a grey line
+        let bulbs = array(value, "underlays")
+            .iter()
+            .chain(array(value, "overlays"))
+            .filter(|shape| shape.get("rounded").and_then(Value::as_bool).unwrap_or(false))
+            .filter_map(|shape| scl_point(shape.get("center")?))
+            .collect::<Vec<_>>();
+        let mut other_lines = false;
+        for line in array(value, "lines") {
+            let cells = array(line, "wayPoints")
+                .iter()
+                .filter_map(scl_point)
+                .collect::<Vec<_>>();
+            let is_thermometer = line.get("color").and_then(Value::as_str) == Some("#CFCFCF")
+                && cells.first().is_some_and(|start| bulbs.contains(start));
+            if is_thermometer {
+                description.thermometers.push(cells);
+            } else {
+                other_lines = true;
+            }
+        }
+
+        let mut constraints = Vec::new();
+        if !description.cages.is_empty() {
+            constraints.push("killer cage".to_string());
+        }
+        if !description.thermometers.is_empty() {
+            constraints.push("thermometer".to_string());
+        }
+        if other_lines {
+            constraints.push("line".to_string());
+        }
+        if !array(value, "arrows").is_empty() {
+            constraints.push("arrow".to_string());
+        }
+        constraints.sort();
+        description.constraints = constraints;
+
+        Ok(description)
+    }
+}
+
+/// Returns a given digit, or an error if it or its cell doesn't fit a grid of the given size.
+fn checked_given(size: usize, (row, col): Cell, digit: u64) -> Result<(Cell, u8), DataError> {
+    match u8::try_from(digit) {
+        Ok(digit) if row < size && col < size && (1..=size).contains(&usize::from(digit)) => {
+            Ok(((row, col), digit))
+        }
+        _ => Err(DataError::Parse(format!(
+            "Given {digit} in row {}, column {} doesn't fit a {size}x{size} grid",
+            row + 1,
+            col + 1
+        ))),
+    }
+}
+
+/// Returns an array field, or an empty slice if it's missing.
+fn array<'a>(value: &'a Value, key: &str) -> &'a [Value] {
+    value
+        .get(key)
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+}
+
+/// Returns a non-empty string field.
+fn string(value: &Value, key: &str) -> Option<String> {
+    value
+        .get(key)
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|text| !text.is_empty())
+        .map(str::to_string)
+}
+
+/// Parses an f-puzzles cell reference such as `R1C3`.
+fn fpuzzles_cell(value: &Value) -> Option<Cell> {
+    let (row, col) = value.as_str()?.trim_start_matches(['R', 'r']).split_once(['C', 'c'])?;
+    Some((row.parse::<usize>().ok()?.checked_sub(1)?, col.parse::<usize>().ok()?.checked_sub(1)?))
+}
+
+/// Parses an SCL cell given as `[row, column]`.
+fn scl_cell(value: &Value) -> Option<Cell> {
+    let cell = value.as_array()?;
+    Some((cell.first()?.as_u64()? as usize, cell.get(1)?.as_u64()? as usize))
+}
+
+/// Finds the cell containing an SCL point given as `[row, column]` in fractional cells.
+fn scl_point(value: &Value) -> Option<Cell> {
+    let point = value.as_array()?;
+    let row = point.first()?.as_f64()?;
+    let col = point.get(1)?.as_f64()?;
+    (row >= 0.0 && col >= 0.0).then_some((row as usize, col as usize))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Solution of the classic puzzle in the f-puzzles fixture.
+    const SOLUTION: &str = "534678912672195348198342567859761423426853791713924856961537284287419635345286179";
+
+    mod fixtures {
+        pub const FPUZZLES_CLASSIC: &str = include_str!("fixtures/puzzles/fpuzzles_classic.txt");
+        pub const SCL_TINY: &str = include_str!("fixtures/puzzles/scl_tiny.txt");
+    }
+
+    #[test]
+    fn decodes_fpuzzles_links() {
+        let puzzle = PuzzleDescription::from_url(fixtures::FPUZZLES_CLASSIC.trim())
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(puzzle.size, 9);
+        assert!(puzzle.is_playable());
+        assert!(!PuzzleDescription { size: 16, ..puzzle.clone() }.is_playable());
+        assert_eq!(puzzle.title.as_deref(), Some("Wiki Classic"));
+        assert_eq!(puzzle.author.as_deref(), Some("Wikipedia"));
+        assert!(puzzle.rules.as_deref().unwrap().starts_with("Normal sudoku rules apply."));
+        assert_eq!(puzzle.givens.len(), 30);
+        assert_eq!(puzzle.givens[0], ((0, 0), 5));
+        assert_eq!(puzzle.constraints, ["killer cage", "thermometer"]);
+        assert_eq!(puzzle.cages, [Cage { cells: vec![(0, 2), (0, 3)], sum: Some(10) }]);
+        assert_eq!(puzzle.thermometers, [vec![(8, 0), (8, 1), (8, 2)]]);
+    }
+
+    #[test]
+    fn decodes_scl_links() {
+        let puzzle = PuzzleDescription::from_url(fixtures::SCL_TINY.trim()).unwrap().unwrap();
+
+        assert_eq!(puzzle.size, 4);
+        assert_eq!(puzzle.title.as_deref(), Some("Tiny Lines"));
+        assert_eq!(puzzle.author.as_deref(), Some("Clover"));
+        assert_eq!(puzzle.givens, [((0, 0), 1), ((1, 2), 1), ((2, 1), 4), ((3, 3), 2)]);
+        assert_eq!(puzzle.regions.len(), 4);
+        assert_eq!(puzzle.constraints, ["killer cage", "line", "thermometer"]);
+        assert_eq!(puzzle.cages[0].sum, Some(5));
+        assert_eq!(puzzle.thermometers, [vec![(1, 0), (1, 1), (2, 1)]]);
+        assert!(puzzle.matches_text("clover"));
+        assert!(!puzzle.matches_text("german"));
+    }
+
+    #[test]
+    fn rejects_givens_that_do_not_fit_the_grid() {
+        let fpuzzles = |digit: u64| {
+            serde_json::json!({
+                "size": 4,
+                "grid": [[{ "value": digit, "given": true }]],
+            })
+        };
+        assert!(PuzzleDescription::from_fpuzzles(&fpuzzles(4)).is_ok());
+        for digit in [0, 5, 257] {
+            assert!(matches!(PuzzleDescription::from_fpuzzles(&fpuzzles(digit)), Err(DataError::Parse(_))));
+        }
+
+        // The f-puzzles grid has a fifth column
+        let wide = serde_json::json!({
+            "size": 4,
+            "grid": [[{}, {}, {}, {}, { "value": 1, "given": true }]],
+        });
+        assert!(matches!(PuzzleDescription::from_fpuzzles(&wide), Err(DataError::Parse(_))));
+
+        // SCL takes its size from the number of rows, so the second row's third cell is outside
+        let scl = serde_json::json!({ "cells": [[{}, {}], [{}, {}, { "value": "1" }]] });
+        assert!(matches!(PuzzleDescription::from_scl(&scl), Err(DataError::Parse(_))));
+        let scl = serde_json::json!({ "cells": [[{}, {}], [{}, { "value": "3" }]] });
+        assert!(matches!(PuzzleDescription::from_scl(&scl), Err(DataError::Parse(_))));
+    }
+
+    #[test]
+    fn validates_solutions_against_every_constraint() {
+        let puzzle = PuzzleDescription::from_url(fixtures::FPUZZLES_CLASSIC.trim())
+            .unwrap()
+            .unwrap();
+        let mut grid = SOLUTION.bytes().map(|digit| digit - b'0').collect::<Vec<_>>();
+        assert!(puzzle.is_valid_solution(&grid));
+
+        // Swapping two digits in a row keeps the row valid but breaks a column
+        grid.swap(0, 1);
+        assert!(!puzzle.is_valid_solution(&grid));
+    }
+
+    #[test]
+    fn uses_standard_boxes_when_no_regions_are_given() {
+        let boxes = |size| PuzzleDescription { size, ..PuzzleDescription::default() }.regions_or_boxes();
+
+        assert_eq!(boxes(9)[4][0], (3, 3));
+        assert_eq!(boxes(6)[1], [(0, 3), (0, 4), (0, 5), (1, 3), (1, 4), (1, 5)]);
+        assert_eq!(boxes(4)[3], [(2, 2), (2, 3), (3, 2), (3, 3)]);
+    }
+
+    #[test]
+    fn ignores_links_without_an_embedded_puzzle() {
+        assert_eq!(PuzzleDescription::from_url("https://sudokupad.app/psq8nvq7kd").unwrap(), None);
+        assert!(matches!(
+            PuzzleDescription::from_url("https://sudokupad.app/fpuzzlesNotReallyCompressedData"),
+            Err(DataError::Parse(_))
+        ));
+    }
+}
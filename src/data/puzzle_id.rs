@@ -0,0 +1,212 @@
+use std::fmt::Display;
+
+use super::links::split_url;
+
+/// Hosts serving the SudokuPad app, all of which accept the same puzzle IDs.
+const SUDOKUPAD_HOSTS: &[&str] = &[
+    "sudokupad.app",
+    "app.crackingthecryptic.com",
+    "cracking-the-cryptic.web.app",
+    "sudokupad.svencodes.com",
+];
+
+/// Prefixes of SudokuPad puzzle IDs that carry the whole puzzle instead of a short ID.
+const ENCODED_PREFIXES: &[&str] = &["fpuzzles", "scl", "ctc"];
+
+/// Shortest payload treated as an encoded puzzle rather than a short ID that happens to start
+/// with an encoding prefix.
+const MIN_ENCODED_LENGTH: usize = 20;
+
+/// Canonical identifier of a puzzle, independent of which link was used to reach it.
+///
+/// Short SudokuPad IDs become `sudokupad:<id>`, encoded puzzles become `<encoding>:<hash>` (so
+/// the same f-puzzles data shared via SudokuPad or f-puzzles.com matches), and links to other
+/// sites become `url:<host>/<path>`.
+#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, sqlx::Type)]
+#[sqlx(transparent)]
+pub struct PuzzleId(pub String);
+impl PuzzleId {
+    /// Returns the canonical identifier of the puzzle a link points at.
+    pub fn from_url(url: &str) -> Option<Self> {
+        match PuzzleRef::parse(url)? {
+            PuzzleRef::SudokuPad(id) => Some(Self(format!("sudokupad:{id}"))),
+            PuzzleRef::Encoded(encoding, payload) => Some(Self::encoded(encoding, &payload)),
+            PuzzleRef::FpuzzlesId(id) => Some(Self(format!("fpuzzles-id:{id}"))),
+            PuzzleRef::Other(url) => Some(Self(format!("url:{url}"))),
+        }
+    }
+
+    /// Creates the identifier of an encoded puzzle from its payload.
+    fn encoded(encoding: &str, payload: &str) -> Self {
+        Self(format!("{encoding}:{:016x}", fnv1a(payload.as_bytes())))
+    }
+}
+impl Display for PuzzleId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+impl std::ops::Deref for PuzzleId {
+    type Target = String;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// What a puzzle link refers to.
+pub(crate) enum PuzzleRef {
+    /// A short SudokuPad ID or `user/slug` path.
+    SudokuPad(String),
+
+    /// A puzzle embedded in the link: the encoding (`fpuzzles`, `scl` or `ctc`) and its payload.
+    Encoded(&'static str, String),
+
+    /// A puzzle stored on f-puzzles.com under a short ID.
+    FpuzzlesId(String),
+
+    /// Any other link, as `<host>/<path>` with its query string.
+    Other(String),
+}
+impl PuzzleRef {
+    /// Works out what a link refers to.
+    pub(crate) fn parse(url: &str) -> Option<Self> {
+        let (host, rest) = split_url(url);
+        let rest = rest.split('#').next().unwrap_or_default();
+        let (path, query) = rest.split_once('?').unwrap_or((rest, ""));
+        let path = path.trim_matches('/');
+
+        if SUDOKUPAD_HOSTS
+            .iter()
+            .any(|sudokupad_host| host == *sudokupad_host)
+        {
+            let id = query_param(query, "puzzleid")
+                .unwrap_or_else(|| path.strip_prefix("sudoku/").unwrap_or(path).to_string());
+            return Self::sudokupad(&percent_decode(&id));
+        }
+
+        if host == "f-puzzles.com" {
+            if let Some(data) = query_param(query, "load") {
+                return Some(Self::Encoded("fpuzzles", percent_decode(&data)));
+            }
+            if let Some(id) = query_param(query, "id") {
+                return Some(Self::FpuzzlesId(id));
+            }
+        }
+
+        if host.is_empty() {
+            return None;
+        }
+        let query = if query.is_empty() {
+            String::new()
+        } else {
+            format!("?{query}")
+        };
+        Some(Self::Other(format!("{host}/{path}{query}")))
+    }
+
+    /// Classifies a SudokuPad puzzle ID (a short ID, a `user/slug` path or an encoded puzzle).
+    fn sudokupad(id: &str) -> Option<Self> {
+        if id.is_empty() {
+            return None;
+        }
+
+        for prefix in ENCODED_PREFIXES {
+            if let Some(payload) = id.strip_prefix(prefix) {
+                if payload.len() >= MIN_ENCODED_LENGTH {
+                    return Some(Self::Encoded(prefix, payload.to_string()));
+                }
+            }
+        }
+
+        Some(Self::SudokuPad(id.to_string()))
+    }
+}
+
+/// Returns the value of a query string parameter.
+fn query_param(query: &str, name: &str) -> Option<String> {
+    query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(key, _)| key.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.to_string())
+        .filter(|value| !value.is_empty())
+}
+
+/// Decodes `%XX` escapes, leaving malformed escapes as they are.
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        let escaped = (bytes[index] == b'%')
+            .then(|| text.get(index + 1..index + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                index += 3;
+            }
+            None => {
+                decoded.push(bytes[index]);
+                index += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// 64-bit FNV-1a hash, used because it's stable across Rust versions and platforms.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn id(url: &str) -> String {
+        PuzzleId::from_url(url).map(|id| id.0).unwrap_or_default()
+    }
+
+    #[test]
+    fn canonicalises_short_ids_on_every_sudokupad_host() {
+        for url in [
+            "https://sudokupad.app/psq8nvq7kd",
+            "https://sudokupad.app/psq8nvq7kd/?setting-nogrid=1",
+            "https://app.crackingthecryptic.com/sudoku/psq8nvq7kd",
+            "https://cracking-the-cryptic.web.app/sudoku/psq8nvq7kd#notes",
+            "https://www.sudokupad.app/?puzzleid=psq8nvq7kd",
+        ] {
+            assert_eq!(id(url), "sudokupad:psq8nvq7kd", "{url}");
+        }
+        assert_eq!(id("https://sudokupad.app/clover/miracle-killer"), "sudokupad:clover/miracle-killer");
+    }
+
+    #[test]
+    fn matches_encoded_puzzles_across_sites() {
+        let payload = "N4IgzglgXgpiBcBOANCA5gJwhAJgQQDsBPAGwFcBjAFwmRzKsPS3kQFF0YBDAayoAsA9hg";
+        let via_sudokupad = id(&format!("https://sudokupad.app/fpuzzles{payload}"));
+        let via_fpuzzles = id(&format!("https://f-puzzles.com/?load={payload}"));
+        let via_query = id(&format!("https://sudokupad.app/?puzzleid=fpuzzles{}", payload.replace('N', "%4E")));
+
+        assert!(via_sudokupad.starts_with("fpuzzles:"));
+        assert_eq!(via_sudokupad, via_fpuzzles);
+        assert_eq!(via_sudokupad, via_query);
+        assert!(id(&format!("https://sudokupad.app/scl{payload}")).starts_with("scl:"));
+        assert_eq!(id("https://sudokupad.app/sclover"), "sudokupad:sclover");
+    }
+
+    #[test]
+    fn falls_back_to_the_url_for_other_sites() {
+        assert_eq!(id("http://f-puzzles.com/?id=yx7l8mh2"), "fpuzzles-id:yx7l8mh2");
+        assert_eq!(
+            id("https://www.logic-masters.de/Raetselportal/Raetsel/zeigen.php?id=000FQ5"),
+            "url:logic-masters.de/Raetselportal/Raetsel/zeigen.php?id=000FQ5"
+        );
+        assert_eq!(id("https://sudokupad.app/"), "");
+    }
+}
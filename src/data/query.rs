@@ -0,0 +1,372 @@
+use chrono::Datelike;
+
+use super::{model::CtcVideo, puzzle::PuzzleDescription};
+
+/// Fields that can be searched with `field:value` terms.
+const FIELDS: &str = "setter, title, channel, tag, notes, duration or year";
+
+/// A parsed filter-box query such as
+/// `setter:Phistomefel duration:<30m year:2021 -completed tag:weekend "German whispers"`.
+/// A video is shown when it matches every term.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Query {
+    pub terms: Vec<Term>,
+}
+
+/// A condition in a query, negated when written with a leading `-`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Term {
+    pub negated: bool,
+    pub condition: Condition,
+}
+
+/// What a single query term checks. Text values are stored lowercase.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Condition {
+    /// A bare word or quoted phrase, found in the title or a decoded puzzle's text.
+    Text(String),
+
+    /// `title:` — found in the video title.
+    Title(String),
+
+    /// `setter:` — the author of a decoded puzzle, or mentioned in the description.
+    Setter(String),
+
+    /// `channel:` — found in the channel name.
+    Channel(String),
+
+    /// `tag:` — one of the user's tags, ignoring case.
+    Tag(String),
+
+    /// `notes:` — found in the user's notes.
+    Notes(String),
+
+    /// `duration:` — compares the video length in seconds. Unknown durations never match.
+    Duration(Comparison, u64),
+
+    /// `year:` — compares the year the video was published.
+    Year(Comparison, i32),
+
+    /// `completed` — the video has been marked as completed.
+    Completed,
+}
+
+/// How a `duration:` or `year:` value is compared, written as a prefix such as `<` or `>=`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    Less,
+    LessOrEqual,
+    Equal,
+    GreaterOrEqual,
+    Greater,
+}
+
+/// A reason the filter text couldn't be parsed, shown under the filter box.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum QueryError {
+    #[error("Missing closing quote")]
+    UnterminatedQuote,
+
+    #[error("`-` must be followed by a term")]
+    MissingTerm,
+
+    #[error("Unknown field `{0}:` (try {FIELDS})")]
+    UnknownField(String),
+
+    #[error("`{0}:` needs a value")]
+    MissingValue(String),
+
+    #[error("Invalid duration `{0}` (try 45m, 1h30m or <30m)")]
+    InvalidDuration(String),
+
+    #[error("Invalid year `{0}` (try 2021 or >=2020)")]
+    InvalidYear(String),
+}
+
+/// A video and the user's data about it, for matching against a query.
+pub struct VideoContext<'a> {
+    pub video: &'a CtcVideo,
+    pub channel_name: &'a str,
+    pub completed: bool,
+    pub tags: &'a [String],
+    pub notes: &'a str,
+
+    /// Puzzles decoded from the video's puzzle links.
+    pub puzzles: Vec<&'a PuzzleDescription>,
+}
+
+impl Query {
+    /// Parses the text of the filter box.
+    pub fn parse(input: &str) -> Result<Self, QueryError> {
+        let mut terms = Vec::new();
+        let mut rest = input.trim_start();
+        while !rest.is_empty() {
+            let (negated, term) = match rest.strip_prefix('-') {
+                Some(term) => (true, term),
+                None => (false, rest),
+            };
+            if term.is_empty() || term.starts_with(char::is_whitespace) {
+                return Err(QueryError::MissingTerm);
+            }
+
+            let (condition, remaining) = parse_condition(term)?;
+            terms.push(Term { negated, condition });
+            rest = remaining.trim_start();
+        }
+        Ok(Self { terms })
+    }
+
+    /// Returns whether the query has no terms and so matches every video.
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// Returns whether the video matches every term of the query.
+    pub fn matches(&self, context: &VideoContext) -> bool {
+        self.terms
+            .iter()
+            .all(|term| term.condition.matches(context) != term.negated)
+    }
+}
+
+impl Condition {
+    /// Returns whether the video meets the condition.
+    fn matches(&self, context: &VideoContext) -> bool {
+        let video = context.video;
+        match self {
+            Condition::Text(text) => {
+                contains(&video.title, text) || context.puzzles.iter().any(|puzzle| puzzle.matches_text(text))
+            }
+            Condition::Title(text) => contains(&video.title, text),
+            Condition::Setter(name) => {
+                context
+                    .puzzles
+                    .iter()
+                    .filter_map(|puzzle| puzzle.author.as_deref())
+                    .any(|author| contains(author, name))
+                    || contains(&video.description, name)
+            }
+            Condition::Channel(name) => contains(context.channel_name, name),
+            Condition::Tag(tag) => context.tags.iter().any(|t| t.to_lowercase() == *tag),
+            Condition::Notes(text) => contains(context.notes, text),
+            Condition::Duration(comparison, seconds) => {
+                !video.duration.is_unknown() && comparison.holds(*video.duration, *seconds)
+            }
+            Condition::Year(comparison, year) => chrono::DateTime::from_timestamp_millis(*video.date)
+                .is_some_and(|date| comparison.holds(date.year(), *year)),
+            Condition::Completed => context.completed,
+        }
+    }
+}
+
+impl Comparison {
+    /// Splits a comparison prefix such as `>=` off a value, defaulting to `Equal`.
+    fn split(value: &str) -> (Self, &str) {
+        let prefixes = [
+            ("<=", Self::LessOrEqual),
+            (">=", Self::GreaterOrEqual),
+            ("<", Self::Less),
+            (">", Self::Greater),
+            ("=", Self::Equal),
+        ];
+        prefixes
+            .into_iter()
+            .find_map(|(prefix, comparison)| Some((comparison, value.strip_prefix(prefix)?)))
+            .unwrap_or((Self::Equal, value))
+    }
+
+    /// Returns whether `value` compares to `target` this way.
+    fn holds<T: PartialOrd>(self, value: T, target: T) -> bool {
+        match self {
+            Self::Less => value < target,
+            Self::LessOrEqual => value <= target,
+            Self::Equal => value == target,
+            Self::GreaterOrEqual => value >= target,
+            Self::Greater => value > target,
+        }
+    }
+}
+
+/// Parses the term at the start of `input`, returning its condition and the rest of the input.
+fn parse_condition(input: &str) -> Result<(Condition, &str), QueryError> {
+    if let Some(quoted) = input.strip_prefix('"') {
+        let (phrase, rest) = split_quoted(quoted)?;
+        return Ok((Condition::Text(phrase.to_lowercase()), rest));
+    }
+
+    let end = input
+        .find(|c: char| c.is_whitespace() || c == ':' || c == '"')
+        .unwrap_or(input.len());
+    let (word, rest) = input.split_at(end);
+    let Some(value) = rest.strip_prefix(':') else {
+        let condition = if word.eq_ignore_ascii_case("completed") {
+            Condition::Completed
+        } else {
+            Condition::Text(word.to_lowercase())
+        };
+        return Ok((condition, rest));
+    };
+
+    let (value, rest) = match value.strip_prefix('"') {
+        Some(quoted) => split_quoted(quoted)?,
+        None => value.split_at(value.find(char::is_whitespace).unwrap_or(value.len())),
+    };
+    Ok((field_condition(word, value)?, rest))
+}
+
+/// Splits a quoted value (after its opening quote) at the closing quote.
+fn split_quoted(input: &str) -> Result<(&str, &str), QueryError> {
+    let end = input.find('"').ok_or(QueryError::UnterminatedQuote)?;
+    Ok((&input[..end], &input[end + 1..]))
+}
+
+/// Creates the condition for a `field:value` term.
+fn field_condition(field: &str, value: &str) -> Result<Condition, QueryError> {
+    let field = field.to_lowercase();
+    let text = value.trim().to_lowercase();
+    let text_condition: Option<fn(String) -> Condition> = match field.as_str() {
+        "title" => Some(Condition::Title),
+        "setter" => Some(Condition::Setter),
+        "channel" => Some(Condition::Channel),
+        "tag" => Some(Condition::Tag),
+        "notes" => Some(Condition::Notes),
+        "duration" | "year" => None,
+        _ => return Err(QueryError::UnknownField(field)),
+    };
+    if text.is_empty() {
+        return Err(QueryError::MissingValue(field));
+    }
+    if let Some(text_condition) = text_condition {
+        return Ok(text_condition(text));
+    }
+
+    let (comparison, amount) = Comparison::split(&text);
+    if field == "duration" {
+        parse_duration(amount)
+            .map(|seconds| Condition::Duration(comparison, seconds))
+            .ok_or_else(|| QueryError::InvalidDuration(value.to_string()))
+    } else {
+        amount
+            .parse()
+            .map(|year| Condition::Year(comparison, year))
+            .map_err(|_| QueryError::InvalidYear(value.to_string()))
+    }
+}
+
+/// Parses a duration such as `45m`, `1h30m` or `90s` into seconds. A bare number is minutes.
+///
+/// Returns `None` for durations too long to count in seconds.
+fn parse_duration(text: &str) -> Option<u64> {
+    if let Ok(minutes) = text.parse::<u64>() {
+        return minutes.checked_mul(60);
+    }
+    if text.is_empty() {
+        return None;
+    }
+
+    let mut seconds = 0;
+    let mut rest = text;
+    while !rest.is_empty() {
+        let digits = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+        let value = rest[..digits].parse::<u64>().ok()?;
+        let unit = match rest[digits..].chars().next()? {
+            'h' => 3600,
+            'm' => 60,
+            's' => 1,
+            _ => return None,
+        };
+        seconds = value.checked_mul(unit)?.checked_add(seconds)?;
+        rest = &rest[digits + 1..];
+    }
+    Some(seconds)
+}
+
+/// Returns whether `haystack` contains the lowercase `needle`, ignoring case.
+fn contains(haystack: &str, needle: &str) -> bool {
+    haystack.to_lowercase().contains(needle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::model::{VideoDuration, VideoId, VideoPublishDate};
+
+    #[test]
+    fn parses_fields_negation_and_phrases() {
+        let query =
+            Query::parse(r#"setter:Phistomefel duration:<30m year:2021 -completed tag:weekend "German whispers""#)
+                .unwrap();
+
+        let conditions = query
+            .terms
+            .iter()
+            .map(|term| (term.negated, term.condition.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            conditions,
+            [
+                (false, Condition::Setter("phistomefel".to_string())),
+                (false, Condition::Duration(Comparison::Less, 1800)),
+                (false, Condition::Year(Comparison::Equal, 2021)),
+                (true, Condition::Completed),
+                (false, Condition::Tag("weekend".to_string())),
+                (false, Condition::Text("german whispers".to_string())),
+            ]
+        );
+        assert_eq!(
+            Query::parse(r#"channel:"Cracking the Cryptic" duration:>=1h30m"#).unwrap().terms[1].condition,
+            Condition::Duration(Comparison::GreaterOrEqual, 5400)
+        );
+        assert!(Query::parse("   ").unwrap().is_empty());
+    }
+
+    #[test]
+    fn reports_syntax_errors() {
+        assert_eq!(Query::parse(r#""German whispers"#), Err(QueryError::UnterminatedQuote));
+        assert_eq!(Query::parse("miracle - completed"), Err(QueryError::MissingTerm));
+        assert_eq!(Query::parse("genre:killer"), Err(QueryError::UnknownField("genre".to_string())));
+        assert_eq!(Query::parse("tag: weekend"), Err(QueryError::MissingValue("tag".to_string())));
+        assert_eq!(Query::parse("duration:<3x"), Err(QueryError::InvalidDuration("<3x".to_string())));
+        assert_eq!(
+            Query::parse("duration:99999999999999999h"),
+            Err(QueryError::InvalidDuration("99999999999999999h".to_string()))
+        );
+        assert_eq!(
+            Query::parse("duration:999999999999999999"),
+            Err(QueryError::InvalidDuration("999999999999999999".to_string()))
+        );
+        assert_eq!(Query::parse("year:last"), Err(QueryError::InvalidYear("last".to_string())));
+    }
+
+    #[test]
+    fn matches_videos_and_user_data() {
+        let video = CtcVideo {
+            id: VideoId::new("id"),
+            title: "The Miracle Sudoku".to_string(),
+            description: "A puzzle by Mitchell Lee".to_string(),
+            // 2020-05-11
+            date: VideoPublishDate::new(1_589_155_200_000),
+            duration: VideoDuration::new(25 * 60),
+            extracted_links: Vec::new(),
+            chapters: Vec::new(),
+            channel_id: String::new(),
+        };
+        let tags = ["Weekend".to_string()];
+        let context = VideoContext {
+            video: &video,
+            channel_name: "Cracking the Cryptic",
+            completed: true,
+            tags: &tags,
+            notes: "Knight's move!",
+            puzzles: Vec::new(),
+        };
+        let matches = |query: &str| Query::parse(query).unwrap().matches(&context);
+
+        assert!(matches("miracle setter:mitchell duration:<30m year:2020 tag:weekend completed"));
+        assert!(matches(r#"channel:cryptic notes:"knight's" year:<=2020"#));
+        assert!(!matches("-completed"));
+        assert!(!matches("year:2021"));
+        assert!(!matches("duration:>1h"));
+        assert!(!matches(r#""killer sudoku""#));
+    }
+}
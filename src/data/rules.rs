@@ -0,0 +1,223 @@
+use regex::Regex;
+use sqlx::prelude::FromRow;
+
+use super::model::CtcVideo;
+
+/// What a filter rule does to the videos it matches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuleAction {
+    /// Hide matching videos.
+    Exclude,
+
+    /// Show matching videos even if an exclude rule matches them.
+    Include,
+}
+
+/// How a filter rule's pattern is matched.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchKind {
+    /// Case-sensitive substring match.
+    Substring,
+
+    /// Regular expression match.
+    Regex,
+}
+
+/// Which part of the video a filter rule looks at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuleField {
+    Title,
+    Description,
+}
+
+/// A user-editable rule deciding whether a video is shown in the grid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilterRule {
+    pub action: RuleAction,
+    pub match_kind: MatchKind,
+    pub field: RuleField,
+    pub pattern: String,
+    pub enabled: bool,
+}
+impl FilterRule {
+    /// Creates an enabled rule excluding videos whose title contains `pattern`.
+    pub fn exclude_title(pattern: &str) -> Self {
+        Self {
+            action: RuleAction::Exclude,
+            match_kind: MatchKind::Substring,
+            field: RuleField::Title,
+            pattern: pattern.to_string(),
+            enabled: true,
+        }
+    }
+
+    /// Rules applied before the user has edited anything: skip non-sudoku word games.
+    pub fn defaults() -> Vec<Self> {
+        ["Wordle", "Plusword", "Quordle"]
+            .into_iter()
+            .map(Self::exclude_title)
+            .collect()
+    }
+
+    /// Compiles the rule's pattern if it is a regular expression.
+    pub fn compile(&self) -> Result<Option<Regex>, regex::Error> {
+        match self.match_kind {
+            MatchKind::Substring => Ok(None),
+            MatchKind::Regex => Regex::new(&self.pattern).map(Some),
+        }
+    }
+}
+
+/// A set of compiled filter rules.
+///
+/// A video is hidden when any enabled exclude rule matches it, unless an enabled include rule
+/// also matches it. Rules with invalid regular expressions are ignored.
+#[derive(Debug, Default, Clone)]
+pub struct RuleSet {
+    rules: Vec<(FilterRule, Option<Regex>)>,
+}
+impl RuleSet {
+    /// Creates a rule set from the given rules.
+    pub fn new(rules: Vec<FilterRule>) -> Self {
+        let rules = rules
+            .into_iter()
+            .filter(|rule| rule.enabled && !rule.pattern.is_empty())
+            .filter_map(|rule| rule.compile().ok().map(|regex| (rule, regex)))
+            .collect();
+        Self { rules }
+    }
+
+    /// Returns whether the rules hide the given video.
+    pub fn is_excluded(&self, video: &CtcVideo) -> bool {
+        let matching = |action: RuleAction| {
+            self.rules
+                .iter()
+                .filter(|(rule, _)| rule.action == action)
+                .any(|(rule, regex)| Self::matches(rule, regex.as_ref(), video))
+        };
+        matching(RuleAction::Exclude) && !matching(RuleAction::Include)
+    }
+
+    fn matches(rule: &FilterRule, regex: Option<&Regex>, video: &CtcVideo) -> bool {
+        let text = match rule.field {
+            RuleField::Title => &video.title,
+            RuleField::Description => &video.description,
+        };
+        match regex {
+            Some(regex) => regex.is_match(text),
+            None => text.contains(&rule.pattern),
+        }
+    }
+}
+
+/// Represents a row in the filter rules table.
+#[derive(FromRow)]
+pub struct FilterRuleRow {
+    pub action: String,
+    pub match_kind: String,
+    pub field: String,
+    pub pattern: String,
+    pub enabled: bool,
+}
+impl From<FilterRuleRow> for FilterRule {
+    fn from(row: FilterRuleRow) -> Self {
+        Self {
+            action: match row.action.as_str() {
+                "include" => RuleAction::Include,
+                _ => RuleAction::Exclude,
+            },
+            match_kind: match row.match_kind.as_str() {
+                "regex" => MatchKind::Regex,
+                _ => MatchKind::Substring,
+            },
+            field: match row.field.as_str() {
+                "description" => RuleField::Description,
+                _ => RuleField::Title,
+            },
+            pattern: row.pattern,
+            enabled: row.enabled,
+        }
+    }
+}
+impl RuleAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Exclude => "exclude",
+            Self::Include => "include",
+        }
+    }
+}
+impl MatchKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Substring => "substring",
+            Self::Regex => "regex",
+        }
+    }
+}
+impl RuleField {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Title => "title",
+            Self::Description => "description",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::model::{VideoDuration, VideoId, VideoPublishDate};
+
+    fn video(title: &str, description: &str) -> CtcVideo {
+        CtcVideo {
+            id: VideoId::new("id"),
+            title: title.to_string(),
+            description: description.to_string(),
+            date: VideoPublishDate::new(0),
+            duration: VideoDuration::new(60),
+            extracted_links: Vec::new(),
+            chapters: Vec::new(),
+            channel_id: String::new(),
+        }
+    }
+
+    #[test]
+    fn default_rules_hide_word_games() {
+        let rules = RuleSet::new(FilterRule::defaults());
+
+        assert!(rules.is_excluded(&video("Wordle #995", "")));
+        assert!(rules.is_excluded(&video("Plusword Friday", "")));
+        assert!(!rules.is_excluded(&video("A Beautiful Killer Sudoku", "")));
+    }
+
+    #[test]
+    fn include_rules_override_exclude_rules() {
+        let mut rules = FilterRule::defaults();
+        rules.push(FilterRule {
+            action: RuleAction::Include,
+            match_kind: MatchKind::Regex,
+            field: RuleField::Description,
+            pattern: r"sudokupad\.app".to_string(),
+            enabled: true,
+        });
+        let rules = RuleSet::new(rules);
+
+        assert!(!rules.is_excluded(&video("Wordle meets Sudoku", "https://sudokupad.app/x")));
+        assert!(rules.is_excluded(&video("Wordle #995", "")));
+    }
+
+    #[test]
+    fn disabled_and_invalid_rules_are_ignored() {
+        let mut disabled = FilterRule::exclude_title("Wordle");
+        disabled.enabled = false;
+        let invalid = FilterRule {
+            match_kind: MatchKind::Regex,
+            pattern: "(".to_string(),
+            ..FilterRule::exclude_title("")
+        };
+        let rules = RuleSet::new(vec![disabled, invalid]);
+
+        assert!(!rules.is_excluded(&video("Wordle #995", "")));
+    }
+}
@@ -0,0 +1,91 @@
+use sqlx::prelude::FromRow;
+
+use super::model::VideoId;
+
+/// Marks the start of a matching word in a search snippet.
+pub const HIGHLIGHT_START: &str = "\u{2}";
+
+/// Marks the end of a matching word in a search snippet.
+pub const HIGHLIGHT_END: &str = "\u{3}";
+
+/// A video matching a full-text search, with an excerpt of its description.
+#[derive(Debug, Clone, PartialEq, Eq, FromRow)]
+pub struct SearchHit {
+    #[sqlx(rename = "id")]
+    pub video_id: VideoId,
+
+    /// Excerpt of the description around the matching words, which are wrapped in
+    /// `HIGHLIGHT_START` and `HIGHLIGHT_END`.
+    pub snippet: String,
+}
+impl SearchHit {
+    /// Splits the snippet into pieces of text, noting which ones matched the search.
+    pub fn highlights(&self) -> Vec<(&str, bool)> {
+        let mut pieces = Vec::new();
+        let mut rest = self.snippet.as_str();
+        while let Some(start) = rest.find(HIGHLIGHT_START) {
+            pieces.push((&rest[..start], false));
+            rest = &rest[start + HIGHLIGHT_START.len()..];
+            let end = rest.find(HIGHLIGHT_END).unwrap_or(rest.len());
+            pieces.push((&rest[..end], true));
+            rest = rest[end..].strip_prefix(HIGHLIGHT_END).unwrap_or_default();
+        }
+        pieces.push((rest, false));
+        pieces.retain(|(text, _)| !text.is_empty());
+        pieces
+    }
+}
+
+/// Turns text typed into the search box into an FTS5 query matching every word and "quoted phrase".
+/// The last word matches as a prefix so results show up while typing.
+///
+/// Returns `None` if there is nothing to search for.
+pub fn fts_query(text: &str) -> Option<String> {
+    let mut terms = Vec::new();
+    for (index, part) in text.split('"').enumerate() {
+        if index % 2 == 1 {
+            // Inside quotes: match the phrase as written
+            if !part.trim().is_empty() {
+                terms.push(format!("\"{}\"", part.trim()));
+            }
+        } else {
+            terms.extend(
+                part.split(|c: char| !c.is_alphanumeric() && c != '\'')
+                    .filter(|word| !word.is_empty())
+                    .map(|word| format!("\"{word}\"")),
+            );
+        }
+    }
+
+    let typing_last_word = !text.ends_with(|c: char| c.is_whitespace() || c == '"');
+    let last = terms.last_mut()?;
+    if typing_last_word {
+        last.push('*');
+    }
+    Some(terms.join(" "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_fts_queries_from_search_text() {
+        assert_eq!(fts_query("renban").as_deref(), Some("\"renban\"*"));
+        assert_eq!(
+            fts_query("\"Dutch whispers\" killer-cage ").as_deref(),
+            Some("\"Dutch whispers\" \"killer\" \"cage\"")
+        );
+        assert_eq!(fts_query(" (\" "), None);
+    }
+
+    #[test]
+    fn splits_snippets_into_highlights() {
+        let hit = SearchHit {
+            video_id: VideoId::new("id"),
+            snippet: format!("…a {HIGHLIGHT_START}renban{HIGHLIGHT_END} line"),
+        };
+
+        assert_eq!(hit.highlights(), [("…a ", false), ("renban", true), (" line", false)]);
+    }
+}
@@ -0,0 +1,123 @@
+//! Optional seed catalogues: JSON lists of videos loaded into an empty database on first
+//! launch, or imported and exported from the settings. None is bundled with the app.
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    error::DataError,
+    model::{CtcVideo, CTC_CHANNEL_ID},
+};
+
+/// A video entry in a seed catalogue file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SeedVideo {
+    /// YouTube video ID.
+    pub id: String,
+
+    /// Title of the video.
+    pub title: String,
+
+    /// Description of the video.
+    #[serde(default)]
+    pub description: String,
+
+    /// Publish date as a Unix timestamp in milliseconds.
+    pub date: i64,
+
+    /// Duration in seconds, or zero if unknown.
+    #[serde(default)]
+    pub duration: u64,
+
+    /// ID of the uploading channel; catalogues without one are assumed to be Cracking the Cryptic.
+    #[serde(default = "default_channel_id")]
+    pub channel_id: String,
+}
+
+fn default_channel_id() -> String {
+    CTC_CHANNEL_ID.to_string()
+}
+impl From<&CtcVideo> for SeedVideo {
+    fn from(video: &CtcVideo) -> Self {
+        Self {
+            id: video.id.to_string(),
+            title: video.title.clone(),
+            description: video.description.clone(),
+            date: *video.date,
+            duration: *video.duration,
+            channel_id: video.channel_id.clone(),
+        }
+    }
+}
+
+/// Parses a seed catalogue from JSON.
+pub fn parse_catalogue(json: &str) -> Result<Vec<SeedVideo>, DataError> {
+    Ok(serde_json::from_str(json)?)
+}
+
+/// Reads a seed catalogue file from disk.
+pub fn read_catalogue(path: &Path) -> Result<Vec<SeedVideo>, DataError> {
+    let json = std::fs::read_to_string(path)
+        .map_err(|e| DataError::Io(format!("Failed to read {}: {e}", path.display())))?;
+    parse_catalogue(&json)
+}
+
+/// Writes videos to a seed catalogue file, newest first.
+pub fn write_catalogue(path: &Path, videos: &[CtcVideo]) -> Result<(), DataError> {
+    write_seed_videos(path, videos.iter().map(SeedVideo::from).collect())
+}
+
+/// Writes catalogue entries to a seed catalogue file, newest first.
+pub fn write_seed_videos(path: &Path, mut entries: Vec<SeedVideo>) -> Result<(), DataError> {
+    entries.sort_by_key(|entry| std::cmp::Reverse(entry.date));
+    let json = serde_json::to_string_pretty(&entries)?;
+    std::fs::write(path, json)
+        .map_err(|e| DataError::Io(format!("Failed to write {}: {e}", path.display())))
+}
+
+/// Returns the catalogue to load on first launch: the file at `local_path` if it exists,
+/// otherwise nothing, leaving the sync to fetch the whole channel history.
+pub fn first_run_catalogue(local_path: &Path) -> Result<Vec<SeedVideo>, DataError> {
+    if local_path.exists() {
+        read_catalogue(local_path)
+    } else {
+        Ok(Vec::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_the_first_run_catalogue_if_present() {
+        let dir = std::env::temp_dir().join(format!("ctc-tracker-seed-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("seed_catalogue.json");
+        assert!(first_run_catalogue(&path).unwrap().is_empty());
+
+        let entry = SeedVideo {
+            id: "abc".to_string(),
+            title: "Title".to_string(),
+            description: String::new(),
+            date: 1,
+            duration: 60,
+            channel_id: CTC_CHANNEL_ID.to_string(),
+        };
+        write_seed_videos(&path, vec![entry.clone()]).unwrap();
+        assert_eq!(first_run_catalogue(&path).unwrap(), [entry]);
+        assert!(matches!(read_catalogue(&dir.join("missing.json")), Err(DataError::Io(_))));
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn missing_optional_fields_default() {
+        let catalogue = parse_catalogue(r#"[{"id": "abc", "title": "Title", "date": 1}]"#).unwrap();
+
+        assert_eq!(catalogue[0].description, "");
+        assert_eq!(catalogue[0].duration, 0);
+        assert_eq!(catalogue[0].channel_id, CTC_CHANNEL_ID);
+    }
+}
@@ -0,0 +1,108 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+/// Something that can be done with a keyboard shortcut.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ShortcutAction {
+    FocusFilter,
+    NextVideo,
+    PreviousVideo,
+    ToggleCompleted,
+    OpenVideo,
+    OpenPuzzle,
+    Refresh,
+    ShowHelp,
+}
+impl ShortcutAction {
+    /// Every action, in the order they're listed in the help and settings.
+    pub const ALL: [Self; 8] = [
+        Self::FocusFilter,
+        Self::NextVideo,
+        Self::PreviousVideo,
+        Self::ToggleCompleted,
+        Self::OpenVideo,
+        Self::OpenPuzzle,
+        Self::Refresh,
+        Self::ShowHelp,
+    ];
+
+    /// Returns what the action does, for the help overlay and settings.
+    pub fn description(self) -> &'static str {
+        match self {
+            Self::FocusFilter => "Focus the filter box",
+            Self::NextVideo => "Select the next video",
+            Self::PreviousVideo => "Select the previous video",
+            Self::ToggleCompleted => "Mark the selected video as completed or not",
+            Self::OpenVideo => "Open the selected video",
+            Self::OpenPuzzle => "Open the selected video's puzzle",
+            Self::Refresh => "Refresh videos",
+            Self::ShowHelp => "Show keyboard shortcuts",
+        }
+    }
+
+    /// Returns the name of the key the action is bound to by default.
+    pub fn default_key(self) -> &'static str {
+        match self {
+            Self::FocusFilter => "/",
+            Self::NextVideo => "J",
+            Self::PreviousVideo => "K",
+            Self::ToggleCompleted => "Space",
+            Self::OpenVideo => "O",
+            Self::OpenPuzzle => "P",
+            Self::Refresh => "R",
+            Self::ShowHelp => "?",
+        }
+    }
+}
+
+/// The keys bound to each shortcut action, by key name (such as `J`, `Space` or `/`).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyBindings(HashMap<ShortcutAction, String>);
+impl KeyBindings {
+    /// Returns the name of the key bound to the action, falling back to its default.
+    pub fn key(&self, action: ShortcutAction) -> &str {
+        self.0.get(&action).map_or(action.default_key(), String::as_str)
+    }
+
+    /// Binds the action to the named key.
+    pub fn set(&mut self, action: ShortcutAction, key: &str) {
+        if key == action.default_key() {
+            self.0.remove(&action);
+        } else {
+            self.0.insert(action, key.to_string());
+        }
+    }
+
+    /// Returns the names of keys bound to more than one action.
+    pub fn conflicts(&self) -> Vec<&str> {
+        let mut keys = ShortcutAction::ALL.map(|action| self.key(action)).to_vec();
+        keys.sort();
+        let mut conflicts = keys.windows(2).filter(|pair| pair[0] == pair[1]).map(|pair| pair[0]).collect::<Vec<_>>();
+        conflicts.dedup();
+        conflicts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn falls_back_to_default_keys_and_finds_conflicts() {
+        let mut bindings = KeyBindings::default();
+        assert_eq!(bindings.key(ShortcutAction::NextVideo), "J");
+        assert!(bindings.conflicts().is_empty());
+
+        bindings.set(ShortcutAction::NextVideo, "ArrowDown");
+        bindings.set(ShortcutAction::Refresh, "O");
+        assert_eq!(bindings.key(ShortcutAction::NextVideo), "ArrowDown");
+        assert_eq!(bindings.conflicts(), ["O"]);
+
+        // Saved bindings only list the keys that were changed
+        bindings.set(ShortcutAction::NextVideo, "J");
+        let json = serde_json::to_string(&bindings).unwrap();
+        assert_eq!(json, r#"{"Refresh":"O"}"#);
+        assert_eq!(serde_json::from_str::<KeyBindings>(&json).unwrap(), bindings);
+    }
+}
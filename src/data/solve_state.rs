@@ -0,0 +1,282 @@
+use serde::{Deserialize, Serialize};
+
+use super::puzzle::{Cell, PuzzleDescription};
+
+/// Most undo steps kept for a puzzle.
+const MAX_UNDO_STEPS: usize = 200;
+
+/// What a key press or digit button does to the selected cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EntryMode {
+    /// Enter a digit.
+    #[default]
+    Digit,
+
+    /// Toggle a corner pencil mark.
+    Corner,
+
+    /// Toggle a centre pencil mark.
+    Centre,
+
+    /// Toggle a colour (the "digit" picks the colour).
+    Colour,
+}
+
+/// Everything the solver has entered into a single cell.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CellState {
+    pub digit: Option<u8>,
+    pub corner_marks: Vec<u8>,
+    pub centre_marks: Vec<u8>,
+    pub colours: Vec<u8>,
+}
+impl CellState {
+    /// Returns the pencil marks or colours edited in the given mode.
+    fn marks(&self, mode: EntryMode) -> &[u8] {
+        match mode {
+            EntryMode::Corner => &self.corner_marks,
+            EntryMode::Centre => &self.centre_marks,
+            EntryMode::Digit | EntryMode::Colour => &self.colours,
+        }
+    }
+
+    fn marks_mut(&mut self, mode: EntryMode) -> &mut Vec<u8> {
+        match mode {
+            EntryMode::Corner => &mut self.corner_marks,
+            EntryMode::Centre => &mut self.centre_marks,
+            EntryMode::Digit | EntryMode::Colour => &mut self.colours,
+        }
+    }
+}
+
+/// In-progress solve of a puzzle, saved per video.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SolveState {
+    size: usize,
+    cells: Vec<CellState>,
+    #[serde(skip)]
+    undo_stack: Vec<Vec<CellState>>,
+}
+impl SolveState {
+    /// Creates an empty solve for a grid of the given size.
+    pub fn new(size: usize) -> Self {
+        Self {
+            size,
+            cells: vec![CellState::default(); size * size],
+            undo_stack: Vec::new(),
+        }
+    }
+
+    /// Returns whether this state belongs to a grid of the given size.
+    pub fn fits(&self, size: usize) -> bool {
+        self.size == size && self.cells.len() == size * size
+    }
+
+    /// Returns what has been entered into a cell.
+    pub fn cell(&self, (row, col): Cell) -> &CellState {
+        &self.cells[row * self.size + col]
+    }
+
+    /// Returns whether there is anything to undo.
+    pub fn can_undo(&self) -> bool {
+        !self.undo_stack.is_empty()
+    }
+
+    /// Applies a digit to the selected cells according to the entry mode.
+    ///
+    /// Cells with givens only accept colours. If every selected cell already has the mark,
+    /// it's removed from all of them; otherwise it's added to all of them.
+    pub fn enter(&mut self, puzzle: &PuzzleDescription, cells: &[Cell], mode: EntryMode, digit: u8) {
+        let editable = cells
+            .iter()
+            .copied()
+            .filter(|cell| mode == EntryMode::Colour || puzzle.given(*cell).is_none())
+            .map(|(row, col)| row * self.size + col)
+            .filter(|index| *index < self.cells.len())
+            .collect::<Vec<_>>();
+        if editable.is_empty() {
+            return;
+        }
+
+        self.push_undo();
+        if mode == EntryMode::Digit {
+            let clear = editable.iter().all(|index| self.cells[*index].digit == Some(digit));
+            for index in editable {
+                self.cells[index].digit = (!clear).then_some(digit);
+            }
+        } else {
+            let remove = editable
+                .iter()
+                .all(|index| self.cells[*index].marks(mode).contains(&digit));
+            for index in editable {
+                let marks = self.cells[index].marks_mut(mode);
+                marks.retain(|mark| *mark != digit);
+                if !remove {
+                    marks.push(digit);
+                    marks.sort_unstable();
+                }
+            }
+        }
+    }
+
+    /// Clears the selected cells: digits first, then pencil marks, then colours.
+    pub fn clear(&mut self, puzzle: &PuzzleDescription, cells: &[Cell]) {
+        let indices = cells
+            .iter()
+            .map(|(row, col)| row * self.size + col)
+            .filter(|index| *index < self.cells.len())
+            .collect::<Vec<_>>();
+        if indices.iter().all(|index| self.cells[*index] == CellState::default()) {
+            return;
+        }
+
+        self.push_undo();
+        let editable = |cell: &Cell| puzzle.given(*cell).is_none();
+        let has_digit = cells
+            .iter()
+            .filter(|cell| editable(cell))
+            .any(|cell| self.cell(*cell).digit.is_some());
+        let has_marks = cells.iter().filter(|cell| editable(cell)).any(|cell| {
+            let state = self.cell(*cell);
+            !state.corner_marks.is_empty() || !state.centre_marks.is_empty()
+        });
+        for index in indices {
+            let cell = &mut self.cells[index];
+            if has_digit {
+                cell.digit = None;
+            } else if has_marks {
+                cell.corner_marks.clear();
+                cell.centre_marks.clear();
+            } else {
+                cell.colours.clear();
+            }
+        }
+    }
+
+    /// Reverts the last change.
+    pub fn undo(&mut self) {
+        if let Some(cells) = self.undo_stack.pop() {
+            self.cells = cells;
+        }
+    }
+
+    /// Returns the digit in a cell, whether given or entered.
+    pub fn digit(&self, puzzle: &PuzzleDescription, cell: Cell) -> Option<u8> {
+        puzzle.given(cell).or(self.cell(cell).digit)
+    }
+
+    /// Returns the digits in the grid row by row, with `0` for empty cells.
+    pub fn grid(&self, puzzle: &PuzzleDescription) -> Vec<u8> {
+        (0..self.size * self.size)
+            .map(|index| self.digit(puzzle, (index / self.size, index % self.size)).unwrap_or(0))
+            .collect()
+    }
+
+    /// Returns whether every cell is filled and the grid obeys the puzzle's rules.
+    pub fn is_solved(&self, puzzle: &PuzzleDescription) -> bool {
+        let grid = self.grid(puzzle);
+        !grid.contains(&0) && puzzle.is_valid_solution(&grid)
+    }
+
+    /// Remembers the current cells so the next change can be undone.
+    fn push_undo(&mut self) {
+        self.undo_stack.push(self.cells.clone());
+        if self.undo_stack.len() > MAX_UNDO_STEPS {
+            self.undo_stack.remove(0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOLUTION: [[u8; 4]; 4] = [[1, 2, 3, 4], [3, 4, 1, 2], [2, 1, 4, 3], [4, 3, 2, 1]];
+
+    fn puzzle() -> PuzzleDescription {
+        PuzzleDescription {
+            size: 4,
+            givens: vec![((0, 0), 1), ((1, 2), 1), ((2, 1), 1), ((3, 3), 1)],
+            ..PuzzleDescription::default()
+        }
+    }
+
+    #[test]
+    fn toggles_digits_and_pencil_marks() {
+        let puzzle = puzzle();
+        let mut state = SolveState::new(4);
+
+        state.enter(&puzzle, &[(0, 1)], EntryMode::Digit, 2);
+        state.enter(&puzzle, &[(0, 2), (0, 3)], EntryMode::Corner, 3);
+        state.enter(&puzzle, &[(0, 2), (0, 3)], EntryMode::Corner, 4);
+        state.enter(&puzzle, &[(0, 3)], EntryMode::Corner, 3);
+        state.enter(&puzzle, &[(0, 0)], EntryMode::Digit, 4);
+
+        assert_eq!(state.cell((0, 1)).digit, Some(2));
+        assert_eq!(state.cell((0, 2)).corner_marks, [3, 4]);
+        assert_eq!(state.cell((0, 3)).corner_marks, [4]);
+        assert_eq!(state.digit(&puzzle, (0, 0)), Some(1), "givens can't be overwritten");
+
+        state.undo();
+        assert_eq!(state.cell((0, 3)).corner_marks, [3, 4]);
+    }
+
+    #[test]
+    fn clears_digits_before_marks_and_colours() {
+        let puzzle = puzzle();
+        let mut state = SolveState::new(4);
+        state.enter(&puzzle, &[(1, 1)], EntryMode::Centre, 2);
+        state.enter(&puzzle, &[(1, 1)], EntryMode::Colour, 5);
+        state.enter(&puzzle, &[(1, 1)], EntryMode::Digit, 4);
+
+        state.clear(&puzzle, &[(1, 1)]);
+        assert_eq!(state.cell((1, 1)).digit, None);
+        assert_eq!(state.cell((1, 1)).centre_marks, [2]);
+
+        state.clear(&puzzle, &[(1, 1)]);
+        state.clear(&puzzle, &[(1, 1)]);
+        assert_eq!(*state.cell((1, 1)), CellState::default());
+    }
+
+    #[test]
+    fn detects_a_solved_grid() {
+        let puzzle = puzzle();
+        let mut state = SolveState::new(4);
+        for (row, digits) in SOLUTION.iter().enumerate() {
+            assert!(!state.is_solved(&puzzle));
+            for (col, digit) in digits.iter().enumerate() {
+                state.enter(&puzzle, &[(row, col)], EntryMode::Digit, *digit);
+            }
+        }
+        assert!(state.is_solved(&puzzle));
+
+        state.enter(&puzzle, &[(3, 2)], EntryMode::Digit, 4);
+        assert!(!state.is_solved(&puzzle));
+    }
+
+    #[test]
+    fn ignores_givens_that_do_not_fit_the_grid() {
+        let mut puzzle = puzzle();
+        puzzle.givens.extend([((0, 1), 9), ((0, 2), 0), ((4, 0), 1)]);
+        let mut state = SolveState::new(4);
+        for (row, digits) in SOLUTION.iter().enumerate() {
+            for (col, digit) in digits.iter().enumerate() {
+                state.enter(&puzzle, &[(row, col)], EntryMode::Digit, *digit);
+            }
+        }
+        assert_eq!(state.digit(&puzzle, (0, 1)), Some(2));
+        assert!(state.is_solved(&puzzle));
+    }
+
+    #[test]
+    fn round_trips_through_json() {
+        let puzzle = puzzle();
+        let mut state = SolveState::new(4);
+        state.enter(&puzzle, &[(2, 2)], EntryMode::Digit, 4);
+
+        let restored = serde_json::from_str::<SolveState>(&serde_json::to_string(&state).unwrap()).unwrap();
+
+        assert_eq!(restored.cell((2, 2)).digit, Some(4));
+        assert!(!restored.can_undo());
+    }
+}
@@ -0,0 +1,637 @@
+//! Solving and hints for decoded puzzles: classic sudoku rules plus killer cages and
+//! thermometers. Other constraints are ignored, so puzzles using them may appear to have
+//! several solutions.
+
+use std::{
+    fmt::Display,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use super::puzzle::{Cell, PuzzleDescription};
+
+/// Largest grid the solver handles. Candidates are bitmasks and cage sums are found by trying
+/// every combination of digits, which gets impractical for bigger grids.
+const MAX_SIZE: usize = 9;
+
+/// Whether a puzzle has exactly one solution.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Uniqueness {
+    /// The givens contradict the rules.
+    NoSolution,
+
+    /// Exactly one solution, row by row.
+    Unique(Vec<u8>),
+
+    /// More than one solution.
+    Multiple,
+
+    /// The grid is too big for the solver, or has givens that don't fit in it.
+    Unsupported,
+}
+
+/// Result of checking a partly or fully entered grid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Check {
+    /// Every cell is filled and the grid obeys the rules.
+    Solved,
+
+    /// Nothing is wrong so far, but some cells are empty.
+    Incomplete,
+
+    /// These cells break a rule or don't match the unique solution.
+    Mistakes(Vec<Cell>),
+}
+
+/// A row, column or region of the grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Unit {
+    Row(usize),
+    Column(usize),
+    Region(usize),
+}
+impl Display for Unit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Row(row) => write!(f, "row {}", row + 1),
+            Self::Column(col) => write!(f, "column {}", col + 1),
+            Self::Region(region) => write!(f, "region {}", region + 1),
+        }
+    }
+}
+
+/// The reasoning behind a hint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Technique {
+    /// An entered digit doesn't match the solution.
+    Mistake,
+
+    /// Only one digit fits in the cell.
+    NakedSingle,
+
+    /// The digit fits in only one cell of the unit.
+    HiddenSingle(Unit),
+
+    /// Only one digit fits once impossible killer cage combinations are ruled out.
+    CageCombinations,
+
+    /// Only one digit fits once the thermometers' ordering is taken into account.
+    Thermometer,
+
+    /// No simple deduction was found, so the answer for the most constrained cell is revealed.
+    Solution,
+}
+
+/// The next step of a solve, revealed a little at a time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hint {
+    pub cell: Cell,
+    pub digit: u8,
+    pub technique: Technique,
+    region: usize,
+}
+impl Hint {
+    /// Most detailed level of [`Hint::describe`].
+    pub const MAX_LEVEL: usize = 2;
+
+    /// Describes the hint: level 0 points at an area of the grid, level 1 explains the
+    /// deduction and level 2 gives the answer.
+    pub fn describe(&self, level: usize) -> String {
+        let cell = format!("r{}c{}", self.cell.0 + 1, self.cell.1 + 1);
+        let area = match self.technique {
+            Technique::HiddenSingle(unit) => unit,
+            _ => Unit::Region(self.region),
+        };
+        match (level, self.technique) {
+            (0, Technique::Mistake) => format!("There's a mistake in {area}."),
+            (0, _) => format!("Look at {area}."),
+            (1, Technique::Mistake) => format!("{cell} is wrong."),
+            (1, Technique::NakedSingle) => format!("Only one digit fits in {cell}."),
+            (1, Technique::HiddenSingle(unit)) => {
+                format!("There's only one place for a {} in {unit}.", self.digit)
+            }
+            (1, Technique::CageCombinations) => {
+                format!("The killer cage combinations leave only one option for {cell}.")
+            }
+            (1, Technique::Thermometer) => {
+                format!("The thermometers leave only one option for {cell}.")
+            }
+            (1, Technique::Solution) => {
+                format!("No simple deduction found; {cell} is the most constrained cell.")
+            }
+            (_, Technique::Mistake) => format!("{cell} should be {}.", self.digit),
+            _ => format!("{cell} is {}.", self.digit),
+        }
+    }
+}
+
+/// Solver for a single puzzle. Grids are passed row by row with `0` for empty cells.
+pub struct Solver<'a> {
+    puzzle: &'a PuzzleDescription,
+    size: usize,
+    /// Given digits by cell index, leaving out any outside the grid or its digits.
+    givens: Vec<(usize, u8)>,
+    malformed_givens: bool,
+    units: Vec<(Unit, Vec<usize>)>,
+    peers: Vec<Vec<usize>>,
+    regions: Vec<usize>,
+    cages: Vec<(Vec<usize>, Option<u32>)>,
+    thermometers: Vec<Vec<usize>>,
+}
+impl<'a> Solver<'a> {
+    /// Creates a solver for the given puzzle.
+    pub fn new(puzzle: &'a PuzzleDescription) -> Self {
+        let size = puzzle.size;
+        let index = |(row, col): Cell| row * size + col;
+        let in_grid = |cells: &[Cell]| cells.iter().all(|(row, col)| *row < size && *col < size);
+
+        let givens = puzzle
+            .givens
+            .iter()
+            .filter(|(cell, digit)| in_grid(&[*cell]) && (1..=size).contains(&(*digit as usize)))
+            .map(|(cell, digit)| (index(*cell), *digit))
+            .collect::<Vec<_>>();
+        let malformed_givens = givens.len() != puzzle.givens.len();
+
+        let mut units = Vec::new();
+        for line in 0..size {
+            units.push((Unit::Row(line), (0..size).map(|col| index((line, col))).collect()));
+            units.push((Unit::Column(line), (0..size).map(|row| index((row, line))).collect()));
+        }
+        let mut regions = vec![0; size * size];
+        for (region, cells) in puzzle.regions_or_boxes().iter().enumerate() {
+            if in_grid(cells) {
+                cells.iter().for_each(|cell| regions[index(*cell)] = region);
+                units.push((Unit::Region(region), cells.iter().copied().map(index).collect()));
+            }
+        }
+
+        let cages = puzzle
+            .cages
+            .iter()
+            .filter(|cage| in_grid(&cage.cells))
+            .map(|cage| (cage.cells.iter().copied().map(index).collect(), cage.sum))
+            .collect::<Vec<(Vec<usize>, Option<u32>)>>();
+        let thermometers = puzzle
+            .thermometers
+            .iter()
+            .filter(|thermometer| in_grid(thermometer))
+            .map(|thermometer| thermometer.iter().copied().map(index).collect())
+            .collect();
+
+        // Cells that can't share a digit: the same row, column, region or cage
+        let mut peers = vec![Vec::new(); size * size];
+        let groups = units.iter().map(|(_, cells)| cells).chain(cages.iter().map(|(cells, _)| cells));
+        for cells in groups {
+            for &cell in cells {
+                peers[cell].extend(cells.iter().copied().filter(|peer| *peer != cell));
+            }
+        }
+        for cell_peers in &mut peers {
+            cell_peers.sort_unstable();
+            cell_peers.dedup();
+        }
+
+        Self {
+            puzzle,
+            size,
+            givens,
+            malformed_givens,
+            units,
+            peers,
+            regions,
+            cages,
+            thermometers,
+        }
+    }
+
+    /// Returns whether the puzzle has no solution, exactly one, or several.
+    ///
+    /// Returns `None` if `cancelled` is set before the search finishes.
+    pub fn uniqueness(&self, cancelled: &AtomicBool) -> Option<Uniqueness> {
+        if !self.is_supported() {
+            return Some(Uniqueness::Unsupported);
+        }
+
+        let mut grid = self.givens();
+        let mut solutions = Vec::new();
+        self.search(&mut grid, &mut solutions, 2, cancelled);
+        if cancelled.load(Ordering::Relaxed) {
+            return None;
+        }
+        Some(match solutions.len() {
+            0 => Uniqueness::NoSolution,
+            1 => Uniqueness::Unique(solutions.remove(0)),
+            _ => Uniqueness::Multiple,
+        })
+    }
+
+    /// Returns whether the grid is small enough to solve and give hints for, and all its givens
+    /// fit in it.
+    fn is_supported(&self) -> bool {
+        (1..=MAX_SIZE).contains(&self.size) && !self.malformed_givens
+    }
+
+    /// Checks an entered grid against the rules and, if known, the unique solution.
+    pub fn check(&self, grid: &[u8], solution: Option<&[u8]>) -> Check {
+        let givens = self.givens();
+        let mut mistakes = (0..grid.len())
+            .filter(|&cell| givens[cell] == 0)
+            .filter(|&cell| {
+                let digit = grid[cell];
+                let breaks_rules = digit != 0 && self.peers[cell].iter().any(|peer| grid[*peer] == digit);
+                let wrong = solution.is_some_and(|solution| digit != 0 && solution.get(cell) != Some(&digit));
+                breaks_rules || wrong
+            })
+            .collect::<Vec<_>>();
+        if solution.is_none() {
+            mistakes.extend(self.broken_variant_cells(grid).into_iter().filter(|cell| givens[*cell] == 0));
+        }
+        mistakes.sort_unstable();
+        mistakes.dedup();
+
+        if !mistakes.is_empty() {
+            Check::Mistakes(mistakes.into_iter().map(|cell| self.cell(cell)).collect())
+        } else if grid.contains(&0) {
+            Check::Incomplete
+        } else if self.puzzle.is_valid_solution(grid) {
+            Check::Solved
+        } else {
+            Check::Mistakes(Vec::new())
+        }
+    }
+
+    /// Finds the next step for an entered grid: a mistake to fix, the simplest deduction, or
+    /// failing that the solution's digit for the most constrained cell.
+    pub fn hint(&self, grid: &[u8], solution: Option<&[u8]>) -> Option<Hint> {
+        if !self.is_supported() {
+            return None;
+        }
+        if let Some(solution) = solution {
+            let mistake = (0..grid.len()).find(|&cell| grid[cell] != 0 && solution.get(cell) != Some(&grid[cell]));
+            if let Some(cell) = mistake {
+                return Some(self.hint_at(cell, solution[cell], Technique::Mistake));
+            }
+        }
+
+        let mut candidates = self.candidates(grid);
+        if let Some(hint) = self.find_single(grid, &candidates) {
+            return Some(hint);
+        }
+
+        while self.restrict_cages(grid, &mut candidates) {}
+        if let Some(hint) = self.find_single(grid, &candidates) {
+            return Some(Hint { technique: Technique::CageCombinations, ..hint });
+        }
+
+        while self.restrict_thermometers(&mut candidates) | self.restrict_cages(grid, &mut candidates) {}
+        if let Some(hint) = self.find_single(grid, &candidates) {
+            return Some(Hint { technique: Technique::Thermometer, ..hint });
+        }
+
+        let solution = solution?;
+        (0..grid.len())
+            .filter(|&cell| grid[cell] == 0)
+            .min_by_key(|&cell| candidates[cell].count_ones())
+            .map(|cell| self.hint_at(cell, solution[cell], Technique::Solution))
+    }
+
+    /// Returns the grid containing only the givens.
+    fn givens(&self) -> Vec<u8> {
+        let mut grid = vec![0; self.size * self.size];
+        for (cell, digit) in &self.givens {
+            grid[*cell] = *digit;
+        }
+        grid
+    }
+
+    fn cell(&self, index: usize) -> Cell {
+        (index / self.size, index % self.size)
+    }
+
+    fn hint_at(&self, cell: usize, digit: u8, technique: Technique) -> Hint {
+        Hint {
+            cell: self.cell(cell),
+            digit,
+            technique,
+            region: self.regions[cell],
+        }
+    }
+
+    /// Returns each cell's candidates as a bitmask (bit `d` for digit `d`), ruling out digits
+    /// already placed in a peer.
+    fn candidates(&self, grid: &[u8]) -> Vec<u32> {
+        let all = ((1u32 << self.size) - 1) << 1;
+        (0..grid.len())
+            .map(|cell| match grid[cell] {
+                0 => self.peers[cell]
+                    .iter()
+                    .fold(all, |mask, peer| mask & !(1 << grid[*peer])),
+                digit => 1 << digit,
+            })
+            .collect()
+    }
+
+    /// Finds an empty cell with one candidate, or a digit with one place in a unit.
+    fn find_single(&self, grid: &[u8], candidates: &[u32]) -> Option<Hint> {
+        let naked = (0..grid.len()).find(|&cell| grid[cell] == 0 && candidates[cell].count_ones() == 1);
+        if let Some(cell) = naked {
+            return Some(self.hint_at(cell, candidates[cell].trailing_zeros() as u8, Technique::NakedSingle));
+        }
+
+        for (unit, cells) in &self.units {
+            for digit in 1..=self.size as u8 {
+                if cells.iter().any(|cell| grid[*cell] == digit) {
+                    continue;
+                }
+                let mut places = cells.iter().filter(|cell| candidates[**cell] & (1 << digit) != 0);
+                if let (Some(&cell), None) = (places.next(), places.next()) {
+                    return Some(self.hint_at(cell, digit, Technique::HiddenSingle(*unit)));
+                }
+            }
+        }
+        None
+    }
+
+    /// Rules out digits that don't appear in any possible combination of a cage with a sum.
+    ///
+    /// Returns whether any candidates were removed.
+    fn restrict_cages(&self, grid: &[u8], candidates: &mut [u32]) -> bool {
+        let mut changed = false;
+        for (cells, sum) in &self.cages {
+            let Some(sum) = sum else { continue };
+            let placed = cells.iter().fold(0u32, |mask, cell| mask | (1 << grid[*cell])) & !1;
+            let empty = cells.iter().filter(|cell| grid[**cell] == 0).collect::<Vec<_>>();
+
+            let mut allowed = 0;
+            for combination in 0..(1u32 << self.size) {
+                let digits = combination << 1;
+                let total = (1..=self.size as u32).filter(|digit| digits & (1 << digit) != 0).sum::<u32>();
+                if digits.count_ones() as usize == cells.len()
+                    && total == *sum
+                    && digits & placed == placed
+                    && empty.iter().all(|cell| candidates[**cell] & digits & !placed != 0)
+                {
+                    allowed |= digits & !placed;
+                }
+            }
+
+            for cell in empty {
+                changed |= restrict(candidates, *cell, allowed);
+            }
+        }
+        changed
+    }
+
+    /// Rules out digits too low or too high for their position on a thermometer.
+    ///
+    /// Returns whether any candidates were removed.
+    fn restrict_thermometers(&self, candidates: &mut [u32]) -> bool {
+        let mut changed = false;
+        for thermometer in &self.thermometers {
+            for pair in thermometer.windows(2) {
+                // Only digits above the lowest candidate of the previous cell
+                let lowest = candidates[pair[0]].trailing_zeros();
+                changed |= restrict(candidates, pair[1], u32::MAX.checked_shl(lowest + 1).unwrap_or(0));
+            }
+            for pair in thermometer.windows(2).rev() {
+                // Only digits below the highest candidate of the next cell
+                let highest = 32 - candidates[pair[1]].leading_zeros();
+                changed |= restrict(candidates, pair[0], (1 << highest.saturating_sub(1)) - 1);
+            }
+        }
+        changed
+    }
+
+    /// Returns the cells of cages and thermometers whose entered digits can't be completed.
+    fn broken_variant_cells(&self, grid: &[u8]) -> Vec<usize> {
+        let mut broken = Vec::new();
+        for (cells, sum) in &self.cages {
+            let total = cells.iter().map(|cell| u32::from(grid[*cell])).sum::<u32>();
+            let full = cells.iter().all(|cell| grid[*cell] != 0);
+            if sum.is_some_and(|sum| total > sum || (full && total != sum)) {
+                broken.extend(cells.iter().filter(|cell| grid[**cell] != 0));
+            }
+        }
+        for thermometer in &self.thermometers {
+            let filled = thermometer.iter().filter(|cell| grid[**cell] != 0).collect::<Vec<_>>();
+            for pair in filled.windows(2) {
+                if grid[*pair[0]] >= grid[*pair[1]] {
+                    broken.extend([*pair[0], *pair[1]]);
+                }
+            }
+        }
+        broken
+    }
+
+    /// Backtracking search collecting up to `limit` solutions.
+    fn search(&self, grid: &mut [u8], solutions: &mut Vec<Vec<u8>>, limit: usize, cancelled: &AtomicBool) {
+        if solutions.len() >= limit || cancelled.load(Ordering::Relaxed) {
+            return;
+        }
+        if (0..grid.len()).any(|cell| grid[cell] != 0 && self.peers[cell].iter().any(|peer| grid[*peer] == grid[cell])) {
+            return;
+        }
+
+        let mut candidates = self.candidates(grid);
+        while self.restrict_cages(grid, &mut candidates) | self.restrict_thermometers(&mut candidates) {}
+
+        let next = (0..grid.len())
+            .filter(|&cell| grid[cell] == 0)
+            .min_by_key(|&cell| candidates[cell].count_ones());
+        let Some(cell) = next else {
+            if self.puzzle.is_valid_solution(grid) {
+                solutions.push(grid.to_vec());
+            }
+            return;
+        };
+
+        for digit in 1..=self.size as u8 {
+            if candidates[cell] & (1 << digit) != 0 {
+                grid[cell] = digit;
+                self.search(grid, solutions, limit, cancelled);
+            }
+        }
+        grid[cell] = 0;
+    }
+}
+
+/// Limits a cell's candidates to `mask`, returning whether any were removed.
+fn restrict(candidates: &mut [u32], cell: usize, mask: u32) -> bool {
+    let removed = candidates[cell] & !mask != 0;
+    candidates[cell] &= mask;
+    removed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::puzzle::Cage;
+
+    /// Solution of the classic puzzle in the f-puzzles fixture.
+    const SOLUTION: &str = "534678912672195348198342567859761423426853791713924856961537284287419635345286179";
+
+    fn digits(text: &str) -> Vec<u8> {
+        text.bytes().map(|byte| byte - b'0').collect()
+    }
+
+    fn classic() -> PuzzleDescription {
+        PuzzleDescription::from_url(include_str!("fixtures/puzzles/fpuzzles_classic.txt").trim())
+            .unwrap()
+            .unwrap()
+    }
+
+    fn uniqueness(puzzle: &PuzzleDescription) -> Uniqueness {
+        Solver::new(puzzle).uniqueness(&AtomicBool::new(false)).unwrap()
+    }
+
+    fn grid(puzzle: &PuzzleDescription) -> Vec<u8> {
+        Solver::new(puzzle).givens()
+    }
+
+    #[test]
+    fn finds_unique_and_ambiguous_puzzles() {
+        let puzzle = classic();
+        assert_eq!(uniqueness(&puzzle), Uniqueness::Unique(digits(SOLUTION)));
+
+        let empty = PuzzleDescription {
+            size: 4,
+            ..PuzzleDescription::default()
+        };
+        assert_eq!(uniqueness(&empty), Uniqueness::Multiple);
+
+        let contradictory = PuzzleDescription {
+            size: 4,
+            givens: vec![((0, 0), 1), ((0, 3), 1)],
+            ..PuzzleDescription::default()
+        };
+        assert_eq!(uniqueness(&contradictory), Uniqueness::NoSolution);
+    }
+
+    #[test]
+    fn skips_grids_too_big_and_stops_when_cancelled() {
+        let big = PuzzleDescription {
+            size: 32,
+            ..PuzzleDescription::default()
+        };
+        assert_eq!(uniqueness(&big), Uniqueness::Unsupported);
+        assert_eq!(Solver::new(&big).hint(&vec![0; 32 * 32], None), None);
+
+        assert_eq!(Solver::new(&classic()).uniqueness(&AtomicBool::new(true)), None);
+    }
+
+    #[test]
+    fn rejects_givens_that_do_not_fit_the_grid() {
+        for given in [((0, 0), 0), ((0, 0), 5), ((0, 0), 40), ((0, 4), 1), ((4, 0), 1)] {
+            let puzzle = PuzzleDescription {
+                size: 4,
+                givens: vec![((1, 1), 2), given],
+                ..PuzzleDescription::default()
+            };
+            let solver = Solver::new(&puzzle);
+            assert_eq!(uniqueness(&puzzle), Uniqueness::Unsupported, "{given:?}");
+            assert_eq!(solver.hint(&[0; 16], None), None);
+            assert_eq!(grid(&puzzle).iter().filter(|digit| **digit != 0).count(), 1);
+            assert_eq!(solver.check(&grid(&puzzle), None), Check::Incomplete);
+        }
+    }
+
+    #[test]
+    fn cages_and_thermometers_narrow_the_solutions() {
+        // Without the cage and thermometer this grid has two solutions
+        let mut puzzle = PuzzleDescription {
+            size: 4,
+            givens: vec![
+                ((0, 0), 1),
+                ((0, 1), 2),
+                ((0, 2), 3),
+                ((1, 0), 3),
+                ((1, 2), 1),
+                ((1, 3), 2),
+                ((2, 1), 1),
+                ((3, 3), 1),
+            ],
+            ..PuzzleDescription::default()
+        };
+        assert_eq!(uniqueness(&puzzle), Uniqueness::Multiple);
+
+        puzzle.cages = vec![Cage {
+            cells: vec![(2, 0), (2, 3)],
+            sum: Some(5),
+        }];
+        let Uniqueness::Unique(solution) = uniqueness(&puzzle) else {
+            panic!("cage should make the solution unique");
+        };
+        assert_eq!(solution[8] + solution[11], 5);
+
+        puzzle.cages.clear();
+        puzzle.thermometers = vec![vec![(2, 0), (3, 0)]];
+        let Uniqueness::Unique(solution) = uniqueness(&puzzle) else {
+            panic!("thermometer should make the solution unique");
+        };
+        assert!(solution[8] < solution[12]);
+    }
+
+    #[test]
+    fn checks_entered_digits() {
+        let puzzle = classic();
+        let solver = Solver::new(&puzzle);
+        let solution = digits(SOLUTION);
+        let mut entered = grid(&puzzle);
+        assert_eq!(solver.check(&entered, Some(&solution)), Check::Incomplete);
+
+        entered[2] = 2; // Fits the row, column and box but not the solution
+        entered[3] = 5; // Repeats the given 5 in row 1, and the cage no longer adds up to 10
+        assert_eq!(solver.check(&entered, Some(&solution)), Check::Mistakes(vec![(0, 2), (0, 3)]));
+        assert_eq!(solver.check(&entered, None), Check::Mistakes(vec![(0, 2), (0, 3)]));
+
+        entered[3] = 0;
+        assert_eq!(solver.check(&entered, None), Check::Incomplete);
+
+        assert_eq!(solver.check(&solution, Some(&solution)), Check::Solved);
+    }
+
+    #[test]
+    fn gives_graduated_hints() {
+        let puzzle = classic();
+        let solver = Solver::new(&puzzle);
+        let solution = digits(SOLUTION);
+        let mut entered = grid(&puzzle);
+
+        // Follow the hints all the way to the solution
+        while let Some(hint) = solver.hint(&entered, Some(&solution)) {
+            let (row, col) = hint.cell;
+            assert_ne!(hint.technique, Technique::Mistake);
+            assert_eq!(hint.digit, solution[row * 9 + col], "{}", hint.describe(Hint::MAX_LEVEL));
+            entered[row * 9 + col] = hint.digit;
+        }
+        assert_eq!(entered, solution);
+
+        entered[80] = 1;
+        let hint = solver.hint(&entered, Some(&solution)).unwrap();
+        assert_eq!(hint.technique, Technique::Mistake);
+        assert_eq!(hint.describe(0), "There's a mistake in region 9.");
+        assert_eq!(hint.describe(1), "r9c9 is wrong.");
+        assert_eq!(hint.describe(2), "r9c9 should be 9.");
+    }
+
+    #[test]
+    fn hints_cage_combinations() {
+        // The cage must be 1+2, and r2c2 shares a box with the given 1
+        let puzzle = PuzzleDescription {
+            size: 4,
+            givens: vec![((0, 0), 1)],
+            cages: vec![Cage {
+                cells: vec![(1, 1), (1, 2)],
+                sum: Some(3),
+            }],
+            ..PuzzleDescription::default()
+        };
+        let solver = Solver::new(&puzzle);
+
+        let hint = solver.hint(&grid(&puzzle), None).unwrap();
+
+        assert_eq!(hint.technique, Technique::CageCombinations);
+        assert_eq!((hint.cell, hint.digit), ((1, 1), 2));
+        assert_eq!(hint.describe(1), "The killer cage combinations leave only one option for r2c2.");
+    }
+}
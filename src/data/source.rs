@@ -0,0 +1,77 @@
+use async_trait::async_trait;
+
+use super::{
+    error::DataError,
+    model::{Channel, ChannelRef, CtcVideo, Playlist, VideoId},
+};
+
+/// A single page of videos returned by a [`VideoSource`].
+#[derive(Debug, Default)]
+pub struct VideoPage {
+    /// IDs of every item on the page, in playlist order.
+    pub video_ids: Vec<VideoId>,
+
+    /// Details for the videos on the page.
+    pub videos: Vec<CtcVideo>,
+
+    /// Token to request the following page, if there is one.
+    pub next_page_token: Option<String>,
+}
+
+/// A backend capable of listing the videos in a YouTube playlist.
+#[async_trait]
+pub trait VideoSource: Send + Sync {
+    /// Fetches a page of videos from the given playlist.
+    async fn get_playlist_page(
+        &self,
+        playlist_id: &str,
+        page_token: Option<String>,
+    ) -> Result<VideoPage, DataError>;
+
+    /// Returns whether paging through a playlist lists every video in it, so the result can
+    /// replace the stored membership. Sources that only see the latest videos return `false`.
+    fn lists_whole_playlists(&self) -> bool {
+        true
+    }
+
+    /// Fetches full details for specific videos.
+    ///
+    /// Sources that can't look up individual videos return an empty list.
+    async fn get_videos(&self, _video_ids: &[VideoId]) -> Result<Vec<CtcVideo>, DataError> {
+        Ok(Vec::new())
+    }
+
+    /// Looks up a channel's ID and display name.
+    async fn resolve_channel(&self, channel: &ChannelRef) -> Result<Channel, DataError>;
+
+    /// Looks up a playlist's title.
+    async fn resolve_playlist(&self, playlist_id: &str) -> Result<Playlist, DataError>;
+}
+
+/// Where the application gets its video list from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SourceKind {
+    /// The YouTube Data API, which needs an API key.
+    #[default]
+    Api,
+
+    /// The channel's public Atom feed, which needs no key but only lists recent uploads.
+    Feed,
+}
+impl SourceKind {
+    /// Returns the value stored in the settings table.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Api => "api",
+            Self::Feed => "feed",
+        }
+    }
+
+    /// Parses a value stored in the settings table, defaulting to the API.
+    pub fn from_setting(value: &str) -> Self {
+        match value {
+            "feed" => Self::Feed,
+            _ => Self::Api,
+        }
+    }
+}
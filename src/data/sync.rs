@@ -0,0 +1,307 @@
+use std::collections::HashSet;
+
+use log::{debug, error, info};
+
+use super::{
+    db::YoutubeDatabase,
+    error::DataError,
+    model::{Channel, ChannelRef, CtcVideo, Playlist, VideoId},
+    source::VideoSource,
+};
+
+/// Outcome of fetching new uploads from a [`VideoSource`].
+#[derive(Debug, Default)]
+pub struct FetchResult {
+    /// New videos found before the fetch finished or failed.
+    pub videos: Vec<CtcVideo>,
+
+    /// The error that stopped the fetch early, if any.
+    pub error: Option<DataError>,
+}
+
+/// Fetches uploads for a channel, newest first, until a page contains an already known video.
+pub async fn fetch_new_videos(
+    source: &dyn VideoSource,
+    channel: &Channel,
+    known_video_ids: &HashSet<VideoId>,
+) -> FetchResult {
+    let playlist_id = &channel.uploads_playlist;
+    let mut result = FetchResult::default();
+    let mut next_page_token = None;
+
+    loop {
+        let page = match source.get_playlist_page(playlist_id, next_page_token).await {
+            Ok(page) => page,
+            Err(e) => {
+                error!("Error fetching videos: {e}");
+                result.error = Some(e);
+                break;
+            }
+        };
+
+        // If we have any of the video IDs in the database, then we don't need to get the next page.
+        let get_next_page = !page.video_ids.iter().any(|id| known_video_ids.contains(id));
+        if !get_next_page {
+            debug!("Page contains a video already in the database, skipping next fetch.");
+        }
+
+        result.videos.extend(
+            page.videos
+                .into_iter()
+                .filter(|video| !known_video_ids.contains(&video.id))
+                .map(|mut video| {
+                    if video.channel_id.is_empty() {
+                        video.channel_id = channel.id.clone();
+                    }
+                    video
+                }),
+        );
+        info!("{} new videos loaded from {}.", result.videos.len(), channel.display_name);
+
+        next_page_token = page.next_page_token;
+        if !get_next_page || next_page_token.is_none() {
+            break;
+        }
+    }
+
+    result
+}
+
+/// Resolves a channel URL, handle or ID entered by the user and starts tracking it.
+pub async fn add_channel(
+    source: &dyn VideoSource,
+    db: &YoutubeDatabase,
+    input: &str,
+) -> Result<Channel, DataError> {
+    let channel_ref = ChannelRef::parse(input)
+        .ok_or_else(|| DataError::NotFound(format!("Not a channel URL, handle or ID: {input}")))?;
+    let channel = source.resolve_channel(&channel_ref).await?;
+    db.add_channel(&channel).await?;
+    Ok(channel)
+}
+
+/// Fetches every page of a playlist.
+///
+/// Returns the playlist's video IDs in order along with the details of its videos.
+pub async fn fetch_playlist(
+    source: &dyn VideoSource,
+    playlist_id: &str,
+) -> Result<(Vec<VideoId>, Vec<CtcVideo>), DataError> {
+    let mut video_ids = Vec::new();
+    let mut videos = Vec::new();
+    let mut next_page_token = None;
+
+    loop {
+        let page = source.get_playlist_page(playlist_id, next_page_token).await?;
+        video_ids.extend(page.video_ids);
+        videos.extend(page.videos);
+
+        next_page_token = page.next_page_token;
+        if next_page_token.is_none() {
+            break;
+        }
+    }
+
+    debug!("{} videos in playlist {playlist_id}.", video_ids.len());
+    Ok((video_ids, videos))
+}
+
+/// Resolves a playlist URL or ID entered by the user and starts tracking it.
+pub async fn add_playlist(
+    source: &dyn VideoSource,
+    db: &YoutubeDatabase,
+    input: &str,
+) -> Result<Playlist, DataError> {
+    let playlist_id = Playlist::parse_id(input)
+        .ok_or_else(|| DataError::NotFound(format!("Not a playlist URL or ID: {input}")))?;
+    let playlist = source.resolve_playlist(&playlist_id).await?;
+    db.add_playlist(&playlist).await?;
+    Ok(playlist)
+}
+
+/// Fills in durations for stored videos that were added without one (e.g. from the public feed).
+///
+/// Returns the number of videos updated.
+pub async fn backfill_durations(
+    source: &dyn VideoSource,
+    db: &YoutubeDatabase,
+) -> Result<usize, DataError> {
+    let video_ids = db.get_video_ids_with_unknown_duration().await?;
+    if video_ids.is_empty() {
+        return Ok(0);
+    }
+
+    debug!("Backfilling durations for {} videos.", video_ids.len());
+    let mut updated = 0;
+    for video in source.get_videos(&video_ids).await? {
+        if !video.duration.is_unknown() {
+            db.set_video_duration(&video.id, *video.duration).await?;
+            updated += 1;
+        }
+    }
+
+    Ok(updated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::{
+        fake_source::{fixtures, seed_video, FakeVideoSource},
+        model::CTC_CHANNEL_ID,
+        rules::{FilterRule, RuleSet},
+    };
+
+    fn ctc() -> Channel {
+        Channel::new(CTC_CHANNEL_ID, "Cracking the Cryptic")
+    }
+
+    fn ids(videos: &[CtcVideo]) -> Vec<&str> {
+        videos.iter().map(|video| video.id.as_str()).collect()
+    }
+
+    fn ids_of<'a>(videos: &[&'a CtcVideo]) -> Vec<&'a str> {
+        videos.iter().map(|video| video.id.as_str()).collect()
+    }
+
+    fn three_page_source() -> FakeVideoSource {
+        FakeVideoSource::new(fixtures::VIDEOS)
+            .with_page(None, fixtures::PLAYLIST_PAGE_1)
+            .with_page(Some("PAGE_2"), fixtures::PLAYLIST_PAGE_2)
+            .with_page(Some("PAGE_3"), fixtures::PLAYLIST_PAGE_3)
+    }
+
+    #[tokio::test]
+    async fn follows_pagination_until_the_last_page() {
+        let source = three_page_source();
+
+        let result = fetch_new_videos(&source, &ctc(), &HashSet::new()).await;
+
+        assert!(result.error.is_none());
+        assert_eq!(
+            ids(&result.videos),
+            ["vid-001", "vid-002", "vid-003", "vid-004", "vid-005", "vid-006", "vid-007", "vid-008"]
+        );
+        assert_eq!(
+            source.requested_pages(),
+            [None, Some("PAGE_2".to_string()), Some("PAGE_3".to_string())]
+        );
+    }
+
+    #[tokio::test]
+    async fn stops_after_a_page_containing_a_known_video() {
+        let source = three_page_source();
+        let known = HashSet::from([VideoId::new("vid-005")]);
+
+        let result = fetch_new_videos(&source, &ctc(), &known).await;
+
+        assert!(result.error.is_none());
+        assert_eq!(ids(&result.videos), ["vid-001", "vid-002", "vid-003", "vid-004", "vid-006"]);
+        assert_eq!(source.requested_pages().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn keeps_word_game_videos_for_display_time_filtering() {
+        let source = three_page_source();
+        let rules = RuleSet::new(FilterRule::defaults());
+
+        let result = fetch_new_videos(&source, &ctc(), &HashSet::new()).await;
+
+        let excluded = result
+            .videos
+            .iter()
+            .filter(|video| rules.is_excluded(video))
+            .collect::<Vec<_>>();
+        assert_eq!(ids_of(&excluded), ["vid-003", "vid-006", "vid-008"]);
+    }
+
+    #[tokio::test]
+    async fn keeps_earlier_pages_when_quota_is_exceeded() {
+        let source = FakeVideoSource::new(fixtures::VIDEOS)
+            .with_page(None, fixtures::PLAYLIST_PAGE_1)
+            .with_error(Some("PAGE_2"), fixtures::ERROR_QUOTA_EXCEEDED);
+
+        let result = fetch_new_videos(&source, &ctc(), &HashSet::new()).await;
+
+        assert!(matches!(result.error, Some(DataError::QuotaExceeded(_))));
+        assert_eq!(ids(&result.videos), ["vid-001", "vid-002", "vid-003"]);
+    }
+
+    #[tokio::test]
+    async fn reports_invalid_api_keys() {
+        let source = FakeVideoSource::new(fixtures::VIDEOS)
+            .with_error(None, fixtures::ERROR_INVALID_KEY);
+
+        let result = fetch_new_videos(&source, &ctc(), &HashSet::new()).await;
+
+        assert!(result.error.as_ref().is_some_and(DataError::is_invalid_api_key));
+        assert!(result.videos.is_empty());
+    }
+
+    #[tokio::test]
+    async fn backfills_unknown_durations() {
+        let source = FakeVideoSource::new(fixtures::VIDEOS);
+        let db = YoutubeDatabase::in_memory().await.unwrap();
+        db.save_fetched_videos(&[seed_video("vid-001", "From the feed", "", 0, 0)]).await.unwrap();
+        db.save_fetched_videos(&[seed_video("vid-unlisted", "Not in the catalogue", "", 0, 0)]).await.unwrap();
+
+        let updated = backfill_durations(&source, &db).await.unwrap();
+
+        assert_eq!(updated, 1);
+        assert_eq!(
+            db.get_video_ids_with_unknown_duration().await.unwrap(),
+            [VideoId::new("vid-unlisted")]
+        );
+    }
+
+    #[tokio::test]
+    async fn adds_channels_by_handle() {
+        let source = FakeVideoSource::new(fixtures::VIDEOS)
+            .with_channel(Channel::new("UCxxxxxxxxxxxxxxxxxxxxxx", "OtherPuzzles"));
+        let db = YoutubeDatabase::in_memory().await.unwrap();
+
+        let channel = add_channel(&source, &db, "https://www.youtube.com/@OtherPuzzles")
+            .await
+            .unwrap();
+
+        assert_eq!(channel.uploads_playlist, "UUxxxxxxxxxxxxxxxxxxxxxx");
+        assert!(db.get_channels().await.unwrap().contains(&channel));
+        assert!(matches!(
+            add_channel(&source, &db, "@Unknown").await,
+            Err(DataError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn tags_fetched_videos_with_their_channel() {
+        let source = three_page_source();
+
+        let result = fetch_new_videos(&source, &ctc(), &HashSet::new()).await;
+
+        assert!(result.videos.iter().all(|video| video.channel_id == CTC_CHANNEL_ID));
+    }
+
+    #[tokio::test]
+    async fn fetches_every_page_of_a_playlist() {
+        let source = three_page_source();
+
+        let (video_ids, videos) = fetch_playlist(&source, "PLseries").await.unwrap();
+
+        assert_eq!(video_ids.len(), 8);
+        assert_eq!(ids(&videos), video_ids.iter().map(|id| id.as_str()).collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn adds_playlists_by_url() {
+        let source = FakeVideoSource::new(fixtures::VIDEOS)
+            .with_playlist(Playlist::new("PLseries", "Miracle Sudokus"));
+        let db = YoutubeDatabase::in_memory().await.unwrap();
+
+        let playlist = add_playlist(&source, &db, "https://www.youtube.com/playlist?list=PLseries")
+            .await
+            .unwrap();
+
+        assert_eq!(playlist.title, "Miracle Sudokus");
+        assert_eq!(db.get_playlists().await.unwrap(), [playlist]);
+    }
+}
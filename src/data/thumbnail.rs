@@ -0,0 +1,109 @@
+use std::path::PathBuf;
+
+use log::{debug, error};
+
+use super::{
+    error::DataError,
+    http::{download, new_http_client, HttpClient},
+    model::VideoId,
+};
+
+/// A decoded thumbnail image.
+pub struct Thumbnail {
+    pub width: usize,
+    pub height: usize,
+
+    /// Pixels as rows of red, green and blue bytes.
+    pub rgb: Vec<u8>,
+}
+
+/// Downloads video thumbnails, keeping a copy on disk so each is only downloaded once.
+#[derive(Clone)]
+pub struct ThumbnailClient {
+    client: HttpClient,
+    cache_dir: PathBuf,
+}
+impl ThumbnailClient {
+    /// Creates a new instance of `ThumbnailClient` caching thumbnails in `cache_dir`.
+    pub fn new(cache_dir: PathBuf) -> Self {
+        Self {
+            client: new_http_client(),
+            cache_dir,
+        }
+    }
+
+    /// Gets a video's thumbnail from the cache, downloading it if it isn't there yet or the
+    /// cached copy is damaged.
+    pub async fn get(&self, video_id: &VideoId) -> Result<Thumbnail, DataError> {
+        let path = self.cache_dir.join(format!("{video_id}.jpg"));
+        if let Ok(bytes) = tokio::fs::read(&path).await {
+            match decode_jpeg(&bytes) {
+                Ok(thumbnail) => return Ok(thumbnail),
+                Err(e) => {
+                    error!("Discarding cached thumbnail {}: {e}", path.display());
+                    tokio::fs::remove_file(&path).await.ok();
+                }
+            }
+        }
+
+        debug!("Downloading thumbnail for {video_id}");
+        let bytes = download(&self.client, &thumbnail_url(video_id)).await?;
+        let thumbnail = decode_jpeg(&bytes)?;
+        let saved = match tokio::fs::create_dir_all(&self.cache_dir).await {
+            Ok(()) => tokio::fs::write(&path, &bytes).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = saved {
+            error!("Failed to cache thumbnail {}: {e}", path.display());
+        }
+        Ok(thumbnail)
+    }
+}
+
+/// Returns the URL of a video's medium-sized (320×180) thumbnail.
+fn thumbnail_url(video_id: &VideoId) -> String {
+    format!("https://i.ytimg.com/vi/{video_id}/mqdefault.jpg")
+}
+
+/// Decodes a JPEG image into RGB pixels.
+fn decode_jpeg(bytes: &[u8]) -> Result<Thumbnail, DataError> {
+    let mut decoder = jpeg_decoder::Decoder::new(bytes);
+    let pixels = decoder
+        .decode()
+        .map_err(|e| DataError::Parse(format!("Invalid thumbnail: {e}")))?;
+    let info = decoder
+        .info()
+        .ok_or_else(|| DataError::Parse("Invalid thumbnail: missing image info".to_string()))?;
+
+    let rgb = match info.pixel_format {
+        jpeg_decoder::PixelFormat::RGB24 => pixels,
+        jpeg_decoder::PixelFormat::L8 => pixels.iter().flat_map(|&luma| [luma; 3]).collect(),
+        format => {
+            return Err(DataError::Parse(format!("Unsupported thumbnail pixel format {format:?}")));
+        }
+    };
+    Ok(Thumbnail {
+        width: info.width.into(),
+        height: info.height.into(),
+        rgb,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_jpeg_thumbnails() {
+        let thumbnail = decode_jpeg(include_bytes!("fixtures/thumbnails/red_blue.jpg")).unwrap();
+        assert_eq!((thumbnail.width, thumbnail.height), (16, 8));
+        assert_eq!(thumbnail.rgb.len(), 16 * 8 * 3);
+
+        // Red on the left, blue on the right
+        let pixel = |x: usize, y: usize| &thumbnail.rgb[(y * 16 + x) * 3..][..3];
+        assert!(matches!(pixel(0, 0), [r, g, b] if *r > 200 && *g < 60 && *b < 60));
+        assert!(matches!(pixel(15, 7), [r, g, b] if *r < 60 && *g < 60 && *b > 200));
+
+        assert!(matches!(decode_jpeg(b"not a jpeg"), Err(DataError::Parse(_))));
+    }
+}
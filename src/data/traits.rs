@@ -3,8 +3,11 @@ use std::{fmt::Display, str::FromStr};
 use chrono::TimeZone;
 use google_youtube3::api::Video;
 
-use super::model::{
-    extract_links_from_description, CtcVideo, CtcVideoRow, VideoDuration, VideoId, VideoPublishDate,
+use super::{
+    description::parse_chapters,
+    model::{
+        extract_links_from_description, CtcVideo, CtcVideoRow, VideoDuration, VideoId, VideoPublishDate,
+    },
 };
 
 impl FromStr for VideoId {
@@ -57,6 +60,8 @@ impl From<Video> for CtcVideo {
         );
         // Extract links from description text.
         let extracted_links = extract_links_from_description(description.as_str());
+        let chapters = parse_chapters(&description);
+        let channel_id = snippet.channel_id.unwrap_or_default();
         Self {
             id,
             title,
@@ -64,6 +69,8 @@ impl From<Video> for CtcVideo {
             date,
             duration,
             extracted_links,
+            chapters,
+            channel_id,
         }
     }
 }
@@ -76,6 +83,8 @@ impl From<CtcVideoRow> for CtcVideo {
             date: VideoPublishDate::new(row.date),
             duration: VideoDuration::new(row.duration),
             extracted_links: extract_links_from_description(row.description.as_str()),
+            chapters: parse_chapters(&row.description),
+            channel_id: row.channel_id,
         }
     }
 }
@@ -102,7 +111,8 @@ impl std::ops::DerefMut for VideoPublishDate {
 impl Display for VideoDuration {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self.0 {
-            0..=3599 => write!(f, "{}m", self.0 / 60),
+            0 => write!(f, "?"),
+            1..=3599 => write!(f, "{}m", self.0 / 60),
             _ => write!(f, "{}h", self.0 / 3600),
         }
     }
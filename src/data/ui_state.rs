@@ -0,0 +1,55 @@
+use serde::{Deserialize, Serialize};
+
+use super::grouping::GroupBy;
+
+/// Which videos the grid shows.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FilterSettings {
+    pub show_completed_videos: bool,
+    pub show_without_links: bool,
+    pub filter_text: String,
+
+    /// Whether the filter text is a full-text search of titles and descriptions.
+    pub search_descriptions: bool,
+    pub channel_filter: Option<String>,
+    pub playlist_filter: Option<String>,
+}
+
+/// A named set of filters the user saved to switch back to quickly.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FilterPreset {
+    pub name: String,
+    pub filter: FilterSettings,
+}
+
+/// How the videos are laid out.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ViewMode {
+    /// One row per video in a table.
+    #[default]
+    Table,
+
+    /// Cards with thumbnails, wrapping to the window width.
+    Cards,
+}
+
+/// UI state restored when the app starts.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UiState {
+    #[serde(flatten)]
+    pub filter: FilterSettings,
+
+    /// Inner size of the window in points, if it has been recorded.
+    pub window_size: Option<[f32; 2]>,
+
+    /// Vertical scroll position of the video grid in points.
+    pub scroll_offset: f32,
+
+    /// Whether the videos are shown as a table or as cards.
+    pub view_mode: ViewMode,
+
+    /// How the videos are grouped under collapsible headers.
+    pub group_by: GroupBy,
+}
@@ -1,7 +1,6 @@
-use std::error::Error;
-
+use async_trait::async_trait;
 use google_youtube3::{
-    api::{PlaylistItemListResponse, Video},
+    api::{PlaylistItemListResponse, VideoListResponse},
     common::NoToken,
     hyper_rustls::{self, HttpsConnector},
     hyper_util::{self, client::legacy::connect::HttpConnector},
@@ -11,7 +10,11 @@ use log::{debug, error};
 
 use crate::data::model::CtcVideo;
 
-use super::model::VideoId;
+use super::{
+    error::DataError,
+    model::{Channel, ChannelRef, Playlist, VideoId},
+    source::{VideoPage, VideoSource},
+};
 
 /// YouTube API client for fetching videos from the Cracking the Cryptic channel.
 #[derive(Clone)]
@@ -26,17 +29,17 @@ impl YouTubeClient {
         Self { api_key, hub }
     }
 
-    /// Fetches the channel page for the given channel ID.
-    pub async fn get_channel_page(
+    /// Fetches a page of items from the given playlist.
+    pub async fn get_playlist_items(
         &self,
-        channel_id: &str,
+        playlist_id: &str,
         page_token: Option<String>,
-    ) -> Result<PlaylistItemListResponse, Box<dyn Error + Send + Sync>> {
+    ) -> Result<PlaylistItemListResponse, DataError> {
         let mut request = self
             .hub
             .playlist_items()
             .list(&vec!["snippet".into(), "contentDetails".into()])
-            .playlist_id(&get_upload_playlist(channel_id))
+            .playlist_id(playlist_id)
             .max_results(50)
             .param("key", self.api_key.as_str());
 
@@ -47,8 +50,8 @@ impl YouTubeClient {
         match request.doit().await {
             Ok((_, response)) => Ok(response),
             Err(e) => {
-                error!("Error fetching channel page: {e}");
-                Err(Box::new(e))
+                error!("Error fetching playlist page: {e}");
+                Err(DataError::from(e))
             }
         }
     }
@@ -56,40 +59,101 @@ impl YouTubeClient {
     /// Helper function to load videos from the playlist items response.
     pub async fn load_playist_videos(
         &self,
-        playlist_items: &mut PlaylistItemListResponse,
-    ) -> Result<Vec<CtcVideo>, Box<dyn Error + Send + Sync>> {
+        playlist_items: &PlaylistItemListResponse,
+    ) -> Result<Vec<CtcVideo>, DataError> {
         let video_ids = get_video_ids_from_playlist(playlist_items);
-        let mut video_data = Vec::<Video>::new();
 
         debug!("Found {} videos in the playlist.", video_ids.len());
 
+        self.load_videos(&video_ids).await
+    }
+
+    /// Loads details for up to 50 videos in a single request.
+    async fn load_videos(&self, video_ids: &[VideoId]) -> Result<Vec<CtcVideo>, DataError> {
+        if video_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
         let mut video_list_call = self
             .hub
             .videos()
             .list(&vec!["snippet".into(), "contentDetails".into()])
             .param("key", self.api_key.as_str());
 
-        for video_id in &video_ids {
+        for video_id in video_ids {
             video_list_call = video_list_call.add_id(video_id);
         }
 
-        let video_result = video_list_call.doit().await?;
+        let (_, video_result) = video_list_call.doit().await?;
+
+        Ok(videos_from_response(video_result))
+    }
+}
+#[async_trait]
+impl VideoSource for YouTubeClient {
+    async fn get_playlist_page(
+        &self,
+        playlist_id: &str,
+        page_token: Option<String>,
+    ) -> Result<VideoPage, DataError> {
+        let playlist_items = self.get_playlist_items(playlist_id, page_token).await?;
+        let videos = self.load_playist_videos(&playlist_items).await?;
+        Ok(page_from_responses(&playlist_items, videos))
+    }
 
-        video_data.extend(video_result.1.items.unwrap_or_default());
+    async fn resolve_channel(&self, channel: &ChannelRef) -> Result<Channel, DataError> {
+        let mut request = self
+            .hub
+            .channels()
+            .list(&vec!["snippet".into()])
+            .param("key", self.api_key.as_str());
+        request = match channel {
+            ChannelRef::Id(id) => request.add_id(id),
+            ChannelRef::Handle(handle) => request.for_handle(handle),
+        };
+
+        let (_, response) = request.doit().await?;
+        let found = response
+            .items
+            .unwrap_or_default()
+            .into_iter()
+            .find_map(|item| {
+                let title = item.snippet.and_then(|snippet| snippet.title);
+                item.id.map(|id| (id, title))
+            });
+
+        match found {
+            Some((id, title)) => Ok(Channel::new(&id, &title.unwrap_or_else(|| id.clone()))),
+            None => Err(DataError::NotFound(format!("No channel found for {channel:?}"))),
+        }
+    }
 
-        let videos = video_data
+    async fn resolve_playlist(&self, playlist_id: &str) -> Result<Playlist, DataError> {
+        let (_, response) = self
+            .hub
+            .playlists()
+            .list(&vec!["snippet".into()])
+            .add_id(playlist_id)
+            .param("key", self.api_key.as_str())
+            .doit()
+            .await?;
+
+        response
+            .items
+            .unwrap_or_default()
             .into_iter()
-            .filter(|video| {
-                if video.snippet.is_some() && video.snippet.as_ref().unwrap().title.is_some() {
-                    let title = video.snippet.as_ref().unwrap().title.as_ref().unwrap();
-                    return !title.contains("Wordle")
-                        && !title.contains("Plusword")
-                        && !title.contains("Quordle");
-                }
-                false
+            .find_map(|item| {
+                let title = item.snippet.and_then(|snippet| snippet.title);
+                item.id.map(|id| Playlist::new(&id, &title.unwrap_or_else(|| id.clone())))
             })
-            .map(CtcVideo::from)
-            .collect::<Vec<_>>();
+            .ok_or_else(|| DataError::NotFound(format!("No playlist found for {playlist_id}")))
+    }
+
+    async fn get_videos(&self, video_ids: &[VideoId]) -> Result<Vec<CtcVideo>, DataError> {
+        let mut videos = Vec::new();
+        for chunk in video_ids.chunks(50) {
+            videos.extend(self.load_videos(chunk).await?);
+        }
         Ok(videos)
     }
 }
@@ -109,17 +173,16 @@ pub(crate) fn get_hub() -> YouTube<HttpsConnector<HttpConnector>> {
 }
 
 /// Generates the upload playlist ID for a given channel ID.
-fn get_upload_playlist(channel_id: &str) -> String {
+pub fn get_upload_playlist(channel_id: &str) -> String {
     format!("UU{}", channel_id.chars().skip(2).collect::<String>())
 }
 
 /// Extracts video IDs from the playlist items response.
-pub fn get_video_ids_from_playlist(playlist_items: &mut PlaylistItemListResponse) -> Vec<VideoId> {
+pub fn get_video_ids_from_playlist(playlist_items: &PlaylistItemListResponse) -> Vec<VideoId> {
     playlist_items
         .items
-        .as_ref()
-        .unwrap()
         .iter()
+        .flatten()
         .map(|item| &item.snippet)
         .filter(|snippet| snippet.is_some())
         .map(|snippet| &snippet.as_ref().unwrap().resource_id)
@@ -129,3 +192,26 @@ pub fn get_video_ids_from_playlist(playlist_items: &mut PlaylistItemListResponse
         .map(|video_id| VideoId::new(&video_id))
         .collect::<Vec<_>>()
 }
+
+/// Converts a video list response into `CtcVideo`s.
+pub(crate) fn videos_from_response(response: VideoListResponse) -> Vec<CtcVideo> {
+    response
+        .items
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|video| video.snippet.as_ref().is_some_and(|s| s.title.is_some()))
+        .map(CtcVideo::from)
+        .collect()
+}
+
+/// Combines a playlist items response and the matching video details into a `VideoPage`.
+pub(crate) fn page_from_responses(
+    playlist_items: &PlaylistItemListResponse,
+    videos: Vec<CtcVideo>,
+) -> VideoPage {
+    VideoPage {
+        video_ids: get_video_ids_from_playlist(playlist_items),
+        videos,
+        next_page_token: playlist_items.next_page_token.clone(),
+    }
+}
@@ -1,7 +1,9 @@
 use std::{path::PathBuf, sync::LazyLock};
 
 use app::CtcTrackerApp;
+use data::{db::YoutubeDatabase, ui_state::UiState};
 use eframe::egui::{self, ViewportBuilder};
+use log::error;
 
 mod app;
 mod components;
@@ -29,13 +31,36 @@ async fn main() {
         .module_filter(|module| module.starts_with("ctc_tracker"))
         .start();
 
-    // Initialize the database connection
-    let db = data::db::YoutubeDatabase::new().await;
+    // Initialize the database connection, honouring `--db <path>` or `CTC_DB` overrides
+    let database_override = database_override().unwrap_or_else(|e| {
+        eprintln!("{e}\nUsage: ctc-tracker [--db <path>]");
+        std::process::exit(2);
+    });
+    let db = match database_override {
+        Some(location) if location == ":memory:" => YoutubeDatabase::in_memory().await,
+        Some(location) if location.starts_with("sqlite:") => YoutubeDatabase::connect(&location).await,
+        Some(location) => YoutubeDatabase::open(&PathBuf::from(location)).await,
+        None => YoutubeDatabase::new().await,
+    };
+    let db = match db {
+        Ok(db) => db,
+        Err(e) => {
+            error!("Failed to open the local database: {e}");
+            std::process::exit(1);
+        }
+    };
+
+    // Restore the filters, window size and scroll position from the last session
+    let ui_state = db.get_ui_state().await.unwrap_or_else(|e| {
+        error!("Failed to load the saved UI state: {e}");
+        UiState::default()
+    });
+    let [width, height] = ui_state.window_size.unwrap_or([1200.0, 720.0]);
 
     // Start egui
     let options = eframe::NativeOptions {
         viewport: ViewportBuilder {
-            inner_size: Some(egui::vec2(1200.0, 720.0)),
+            inner_size: Some(egui::vec2(width, height)),
             ..Default::default()
         },
         ..Default::default()
@@ -43,6 +68,31 @@ async fn main() {
     let _ = eframe::run_native(
         "Cracking the Cryptic Tracker",
         options,
-        Box::new(|_cc| Ok(Box::new(CtcTrackerApp::new(db)))),
+        Box::new(|_cc| Ok(Box::new(CtcTrackerApp::new(db, ui_state)))),
     );
 }
+
+/// Returns the database location passed with `--db <path>` (or `--db=<path>`), falling back to
+/// the `CTC_DB` environment variable. Values starting with `sqlite:` are treated as connection URLs
+/// and `:memory:` opens a throwaway in-memory database.
+///
+/// Returns an error if `--db` is given without a location.
+fn database_override() -> Result<Option<String>, String> {
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let location = if arg == "--db" {
+            args.next()
+        } else if let Some(location) = arg.strip_prefix("--db=") {
+            Some(location.to_string())
+        } else {
+            continue;
+        };
+        return match location.filter(|location| !location.trim().is_empty()) {
+            Some(location) => Ok(Some(location)),
+            None => Err("--db needs a database location".to_string()),
+        };
+    }
+    Ok(std::env::var("CTC_DB")
+        .ok()
+        .filter(|location| !location.trim().is_empty()))
+}